@@ -22,9 +22,9 @@ use grandpa_primitives::AuthorityId as GrandpaId;
 use hex_literal::hex;
 use node_runtime::{
 	constants::currency::*, wasm_binary_unwrap, AuthorityDiscoveryConfig, BabeConfig,
-	BalancesConfig, Block, CouncilConfig, DemocracyConfig, ElectionsConfig, GrandpaConfig,
-	ImOnlineConfig, IndicesConfig, MaxNominations, SessionConfig, SessionKeys, StakerStatus,
-	StakingConfig, SudoConfig, SystemConfig, TechnicalCommitteeConfig,
+	BalancesConfig, Block, CouncilConfig, DemocracyConfig, ElectionsConfig, FileStorageConfig,
+	GrandpaConfig, ImOnlineConfig, IndicesConfig, MaxNominations, SessionConfig, SessionKeys,
+	StakerStatus, StakingConfig, SudoConfig, SystemConfig, TechnicalCommitteeConfig,
 };
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use sc_chain_spec::{ChainSpecExtension, Properties};
@@ -316,7 +316,15 @@ pub fn testnet_genesis(
 		technical_membership: Default::default(),
 		treasury: Default::default(),
 		vesting: Default::default(),
-		file_storage: Default::default(),
+		file_storage: FileStorageConfig {
+			trust_anchors: vec![(
+				pallet_storage::IAS_ROOT_CA_SUBJECT.to_vec(),
+				pallet_storage::IAS_ROOT_CA_SPKI.to_vec(),
+				pallet_storage::IAS_ROOT_CA_NOT_BEFORE,
+				pallet_storage::IAS_ROOT_CA_NOT_AFTER,
+			)],
+			..Default::default()
+		},
 		transaction_storage: Default::default(),
 		transaction_payment: Default::default(),
 		nft: Default::default(),
@@ -94,6 +94,7 @@ pub fn create_extrinsic(
 		frame_system::CheckNonce::<node_runtime::Runtime>::from(nonce),
 		frame_system::CheckWeight::<node_runtime::Runtime>::new(),
 		pallet_transaction_payment::ChargeTransactionPayment::<node_runtime::Runtime>::from(tip),
+		pallet_storage::CheckStorageReport::<node_runtime::Runtime>::new(),
 	);
 
 	let raw_payload = node_runtime::SignedPayload::from_raw(
@@ -108,6 +109,7 @@ pub fn create_extrinsic(
 			(),
 			(),
 			(),
+			(),
 		),
 	);
 	let signature = raw_payload.using_encoded(|e| sender.sign(e));
@@ -770,6 +772,7 @@ mod tests {
 				let check_nonce = frame_system::CheckNonce::from(index);
 				let check_weight = frame_system::CheckWeight::new();
 				let payment = pallet_transaction_payment::ChargeTransactionPayment::from(0);
+				let check_storage_report = pallet_storage::CheckStorageReport::new();
 				let extra = (
 					check_non_zero_sender,
 					check_spec_version,
@@ -779,11 +782,22 @@ mod tests {
 					check_nonce,
 					check_weight,
 					payment,
+					check_storage_report,
 				);
 				let raw_payload = SignedPayload::from_raw(
 					function,
 					extra,
-					((), spec_version, transaction_version, genesis_hash, genesis_hash, (), (), ()),
+					(
+						(),
+						spec_version,
+						transaction_version,
+						genesis_hash,
+						genesis_hash,
+						(),
+						(),
+						(),
+						(),
+					),
 				);
 				let signature = raw_payload.using_encoded(|payload| signer.sign(payload));
 				let (function, extra, _) = raw_payload.deconstruct();
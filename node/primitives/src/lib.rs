@@ -56,6 +56,15 @@ pub type Hash = sp_core::H256;
 /// time scale is milliseconds.
 pub type Timestamp = u64;
 
+/// Identifier of nft class.
+pub type NftClassId = u32;
+
+/// Identifier of nft token in class.
+pub type NftTokenId = u32;
+
+/// Quantity of nft token.
+pub type NftQuantity = u64;
+
 /// Digest item type.
 pub type DigestItem = generic::DigestItem;
 /// Header type.
@@ -34,7 +34,9 @@
 use std::sync::Arc;
 
 use jsonrpsee::RpcModule;
-use node_primitives::{AccountId, Balance, Block, BlockNumber, Hash, Index};
+use node_primitives::{
+	AccountId, Balance, Block, BlockNumber, Hash, Index, NftClassId, NftQuantity, NftTokenId,
+};
 use sc_client_api::AuxStore;
 use sc_consensus_babe::{Config, Epoch};
 use sc_consensus_epochs::SharedEpochChanges;
@@ -109,7 +111,14 @@ where
 		+ 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
-	C::Api: pallet_nft_rpc::NFTRuntimeApi<Block, Balance>,
+	C::Api: pallet_nft_rpc::NFTRuntimeApi<
+		Block,
+		AccountId,
+		Balance,
+		NftClassId,
+		NftTokenId,
+		NftQuantity,
+	>,
 	C::Api: pallet_storage_rpc::FileStorageRuntimeApi<Block, AccountId, Balance, BlockNumber>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
@@ -25,7 +25,9 @@ use frame_system::{
 	EnsureRoot,
 };
 pub use node_primitives::{AccountId, Signature};
-use node_primitives::{AccountIndex, Balance, BlockNumber, Hash, Index, Moment};
+use node_primitives::{
+	AccountIndex, Balance, BlockNumber, Hash, Index, Moment, NftClassId, NftQuantity, NftTokenId,
+};
 use pallet_election_provider_multi_phase::SolutionAccuracyOf;
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
@@ -102,10 +104,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 410,
+	spec_version: 411,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -978,6 +980,7 @@ where
 			frame_system::CheckNonce::<Runtime>::from(nonce),
 			frame_system::CheckWeight::<Runtime>::new(),
 			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+			pallet_storage::CheckStorageReport::<Runtime>::new(),
 		);
 		let raw_payload = SignedPayload::new(call, extra)
 			.map_err(|e| {
@@ -1095,22 +1098,59 @@ parameter_types! {
 	pub const TokenDeposit: Balance = 1 * CENTS;
 	pub const MetaDataByteDeposit: Balance = 10 * MILLICENTS;
 	pub const RoyaltyRateLimit: Perbill = Perbill::from_percent(20);
+	pub const AttributeDepositBase: Balance = 1 * CENTS;
+	pub const NftKeyLimit: u32 = 32;
+	pub const NftValueLimit: u32 = 256;
+	pub const NftMaxAttributes: u32 = 64;
+	pub const NftMaxProofDepth: u32 = 32;
+	pub const NftVoucherMetadataLimit: u32 = 1024;
+	pub const NftMaxApprovals: u32 = 256;
+	pub const NftBatchLimit: u32 = 100;
 	pub const ClassIdIncLimit: u32 = 100;
 }
 
 impl pallet_nft::Config for Runtime {
 	type Event = Event;
-	type ClassId = u32;
-	type TokenId = u32;
-	type Quantity = u64;
+	type ClassId = NftClassId;
+	type TokenId = NftTokenId;
+	type Quantity = NftQuantity;
 	type Currency = Balances;
 	type ClassDeposit = ClassDeposit;
 	type TokenDeposit = TokenDeposit;
 	type MetaDataByteDeposit = MetaDataByteDeposit;
 	type RoyaltyRateLimit = RoyaltyRateLimit;
+	type AttributeDepositBase = AttributeDepositBase;
+	type KeyLimit = NftKeyLimit;
+	type ValueLimit = NftValueLimit;
+	type MaxAttributes = NftMaxAttributes;
+	type MaxProofDepth = NftMaxProofDepth;
+	type VoucherMetadataLimit = NftVoucherMetadataLimit;
+	type MaxApprovals = NftMaxApprovals;
+	type BatchLimit = NftBatchLimit;
+	type OnTokenTransfer = ();
+	type CanTransfer = ();
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as traits::Verify>::Signer;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Helper = NftBenchmarkHelper;
 	type WeightInfo = pallet_nft::weights::SubstrateWeight<Runtime>;
 }
 
+#[cfg(feature = "runtime-benchmarks")]
+pub struct NftBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_nft::BenchmarkHelper<<Signature as traits::Verify>::Signer, Signature>
+	for NftBenchmarkHelper
+{
+	fn sign(message: &[u8]) -> (<Signature as traits::Verify>::Signer, Signature) {
+		let public = sp_io::crypto::sr25519_generate(0.into(), None);
+		let signature = sp_io::crypto::sr25519_sign(0.into(), &public, message)
+			.expect("key was just generated; qed");
+		(public.into(), signature.into())
+	}
+}
+
 parameter_types! {
 	pub const OrderDeposit: Balance = 10 * CENTS;
 	pub const NftOrderPalletId: PalletId = PalletId(*b"nftordr*");
@@ -1145,17 +1185,38 @@ parameter_types! {
 	pub const SlashBalance: Balance = 100 * DOLLARS;
 	pub const SessionDuration: BlockNumber = HOURS;
 	pub const LiquidateDuration: u32 = 3 * DAYS;
+	pub const ColdLiquidateDuration: u32 = 30 * DAYS;
 	pub const MaxFileReplicas: u32 = 15;
 	pub const EffectiveFileReplicas: u32 = 9;
+	pub const FileSizeQuorum: u32 = 3;
+	pub const MaxReplicasPerRegion: u32 = 5;
+	pub const MaxReplicasPerOperator: u32 = 3;
+	pub const MaxNodeTagLength: u32 = 64;
 	pub const MaxFileSize: u64 = 1_073_741_824; // 1G
+	pub const MaxFileChunks: u32 = 64; // 64G
+	pub const MaxFileOwners: u32 = 8;
 	pub const MaxPower: u64 = 1_125_899_906_842_624; // 1P
 	pub const MaxReportFiles: u32 = 200;
+	pub const StorageReportPriority: TransactionPriority = TransactionPriority::max_value() / 2;
 	pub const FileBaseFee: Balance = 10 * MILLICENTS;
 	pub const FileSizePrice: Balance = 2 * MILLICENTS;
+	pub const ColdFileSizePrice: Balance = 5 * MILLICENTS;
 	pub const StoreRewardRatio: Perbill = Perbill::from_percent(50);
 	pub const StashBalance: Balance = 1000 * DOLLARS;
 	pub const MineFactor: Perbill = Perbill::from_parts(16629317);
 	pub const MaxMineReward: Balance = 342231348 * MILLICENTS;
+	pub const MinDelegation: Balance = 10 * DOLLARS;
+	pub const UnbondingDuration: BlockNumber = 7 * DAYS;
+	pub const MaxUnbondingChunks: u32 = 32;
+	pub const RewardLockDuration: BlockNumber = 0;
+	pub const MaxRewardChunks: u32 = 64;
+	pub const MaxStashControllers: u32 = 256;
+	pub const ChallengeBond: Balance = 10 * DOLLARS;
+	pub const ChallengeDuration: BlockNumber = HOURS;
+	pub const ChallengerRewardRatio: Perbill = Perbill::from_percent(50);
+	pub const MaxReportAge: u64 = 24 * 60 * 60; // 1 day
+	pub const AttestationValidity: BlockNumber = 30 * DAYS;
+	pub const AttestationWarningSessions: u32 = 24;
 }
 
 impl pallet_storage::Config for Runtime {
@@ -1167,17 +1228,38 @@ impl pallet_storage::Config for Runtime {
 	type SlashBalance = SlashBalance;
 	type SessionDuration = SessionDuration;
 	type LiquidateDuration = LiquidateDuration;
+	type ColdLiquidateDuration = ColdLiquidateDuration;
 	type MaxFileReplicas = MaxFileReplicas;
 	type MaxFileSize = MaxFileSize;
+	type MaxFileChunks = MaxFileChunks;
+	type MaxFileOwners = MaxFileOwners;
 	type MaxPower = MaxPower;
+	type ReportPriority = StorageReportPriority;
 	type MaxReportFiles = MaxReportFiles;
 	type EffectiveFileReplicas = EffectiveFileReplicas;
+	type FileSizeQuorum = FileSizeQuorum;
+	type MaxReplicasPerRegion = MaxReplicasPerRegion;
+	type MaxReplicasPerOperator = MaxReplicasPerOperator;
+	type MaxNodeTagLength = MaxNodeTagLength;
 	type FileBaseFee = FileBaseFee;
 	type FileSizePrice = FileSizePrice;
+	type ColdFileSizePrice = ColdFileSizePrice;
 	type StoreRewardRatio = StoreRewardRatio;
 	type StashBalance = StashBalance;
 	type MineFactor = MineFactor;
 	type MaxMineReward = MaxMineReward;
+	type MinDelegation = MinDelegation;
+	type UnbondingDuration = UnbondingDuration;
+	type MaxUnbondingChunks = MaxUnbondingChunks;
+	type RewardLockDuration = RewardLockDuration;
+	type MaxRewardChunks = MaxRewardChunks;
+	type MaxStashControllers = MaxStashControllers;
+	type ChallengeBond = ChallengeBond;
+	type ChallengeDuration = ChallengeDuration;
+	type ChallengerRewardRatio = ChallengerRewardRatio;
+	type MaxReportAge = MaxReportAge;
+	type AttestationValidity = AttestationValidity;
+	type AttestationWarningSessions = AttestationWarningSessions;
 	type WeightInfo = pallet_storage::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1292,6 +1374,7 @@ pub type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_storage::CheckStorageReport<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -1523,7 +1606,11 @@ impl_runtime_apis! {
 
 	impl pallet_nft_rpc_runtime_api::NFTApi<
 		Block,
-		Balance
+		AccountId,
+		Balance,
+		NftClassId,
+		NftTokenId,
+		NftQuantity
 	> for Runtime {
 		fn create_class_deposit(bytes_len: u32) -> pallet_nft_rpc_runtime_api::BalanceInfo<Balance> {
 			pallet_nft_rpc_runtime_api::BalanceInfo {
@@ -1535,6 +1622,24 @@ impl_runtime_apis! {
 			   amount: NFT::mint_token_deposit(bytes_len)
 			}
 		}
+		fn allowance(
+			class_id: NftClassId,
+			token_id: NftTokenId,
+			owner: AccountId,
+			operator: AccountId,
+		) -> pallet_nft_rpc_runtime_api::ApprovalInfo<NftQuantity> {
+			let (quantity, approved_for_all) = NFT::allowance(class_id, token_id, &owner, &operator);
+			pallet_nft_rpc_runtime_api::ApprovalInfo { quantity, approved_for_all }
+		}
+		fn attributes(
+			class_id: NftClassId,
+			token_id: Option<NftTokenId>,
+		) -> Vec<pallet_nft_rpc_runtime_api::AttributeInfo> {
+			NFT::attributes(class_id, token_id)
+				.into_iter()
+				.map(|(key, value)| pallet_nft_rpc_runtime_api::AttributeInfo { key, value })
+				.collect()
+		}
 	}
 
 	impl pallet_storage_rpc_runtime_api::FileStorageApi<
@@ -1543,14 +1648,26 @@ impl_runtime_apis! {
 		Balance,
 		BlockNumber
 	> for Runtime {
-		fn store_fee(file_size: u64, time: BlockNumber) -> pallet_storage_rpc_runtime_api::StoreFeeInfo<Balance> {
+		fn store_fee(file_size: u64, time: BlockNumber, class: pallet_storage::StorageClass) -> pallet_storage_rpc_runtime_api::StoreFeeInfo<Balance> {
 			pallet_storage_rpc_runtime_api::StoreFeeInfo {
-				fee: FileStorage::store_fee(file_size, time)
+				fee: FileStorage::store_fee(file_size, time, class)
 			}
 		}
 		fn node_deposit(controller: &AccountId) -> pallet_storage_rpc_runtime_api::NodeDepositInfo<Balance> {
 			FileStorage::node_deposit(controller)
 		}
+		fn stash_info(stash: &AccountId) -> pallet_storage_rpc_runtime_api::StashDepositInfo<AccountId, Balance> {
+			FileStorage::stash_info(stash)
+		}
+		fn file_diversity(cid: Vec<u8>) -> Option<pallet_storage_rpc_runtime_api::FileDiversityInfo> {
+			FileStorage::file_diversity(&cid)
+		}
+		fn sponsorship_budget(sponsor: &AccountId) -> pallet_storage_rpc_runtime_api::SponsorshipBudgetInfo<Balance> {
+			FileStorage::sponsorship_budget(sponsor)
+		}
+		fn account_usage(account: &AccountId) -> pallet_storage_rpc_runtime_api::AccountUsageInfo<Balance> {
+			FileStorage::account_usage(account)
+		}
 	}
 
 	impl sp_session::SessionKeys<Block> for Runtime {
@@ -34,6 +34,7 @@ fn create_nft<T: Config<I>, I: 'static>(
 		vec![0, 0, 0],
 		rate(10),
 		permission,
+		None,
 	));
 	let to: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(owner.clone());
 	let token_id = NextTokenId::<T, I>::get(&class_id);
@@ -654,8 +654,7 @@ pub mod pallet {
 			let class_id = auction.class_id;
 			let token_id = auction.token_id;
 			let quantity = auction.quantity;
-			pallet_nft::Pallet::<T, I>::unreserve(class_id, token_id, quantity, &auction_owner)?;
-			pallet_nft::Pallet::<T, I>::swap(
+			pallet_nft::Pallet::<T, I>::swap_reserved(
 				class_id,
 				token_id,
 				quantity,
@@ -728,8 +727,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let class_id = auction.class_id;
 		let token_id = auction.token_id;
 		let quantity = auction.quantity;
-		pallet_nft::Pallet::<T, I>::unreserve(class_id, token_id, quantity, auction_owner)?;
-		pallet_nft::Pallet::<T, I>::swap(
+		pallet_nft::Pallet::<T, I>::swap_reserved(
 			class_id,
 			token_id,
 			quantity,
@@ -4,7 +4,7 @@ use crate as pallet_nft_auction;
 use frame_support::{construct_runtime, parameter_types};
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
+	testing::{Header, TestSignature, UintAuthorityId},
 	traits::{BlakeTwo256, IdentityLookup},
 	Perbill,
 };
@@ -77,6 +77,14 @@ parameter_types! {
 	pub const TokenDeposit: u64 = 1;
 	pub const MetaDataByteDeposit: u64 = 1;
 	pub const RoyaltyRateLimit: Perbill = Perbill::from_percent(20);
+	pub const AttributeDepositBase: u64 = 1;
+	pub const KeyLimit: u32 = 8;
+	pub const ValueLimit: u32 = 16;
+	pub const MaxAttributes: u32 = 4;
+	pub const MaxProofDepth: u32 = 4;
+	pub const VoucherMetadataLimit: u32 = 16;
+	pub const MaxApprovals: u32 = 4;
+	pub const BatchLimit: u32 = 4;
 }
 
 impl pallet_nft::Config for Test {
@@ -89,9 +97,33 @@ impl pallet_nft::Config for Test {
 	type TokenDeposit = TokenDeposit;
 	type MetaDataByteDeposit = MetaDataByteDeposit;
 	type RoyaltyRateLimit = RoyaltyRateLimit;
+	type AttributeDepositBase = AttributeDepositBase;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type MaxAttributes = MaxAttributes;
+	type MaxProofDepth = MaxProofDepth;
+	type VoucherMetadataLimit = VoucherMetadataLimit;
+	type MaxApprovals = MaxApprovals;
+	type BatchLimit = BatchLimit;
+	type OnTokenTransfer = ();
+	type CanTransfer = ();
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Helper = MockBenchmarkHelper;
 	type WeightInfo = ();
 }
 
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_nft::BenchmarkHelper<UintAuthorityId, TestSignature> for MockBenchmarkHelper {
+	fn sign(message: &[u8]) -> (UintAuthorityId, TestSignature) {
+		(UintAuthorityId(1), TestSignature(1, message.to_vec()))
+	}
+}
+
 parameter_types! {
 	pub const AuctionDeposit: u64 = 10;
 	pub const AuctionFeeTaxRatio: Perbill = Perbill::from_percent(10);
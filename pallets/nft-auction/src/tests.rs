@@ -7,7 +7,7 @@ use pallet_nft::{ClassPermission, Error as NFTError, Permission};
 
 fn prepare_token() {
 	let permission = ClassPermission(Permission::Burnable | Permission::Transferable);
-	assert_ok!(NFT::create_class(Origin::signed(1), vec![], rate(10), permission));
+	assert_ok!(NFT::create_class(Origin::signed(1), vec![], rate(10), permission, None));
 	assert_ok!(NFT::mint(Origin::signed(1), 1, 0, 1, vec![], None, None));
 }
 
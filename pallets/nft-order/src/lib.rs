@@ -372,8 +372,7 @@ pub mod pallet {
 
 					let class_id = order.class_id;
 					let token_id = order.token_id;
-					pallet_nft::Pallet::<T, I>::unreserve(class_id, token_id, quantity, &seller)?;
-					pallet_nft::Pallet::<T, I>::swap(
+					pallet_nft::Pallet::<T, I>::swap_reserved(
 						class_id,
 						token_id,
 						quantity,
@@ -5,7 +5,7 @@ use frame_support::{assert_ok, construct_runtime, parameter_types};
 use pallet_nft::{ClassPermission, Permission};
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
+	testing::{Header, TestSignature, UintAuthorityId},
 	traits::{BlakeTwo256, IdentityLookup},
 	Perbill,
 };
@@ -78,6 +78,14 @@ parameter_types! {
 	pub const TokenDeposit: u64 = 1;
 	pub const MetaDataByteDeposit: u64 = 1;
 	pub const RoyaltyRateLimit: Perbill = Perbill::from_percent(20);
+	pub const AttributeDepositBase: u64 = 1;
+	pub const KeyLimit: u32 = 8;
+	pub const ValueLimit: u32 = 16;
+	pub const MaxAttributes: u32 = 4;
+	pub const MaxProofDepth: u32 = 4;
+	pub const VoucherMetadataLimit: u32 = 16;
+	pub const MaxApprovals: u32 = 4;
+	pub const BatchLimit: u32 = 4;
 }
 
 impl pallet_nft::Config for Test {
@@ -90,9 +98,33 @@ impl pallet_nft::Config for Test {
 	type TokenDeposit = TokenDeposit;
 	type MetaDataByteDeposit = MetaDataByteDeposit;
 	type RoyaltyRateLimit = RoyaltyRateLimit;
+	type AttributeDepositBase = AttributeDepositBase;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type MaxAttributes = MaxAttributes;
+	type MaxProofDepth = MaxProofDepth;
+	type VoucherMetadataLimit = VoucherMetadataLimit;
+	type MaxApprovals = MaxApprovals;
+	type BatchLimit = BatchLimit;
+	type OnTokenTransfer = ();
+	type CanTransfer = ();
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Helper = MockBenchmarkHelper;
 	type WeightInfo = ();
 }
 
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_nft::BenchmarkHelper<UintAuthorityId, TestSignature> for MockBenchmarkHelper {
+	fn sign(message: &[u8]) -> (UintAuthorityId, TestSignature) {
+		(UintAuthorityId(1), TestSignature(1, message.to_vec()))
+	}
+}
+
 parameter_types! {
 	pub const OrderDeposit: u64 = 10;
 	pub const MaxOrders: u32 = 50;
@@ -113,7 +145,13 @@ pub(crate) fn rate(v: u32) -> Perbill {
 
 pub(crate) fn add_class(caller: u32) -> u32 {
 	let permission = ClassPermission(Permission::Burnable | Permission::Transferable);
-	assert_ok!(NFT::create_class(Origin::signed(caller.into()), vec![], rate(10), permission));
+	assert_ok!(NFT::create_class(
+		Origin::signed(caller.into()),
+		vec![],
+		rate(10),
+		permission,
+		None
+	));
 	pallet_nft::NextClassId::<Test>::get() - 1
 }
 
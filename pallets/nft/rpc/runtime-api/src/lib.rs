@@ -18,6 +18,28 @@ pub struct BalanceInfo<Balance> {
 	pub amount: Balance,
 }
 
+/// Approvals operator holds on owner's token
+#[derive(Eq, PartialEq, Encode, Decode, Default, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ApprovalInfo<Quantity> {
+	/// Quantity of token operator can transfer
+	pub quantity: Quantity,
+	/// Whether operator can transfer all tokens of class
+	pub approved_for_all: bool,
+}
+
+/// Attribute of class or token
+#[derive(Eq, PartialEq, Encode, Decode, Default, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AttributeInfo {
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub key: Vec<u8>,
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub value: Vec<u8>,
+}
+
 #[cfg(feature = "std")]
 fn serialize_as_string<S: Serializer, T: std::fmt::Display>(
 	t: &T,
@@ -35,10 +57,22 @@ fn deserialize_from_string<'de, D: Deserializer<'de>, T: std::str::FromStr>(
 }
 
 sp_api::decl_runtime_apis! {
-	pub trait NFTApi<Balance> where Balance: Codec {
+	/// Version 2 adds `allowance` and `attributes`, methods of version 1 keep their encoding
+	#[api_version(2)]
+	pub trait NFTApi<AccountId, Balance, ClassId, TokenId, Quantity> where
+		AccountId: Codec,
+		Balance: Codec,
+		ClassId: Codec,
+		TokenId: Codec,
+		Quantity: Codec,
+	{
 		/// Get deposit for create nft class.
 		fn create_class_deposit(bytes_len: u32) -> BalanceInfo<Balance>;
 		/// Get deposit for mint nft token.
 		fn mint_token_deposit(bytes_len: u32) -> BalanceInfo<Balance>;
+		/// Get approvals operator holds on owner's token, since version 2.
+		fn allowance(class_id: ClassId, token_id: TokenId, owner: AccountId, operator: AccountId) -> ApprovalInfo<Quantity>;
+		/// Get all attributes of class, or of token if token id is given, since version 2.
+		fn attributes(class_id: ClassId, token_id: Option<TokenId>) -> Vec<AttributeInfo>;
 	}
 }
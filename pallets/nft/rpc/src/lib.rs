@@ -6,9 +6,9 @@ use jsonrpsee::{
 	proc_macros::rpc,
 	types::error::{CallError, ErrorObject},
 };
-use pallet_nft_rpc_runtime_api::BalanceInfo;
 pub use pallet_nft_rpc_runtime_api::NFTApi as NFTRuntimeApi;
-use sp_api::ProvideRuntimeApi;
+use pallet_nft_rpc_runtime_api::{ApprovalInfo, AttributeInfo, BalanceInfo};
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
@@ -16,12 +16,28 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 const RUNTIME_ERROR: i32 = 1;
 
 #[rpc(client, server)]
-pub trait NFTApi<Balance, ResponseType> {
+pub trait NFTApi<AccountId, Balance, ClassId, TokenId, ResponseType, ResponseApprovalType> {
 	#[method(name = "nft_createClassDeposit")]
 	fn create_class_deposit(&self, bytes_len: u32) -> RpcResult<ResponseType>;
 
 	#[method(name = "nft_mintTokenDeposit")]
 	fn mint_token_deposit(&self, bytes_len: u32) -> RpcResult<ResponseType>;
+
+	#[method(name = "nft_allowance")]
+	fn allowance(
+		&self,
+		class_id: ClassId,
+		token_id: TokenId,
+		owner: AccountId,
+		operator: AccountId,
+	) -> RpcResult<ResponseApprovalType>;
+
+	#[method(name = "nft_attributes")]
+	fn attributes(
+		&self,
+		class_id: ClassId,
+		token_id: Option<TokenId>,
+	) -> RpcResult<Vec<AttributeInfo>>;
 }
 
 /// A struct that implements the [`NFTApi`].
@@ -54,12 +70,18 @@ impl From<Error> for i64 {
 	}
 }
 
-impl<Client, Block, Balance> NFTApiServer<Balance, BalanceInfo<Balance>> for NFT<Client, Block>
+impl<Client, Block, AccountId, Balance, ClassId, TokenId, Quantity>
+	NFTApiServer<AccountId, Balance, ClassId, TokenId, BalanceInfo<Balance>, ApprovalInfo<Quantity>>
+	for NFT<Client, Block>
 where
 	Block: BlockT,
 	Client: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-	Client::Api: NFTRuntimeApi<Block, Balance>,
+	Client::Api: NFTRuntimeApi<Block, AccountId, Balance, ClassId, TokenId, Quantity>,
+	AccountId: Codec,
 	Balance: Codec + Copy + TryFrom<NumberOrHex> + Into<NumberOrHex>,
+	ClassId: Codec,
+	TokenId: Codec,
+	Quantity: Codec,
 {
 	fn create_class_deposit(&self, bytes_len: u32) -> RpcResult<BalanceInfo<Balance>> {
 		let api = self.client.runtime_api();
@@ -72,6 +94,47 @@ where
 		let at = BlockId::hash(self.client.info().best_hash);
 		api.mint_token_deposit(&at, bytes_len).map_err(runtime_error_into_rpc_err)
 	}
+
+	fn allowance(
+		&self,
+		class_id: ClassId,
+		token_id: TokenId,
+		owner: AccountId,
+		operator: AccountId,
+	) -> RpcResult<ApprovalInfo<Quantity>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		let version = api
+			.api_version::<dyn NFTRuntimeApi<Block, AccountId, Balance, ClassId, TokenId, Quantity>>(
+				&at,
+			)
+			.map_err(runtime_error_into_rpc_err)?
+			.unwrap_or(1);
+		if version < 2 {
+			return Err(runtime_error_into_rpc_err("Allowance is not supported"))
+		}
+		api.allowance(&at, class_id, token_id, owner, operator)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn attributes(
+		&self,
+		class_id: ClassId,
+		token_id: Option<TokenId>,
+	) -> RpcResult<Vec<AttributeInfo>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		let version = api
+			.api_version::<dyn NFTRuntimeApi<Block, AccountId, Balance, ClassId, TokenId, Quantity>>(
+				&at,
+			)
+			.map_err(runtime_error_into_rpc_err)?
+			.unwrap_or(1);
+		if version < 2 {
+			return Err(runtime_error_into_rpc_err("Attributes are not supported"))
+		}
+		api.attributes(&at, class_id, token_id).map_err(runtime_error_into_rpc_err)
+	}
 }
 
 /// Converts a runtime trap into an RPC error.
@@ -7,6 +7,7 @@ use frame_benchmarking::{
 };
 use frame_support::assert_ok;
 use frame_system::RawOrigin as SystemOrigin;
+use sp_core::{hashing::blake2_256, H256};
 use sp_runtime::{traits::Bounded, Perbill};
 use sp_std::prelude::*;
 
@@ -21,14 +22,24 @@ fn rate(v: u32) -> Perbill {
 fn new_class<T: Config<I>, I: 'static>() -> (T::ClassId, T::AccountId) {
 	let caller: T::AccountId = whitelisted_caller();
 	let permission = ClassPermission(
-		Permission::Burnable | Permission::Transferable | Permission::DelegateMintable,
+		Permission::Burnable |
+			Permission::Transferable |
+			Permission::DelegateMintable |
+			Permission::Mutable,
 	);
 	T::Currency::make_free_balance_be(&caller, BalanceOf::<T, I>::max_value());
+	// Limited class so that minting counts against limits
+	let mint_limit = MintLimit {
+		max_tokens: Some(T::Quantity::max_value()),
+		max_issuance: Some(T::Quantity::max_value()),
+		..Default::default()
+	};
 	assert_ok!(NFT::<T, I>::create_class(
 		SystemOrigin::Signed(caller.clone()).into(),
 		vec![0, 0, 0],
 		rate(10),
-		permission
+		permission,
+		Some(mint_limit)
 	));
 	let class_id = NextClassId::<T, I>::get().saturating_sub(One::one());
 	(class_id, caller)
@@ -56,6 +67,13 @@ fn mint_token<T: Config<I>, I: 'static>(
 	(token_id, quantity, caller)
 }
 
+fn merkle_root(leaf: H256, proof: &[H256]) -> H256 {
+	proof.iter().fold(leaf, |node, sibling| {
+		let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+		H256(blake2_256(&[left.as_bytes(), right.as_bytes()].concat()))
+	})
+}
+
 fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::Event) {
 	let events = frame_system::Pallet::<T>::events();
 	let system_event: <T as frame_system::Config>::Event = generic_event.into();
@@ -72,11 +90,58 @@ benchmarks_instance_pallet! {
 			Permission::Burnable | Permission::Transferable | Permission::DelegateMintable,
 		);
 		let class_id = NextClassId::<T, I>::get();
-	}: _(SystemOrigin::Signed(caller.clone()), vec![0, 0, 0], rate(10), permission)
+	}: _(SystemOrigin::Signed(caller.clone()), vec![0, 0, 0], rate(10), permission, None)
 	verify {
 		assert_last_event::<T, I>(Event::CreatedClass { class_id, owner: caller }.into());
 	}
 
+	transfer_class_ownership {
+		let t in 0 .. 100;
+		let (class_id, caller) = new_class::<T, I>();
+		for _ in 0 .. t {
+			mint_token::<T, I>(class_id, 1u32.into());
+		}
+		let target: T::AccountId = account("target", 0, SEED);
+		whitelist_account!(target);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(SystemOrigin::Signed(caller.clone()), class_id, target_lookup, t)
+	verify {
+		assert_last_event::<T, I>(Event::TransferredClass { class_id, from: caller, to: target }.into());
+	}
+
+	destroy_class {
+		let m in 0 .. 1000;
+		let v in 0 .. 1000;
+		let (class_id, caller) = new_class::<T, I>();
+		for i in 0 .. m {
+			let minter: T::AccountId = account("minter", i, SEED);
+			AllowlistMinted::<T, I>::insert(class_id, minter, T::Quantity::from(1u32));
+		}
+		AllowlistMinters::<T, I>::insert(class_id, m);
+		for nonce in 0 .. v {
+			UsedVoucherNonces::<T, I>::insert(class_id, nonce as u64, ());
+		}
+		UsedVoucherCounts::<T, I>::insert(class_id, v);
+	}: _(SystemOrigin::Signed(caller.clone()), class_id, m, v)
+	verify {
+		assert_last_event::<T, I>(Event::DestroyedClass { class_id, owner: caller }.into());
+	}
+
+	lock_mint {
+		let (class_id, caller) = new_class::<T, I>();
+	}: _(SystemOrigin::Signed(caller.clone()), class_id)
+	verify {
+		assert_last_event::<T, I>(Event::MintLocked { class_id }.into());
+	}
+
+	set_mint_allowlist {
+		let (class_id, caller) = new_class::<T, I>();
+		let root = H256::repeat_byte(1);
+	}: _(SystemOrigin::Signed(caller.clone()), class_id, Some(root))
+	verify {
+		assert_last_event::<T, I>(Event::MintAllowlistSet { class_id, root: Some(root) }.into());
+	}
+
 	mint {
 		let (class_id, caller) = new_class::<T, I>();
 		let to: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
@@ -89,7 +154,19 @@ benchmarks_instance_pallet! {
 		assert_last_event::<T, I>(Event::MintedToken { class_id, token_id, quantity, owner: caller.clone(), caller }.into());
 	}
 
+	mint_batch {
+		let n in 1 .. T::BatchLimit::get();
+		let (class_id, caller) = new_class::<T, I>();
+		let to: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
+		let items: Vec<_> = (0..n).map(|_| (1u32.into(), vec![0, 0, 0], Some(rate(10)))).collect();
+		let token_id = NextTokenId::<T, I>::get(&class_id).saturating_add((n - 1).into());
+	}: _(SystemOrigin::Signed(caller.clone()), to, class_id, items)
+	verify {
+		assert_last_event::<T, I>(Event::MintedToken { class_id, token_id, quantity: 1u32.into(), owner: caller.clone(), caller }.into());
+	}
+
 	delegate_mint {
+		let p in 0 .. T::MaxProofDepth::get();
 		let caller: T::AccountId = whitelisted_caller();
 		T::Currency::make_free_balance_be(&caller, BalanceOf::<T, I>::max_value());
 		let (class_id, owner) = new_class::<T, I>();
@@ -98,14 +175,52 @@ benchmarks_instance_pallet! {
 		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
 		let token_id = NextTokenId::<T, I>::get(&class_id);
 		whitelist_account!(beneficiary);
-	}: _(SystemOrigin::Signed(caller.clone()), class_id, quantity, vec![0, 0, 0], Some(rate(10)), Some(beneficiary))
+		let max_quantity: T::Quantity = 10u32.into();
+		let price: BalanceOf<T, I> = 1u32.into();
+		let leaf = H256(blake2_256(&(&caller, max_quantity, price).encode()));
+		let proof: Vec<H256> = (0..p).map(|i| H256::repeat_byte(i as u8)).collect();
+		assert_ok!(NFT::<T, I>::set_mint_allowlist(SystemOrigin::Signed(owner.clone()).into(), class_id, Some(merkle_root(leaf, &proof))));
+		let proof = AllowlistProof { max_quantity, price, proof };
+	}: _(SystemOrigin::Signed(caller.clone()), class_id, quantity, vec![0, 0, 0], Some(rate(10)), Some(beneficiary), Some(proof))
 	verify {
 		assert_last_event::<T, I>(Event::MintedToken { class_id, token_id, quantity, owner: caller.clone(), caller }.into());
 	}
 
+	redeem_voucher {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T, I>::max_value());
+		let class_id = NextClassId::<T, I>::get();
+		let voucher = MintVoucher {
+			class_id,
+			metadata: vec![0; T::VoucherMetadataLimit::get() as usize],
+			quantity: 1u32.into(),
+			price: 1u32.into(),
+			royalty_rate: Some(rate(10)),
+			expire_at: 10u32.into(),
+			nonce: 0,
+		};
+		let (public, signature) = T::Helper::sign(&NFT::<T, I>::voucher_message(&voucher));
+		let owner = public.into_account();
+		T::Currency::make_free_balance_be(&owner, BalanceOf::<T, I>::max_value());
+		assert_ok!(NFT::<T, I>::create_class(
+			SystemOrigin::Signed(owner).into(),
+			vec![0, 0, 0],
+			rate(10),
+			ClassPermission(Permission::Burnable | Permission::Transferable),
+			None
+		));
+	}: _(SystemOrigin::Signed(caller.clone()), voucher, signature)
+	verify {
+		assert_last_event::<T, I>(Event::RedeemedVoucher { class_id, nonce: 0, buyer: caller, price: 1u32.into() }.into());
+	}
+
 	burn {
+		let a in 0 .. T::MaxAttributes::get();
 		let (class_id, caller) = new_class::<T, I>();
 		let (token_id, quantity, ..) = mint_token::<T, I>(class_id, 1u32.into());
+		for i in 0 .. a {
+			assert_ok!(NFT::<T, I>::set_attribute(SystemOrigin::Signed(caller.clone()).into(), class_id, Some(token_id), i.encode(), vec![]));
+		}
 	}: _(SystemOrigin::Signed(caller.clone()), class_id, token_id, quantity)
 	verify {
 		assert_last_event::<T, I>(Event::BurnedToken{ class_id, token_id, quantity, owner: caller }.into());
@@ -130,6 +245,60 @@ benchmarks_instance_pallet! {
 		assert_last_event::<T, I>(Event::UpdatedToken { class_id, token_id }.into());
 	}
 
+	set_class_metadata {
+		let (class_id, caller) = new_class::<T, I>();
+	}: _(SystemOrigin::Signed(caller.clone()), class_id, vec![0; 64])
+	verify {
+		assert_last_event::<T, I>(Event::UpdatedClass { class_id }.into());
+	}
+
+	set_token_metadata {
+		let (class_id, caller) = new_class::<T, I>();
+		let (token_id, ..) = mint_token::<T, I>(class_id, 1u32.into());
+	}: _(SystemOrigin::Signed(caller.clone()), class_id, token_id, vec![0; 64])
+	verify {
+		assert_last_event::<T, I>(Event::UpdatedToken { class_id, token_id }.into());
+	}
+
+	freeze_metadata {
+		let (class_id, caller) = new_class::<T, I>();
+	}: _(SystemOrigin::Signed(caller.clone()), class_id)
+	verify {
+		assert_last_event::<T, I>(Event::FrozenMetadata { class_id }.into());
+	}
+
+	set_attribute {
+		let (class_id, caller) = new_class::<T, I>();
+		let (token_id, ..) = mint_token::<T, I>(class_id, 1u32.into());
+		let key = vec![0u8; T::KeyLimit::get() as usize];
+		let value = vec![0u8; T::ValueLimit::get() as usize];
+		assert_ok!(NFT::<T, I>::set_attribute(SystemOrigin::Signed(caller.clone()).into(), class_id, Some(token_id), key.clone(), vec![]));
+	}: _(SystemOrigin::Signed(caller.clone()), class_id, Some(token_id), key.clone(), value.clone())
+	verify {
+		assert_last_event::<T, I>(Event::AttributeSet { class_id, token_id: Some(token_id), key, value }.into());
+	}
+
+	clear_attribute {
+		let (class_id, caller) = new_class::<T, I>();
+		let (token_id, ..) = mint_token::<T, I>(class_id, 1u32.into());
+		let key = vec![0u8; T::KeyLimit::get() as usize];
+		let value = vec![0u8; T::ValueLimit::get() as usize];
+		assert_ok!(NFT::<T, I>::set_attribute(SystemOrigin::Signed(caller.clone()).into(), class_id, Some(token_id), key.clone(), value));
+	}: _(SystemOrigin::Signed(caller.clone()), class_id, Some(token_id), key.clone())
+	verify {
+		assert_last_event::<T, I>(Event::AttributeCleared { class_id, token_id: Some(token_id), key }.into());
+	}
+
+	set_attribute_manager {
+		let (class_id, caller) = new_class::<T, I>();
+		let manager: T::AccountId = account("manager", 0, SEED);
+		whitelist_account!(manager);
+		let manager_lookup = T::Lookup::unlookup(manager.clone());
+	}: _(SystemOrigin::Signed(caller.clone()), class_id, Some(manager_lookup))
+	verify {
+		assert_last_event::<T, I>(Event::AttributeManagerSet { class_id, manager: Some(manager) }.into());
+	}
+
 	transfer {
 		let (class_id, caller) = new_class::<T, I>();
 		let (token_id, quantity, ..) = mint_token::<T, I>(class_id, 1u32.into());
@@ -140,6 +309,72 @@ benchmarks_instance_pallet! {
 	verify {
 		assert_last_event::<T, I>(Event::TransferredToken { class_id, token_id, quantity, from: caller, to: target, reason: TransferReason::Direct, price: Zero::zero() }.into());
 	}
+
+	transfer_batch {
+		let n in 1 .. T::BatchLimit::get();
+		let (class_id, caller) = new_class::<T, I>();
+		let mut items = vec![];
+		let mut last = None;
+		for i in 0 .. n {
+			let (token_id, quantity, ..) = mint_token::<T, I>(class_id, 1u32.into());
+			let target: T::AccountId = account("target", i, SEED);
+			items.push((class_id, token_id, quantity, T::Lookup::unlookup(target.clone())));
+			last = Some((token_id, quantity, target));
+		}
+		let (token_id, quantity, target) = last.unwrap();
+	}: _(SystemOrigin::Signed(caller.clone()), items)
+	verify {
+		assert_last_event::<T, I>(Event::TransferredToken { class_id, token_id, quantity, from: caller, to: target, reason: TransferReason::Direct, price: Zero::zero() }.into());
+	}
+
+	approve {
+		let (class_id, caller) = new_class::<T, I>();
+		let (token_id, quantity, ..) = mint_token::<T, I>(class_id, 1u32.into());
+		let operator: T::AccountId = account("operator", 0, SEED);
+		whitelist_account!(operator);
+		let operator_lookup = T::Lookup::unlookup(operator.clone());
+	}: _(SystemOrigin::Signed(caller.clone()), class_id, token_id, operator_lookup, quantity)
+	verify {
+		assert_last_event::<T, I>(Event::Approved { class_id, token_id, owner: caller, operator, quantity }.into());
+	}
+
+	cancel_approval {
+		let (class_id, caller) = new_class::<T, I>();
+		let (token_id, quantity, ..) = mint_token::<T, I>(class_id, 1u32.into());
+		let operator: T::AccountId = account("operator", 0, SEED);
+		whitelist_account!(operator);
+		let operator_lookup = T::Lookup::unlookup(operator.clone());
+		assert_ok!(NFT::<T, I>::approve(SystemOrigin::Signed(caller.clone()).into(), class_id, token_id, operator_lookup.clone(), quantity));
+	}: _(SystemOrigin::Signed(caller.clone()), class_id, token_id, operator_lookup)
+	verify {
+		assert_last_event::<T, I>(Event::ApprovalCancelled { class_id, token_id, owner: caller, operator }.into());
+	}
+
+	set_approval_for_all {
+		let (class_id, caller) = new_class::<T, I>();
+		let operator: T::AccountId = account("operator", 0, SEED);
+		whitelist_account!(operator);
+		let operator_lookup = T::Lookup::unlookup(operator.clone());
+	}: _(SystemOrigin::Signed(caller.clone()), class_id, operator_lookup, true)
+	verify {
+		assert_last_event::<T, I>(Event::ApprovalForAll { class_id, owner: caller, operator, approved: true }.into());
+	}
+
+	transfer_from {
+		let (class_id, caller) = new_class::<T, I>();
+		let (token_id, quantity, ..) = mint_token::<T, I>(class_id, 1u32.into());
+		let operator: T::AccountId = account("operator", 0, SEED);
+		whitelist_account!(operator);
+		let operator_lookup = T::Lookup::unlookup(operator.clone());
+		assert_ok!(NFT::<T, I>::approve(SystemOrigin::Signed(caller.clone()).into(), class_id, token_id, operator_lookup, quantity));
+		let target: T::AccountId = account("target", 0, SEED);
+		whitelist_account!(target);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let target_lookup = T::Lookup::unlookup(target.clone());
+	}: _(SystemOrigin::Signed(operator.clone()), class_id, token_id, quantity, caller_lookup, target_lookup)
+	verify {
+		assert_last_event::<T, I>(Event::TransferredToken { class_id, token_id, quantity, from: caller, to: target, reason: TransferReason::Direct, price: Zero::zero() }.into());
+	}
 }
 
 impl_benchmark_test_suite!(NFT, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,203 @@
+//! Implementations for `nonfungibles` traits.
+//!
+//! A token is treated as a single item only while one account holds all of its quantity free,
+//! otherwise it has no owner and can't be transferred or burned through these traits.
+
+use super::*;
+use frame_support::traits::tokens::nonfungibles::{
+	Create, Inspect, InspectEnumerable, Mutate, Transfer,
+};
+use sp_std::boxed::Box;
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The only account holding all quantity of token free
+	fn sole_owner(class_id: T::ClassId, token_id: T::TokenId) -> Option<T::AccountId> {
+		let token_details = Tokens::<T, I>::get(class_id, token_id)?;
+		let mut owners = OwnersByToken::<T, I>::iter_key_prefix((class_id, token_id));
+		let owner = owners.next()?;
+		if owners.next().is_some() {
+			return None
+		}
+		let token_amount = TokensByOwner::<T, I>::get(&owner, (class_id, token_id))?;
+		if token_amount.free == token_details.quantity {
+			Some(owner)
+		} else {
+			None
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> Inspect<T::AccountId> for Pallet<T, I> {
+	type ItemId = T::TokenId;
+	type CollectionId = T::ClassId;
+
+	fn owner(collection: &Self::CollectionId, item: &Self::ItemId) -> Option<T::AccountId> {
+		Self::sole_owner(*collection, *item)
+	}
+
+	fn collection_owner(collection: &Self::CollectionId) -> Option<T::AccountId> {
+		Classes::<T, I>::get(collection).map(|c| c.owner)
+	}
+
+	fn attribute(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		key: &[u8],
+	) -> Option<Vec<u8>> {
+		Attributes::<T, I>::get((collection, Some(*item), key.to_vec())).map(|(value, ..)| value)
+	}
+
+	fn collection_attribute(collection: &Self::CollectionId, key: &[u8]) -> Option<Vec<u8>> {
+		Attributes::<T, I>::get((collection, None::<T::TokenId>, key.to_vec()))
+			.map(|(value, ..)| value)
+	}
+
+	fn can_transfer(collection: &Self::CollectionId, item: &Self::ItemId) -> bool {
+		match (Classes::<T, I>::get(collection), Tokens::<T, I>::get(collection, item)) {
+			(Some(class_details), Some(token_details)) =>
+				class_details.permission.0.contains(Permission::Transferable) &&
+					token_details.consumers == 0 &&
+					Self::sole_owner(*collection, *item).is_some(),
+			_ => false,
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> InspectEnumerable<T::AccountId> for Pallet<T, I> {
+	fn collections() -> Box<dyn Iterator<Item = Self::CollectionId>> {
+		Box::new(Classes::<T, I>::iter_keys())
+	}
+
+	fn items(collection: &Self::CollectionId) -> Box<dyn Iterator<Item = Self::ItemId>> {
+		Box::new(Tokens::<T, I>::iter_key_prefix(collection))
+	}
+
+	/// Tokens whose sole owner is `who`, partial or reserved holdings are skipped
+	fn owned(who: &T::AccountId) -> Box<dyn Iterator<Item = (Self::CollectionId, Self::ItemId)>> {
+		let who = who.clone();
+		Box::new(
+			TokensByOwner::<T, I>::iter_key_prefix(who.clone())
+				.filter(move |(c, t)| Self::sole_owner(*c, *t).as_ref() == Some(&who)),
+		)
+	}
+
+	fn owned_in_collection(
+		collection: &Self::CollectionId,
+		who: &T::AccountId,
+	) -> Box<dyn Iterator<Item = Self::ItemId>> {
+		let collection = *collection;
+		Box::new(
+			Self::owned(who).filter_map(move |(c, t)| if c == collection { Some(t) } else { None }),
+		)
+	}
+}
+
+impl<T: Config<I>, I: 'static> Create<T::AccountId> for Pallet<T, I> {
+	/// Create class with next available ID, `admin` is ignored as class has only an owner
+	fn create_collection(
+		collection: &Self::CollectionId,
+		who: &T::AccountId,
+		_admin: &T::AccountId,
+	) -> DispatchResult {
+		ensure!(*collection == NextClassId::<T, I>::get(), Error::<T, I>::NoAvailableClassId);
+		Self::do_create_class(
+			who.clone(),
+			Vec::new(),
+			Perbill::zero(),
+			ClassPermission(Permission::Burnable | Permission::Transferable),
+			None,
+		)?;
+		Ok(())
+	}
+}
+
+impl<T: Config<I>, I: 'static> Mutate<T::AccountId> for Pallet<T, I> {
+	/// Mint one unit of token with next available ID, deposit is reserved from class owner
+	fn mint_into(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		who: &T::AccountId,
+	) -> DispatchResult {
+		ensure!(*item == NextTokenId::<T, I>::get(collection), Error::<T, I>::NoAvailableTokenId);
+		Classes::<T, I>::try_mutate(collection, |maybe_class_details| -> DispatchResult {
+			let class_details = maybe_class_details.as_mut().ok_or(Error::<T, I>::ClassNotFound)?;
+			let owner = class_details.owner.clone();
+			Self::mint_token(
+				class_details,
+				&owner,
+				who,
+				*collection,
+				One::one(),
+				Vec::new(),
+				None,
+				None,
+			)
+		})
+	}
+
+	fn burn(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		maybe_check_owner: Option<&T::AccountId>,
+	) -> DispatchResult {
+		let owner = Self::sole_owner(*collection, *item).ok_or(Error::<T, I>::NoPermission)?;
+		if let Some(check_owner) = maybe_check_owner {
+			ensure!(check_owner == &owner, Error::<T, I>::NoPermission);
+		}
+		let quantity = Tokens::<T, I>::get(collection, item)
+			.ok_or(Error::<T, I>::TokenNotFound)?
+			.quantity;
+		Self::do_burn(owner, *collection, *item, quantity)
+	}
+
+	/// Set attribute of token, deposit is reserved from class owner
+	fn set_attribute(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		key: &[u8],
+		value: &[u8],
+	) -> DispatchResult {
+		let class_details = Classes::<T, I>::get(collection).ok_or(Error::<T, I>::ClassNotFound)?;
+		Self::do_set_attribute(
+			class_details.owner,
+			*collection,
+			Some(*item),
+			key.to_vec(),
+			value.to_vec(),
+		)
+	}
+
+	/// Set attribute of class, deposit is reserved from class owner
+	fn set_collection_attribute(
+		collection: &Self::CollectionId,
+		key: &[u8],
+		value: &[u8],
+	) -> DispatchResult {
+		let class_details = Classes::<T, I>::get(collection).ok_or(Error::<T, I>::ClassNotFound)?;
+		Self::do_set_attribute(class_details.owner, *collection, None, key.to_vec(), value.to_vec())
+	}
+}
+
+impl<T: Config<I>, I: 'static> Transfer<T::AccountId> for Pallet<T, I> {
+	fn transfer(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		destination: &T::AccountId,
+	) -> DispatchResult {
+		let owner = Self::sole_owner(*collection, *item).ok_or(Error::<T, I>::NoPermission)?;
+		let quantity = Tokens::<T, I>::get(collection, item)
+			.ok_or(Error::<T, I>::TokenNotFound)?
+			.quantity;
+		Self::ensure_transferable(*collection, *item, quantity, &owner)?;
+		Self::transfer_token(
+			*collection,
+			*item,
+			quantity,
+			&owner,
+			destination,
+			TransferReason::Direct,
+			Zero::zero(),
+		)?;
+		Ok(())
+	}
+}
@@ -3,6 +3,7 @@
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+mod impl_nonfungibles;
 #[cfg(test)]
 pub mod mock;
 #[cfg(test)]
@@ -16,13 +17,20 @@ pub use enumflags2::{bitflags, BitFlags};
 use frame_support::{
 	dispatch::{DispatchError, DispatchResult},
 	ensure,
-	traits::{Currency, ExistenceRequirement, Get, ReservableCurrency, WithdrawReasons},
+	traits::{
+		BalanceStatus, Currency, ExistenceRequirement, Get, PalletInfoAccess, ReservableCurrency,
+		WithdrawReasons,
+	},
 	transactional,
 };
 use frame_system::Config as SystemConfig;
 use scale_info::{build::Fields, meta_type, Path, Type, TypeInfo, TypeParameter};
+use sp_core::{hashing::blake2_256, H256};
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, One, Saturating, StaticLookup, Zero},
+	traits::{
+		AtLeast32BitUnsigned, CheckedAdd, CheckedSub, IdentifyAccount, One, Saturating,
+		StaticLookup, Verify, Zero,
+	},
 	Perbill, RuntimeDebug, SaturatedConversion,
 };
 use sp_std::prelude::*;
@@ -37,6 +45,18 @@ pub type ClassDetailsOf<T, I> =
 	ClassDetails<<T as SystemConfig>::AccountId, BalanceOf<T, I>, <T as Config<I>>::Quantity>;
 pub type TokenDetailsOf<T, I> =
 	TokenDetails<<T as SystemConfig>::AccountId, BalanceOf<T, I>, <T as Config<I>>::Quantity>;
+pub type MintLimitOf<T, I> =
+	MintLimit<<T as Config<I>>::Quantity, <T as SystemConfig>::BlockNumber>;
+pub type AllowlistProofOf<T, I> = AllowlistProof<<T as Config<I>>::Quantity, BalanceOf<T, I>>;
+pub type MintVoucherOf<T, I> = MintVoucher<
+	<T as Config<I>>::ClassId,
+	<T as Config<I>>::Quantity,
+	BalanceOf<T, I>,
+	<T as SystemConfig>::BlockNumber,
+>;
+
+/// Context prefixed to mint voucher before it is signed
+pub const VOUCHER_CONTEXT: &[u8] = b"deer/nft-voucher";
 
 // A value placed in storage that represents the current version of the Scheduler storage.
 // This value is used by the `on_runtime_upgrade` logic to determine whether we run
@@ -74,6 +94,8 @@ pub enum Permission {
 	Burnable = 0b00000010,
 	/// Token can be minted by user other than class owner
 	DelegateMintable = 0b00000100,
+	/// Metadata of class and tokens can be updated by class owner
+	Mutable = 0b00001000,
 }
 
 /// Type used to encode the number of references an token has.
@@ -154,6 +176,96 @@ pub struct TokenDetails<AccountId, Balance, Quantity> {
 	pub royalty_beneficiary: AccountId,
 }
 
+/// Limits on minting tokens of class
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
+pub struct MintLimit<Quantity, BlockNumber> {
+	/// Maximum kinds of tokens in class
+	pub max_tokens: Option<Quantity>,
+	/// Maximum amount of tokens in class
+	pub max_issuance: Option<Quantity>,
+	/// Minting is allowed from this block
+	pub start: Option<BlockNumber>,
+	/// Minting is not allowed from this block
+	pub end: Option<BlockNumber>,
+}
+
+/// Proof that `(account, max_quantity, price)` is a leaf of class's allowlist
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
+pub struct AllowlistProof<Quantity, Balance> {
+	/// Max quantity account can mint in total
+	pub max_quantity: Quantity,
+	/// Price of each token unit paid to class owner
+	pub price: Balance,
+	/// Sibling hashes from leaf to root
+	pub proof: Vec<H256>,
+}
+
+/// Voucher signed off-chain by class owner, redeemed by buyer to mint token
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct MintVoucher<ClassId, Quantity, Balance, BlockNumber> {
+	/// Class of token to mint
+	pub class_id: ClassId,
+	/// Metadata of token
+	pub metadata: Vec<u8>,
+	/// Quantity of token
+	pub quantity: Quantity,
+	/// Price paid to class owner
+	pub price: Balance,
+	/// Royalty rate of token, class royalty rate is used if none
+	pub royalty_rate: Option<Perbill>,
+	/// Voucher can't be redeemed after this block
+	pub expire_at: BlockNumber,
+	/// Unique number of voucher in class
+	pub nonce: u64,
+}
+
+/// Handler of token moves between accounts, `from` is none on mint and `to` is none on burn
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait OnTokenTransfer<AccountId, ClassId: Copy, TokenId: Copy, Quantity: Copy> {
+	/// Called after quantity of token was moved
+	fn on_token_transfer(
+		class_id: ClassId,
+		token_id: TokenId,
+		quantity: Quantity,
+		from: Option<&AccountId>,
+		to: Option<&AccountId>,
+	);
+}
+
+/// Check whether token can move between accounts, `from` is none on mint and `to` is none on burn
+pub trait CanTransfer<AccountId, ClassId: Copy, TokenId: Copy, Quantity: Copy> {
+	/// Return error to veto the move
+	fn can_transfer(
+		class_id: ClassId,
+		token_id: TokenId,
+		quantity: Quantity,
+		from: Option<&AccountId>,
+		to: Option<&AccountId>,
+	) -> DispatchResult;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl<AccountId, ClassId: Copy, TokenId: Copy, Quantity: Copy>
+	CanTransfer<AccountId, ClassId, TokenId, Quantity> for Tuple
+{
+	fn can_transfer(
+		class_id: ClassId,
+		token_id: TokenId,
+		quantity: Quantity,
+		from: Option<&AccountId>,
+		to: Option<&AccountId>,
+	) -> DispatchResult {
+		for_tuples!( #( Tuple::can_transfer(class_id, token_id, quantity, from, to)?; )* );
+		Ok(())
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<Public, Signature> {
+	/// Generate a key pair and sign message with it
+	fn sign(message: &[u8]) -> (Public, Signature);
+}
+
 /// Account Token
 #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
 pub struct TokenAmount<Quantity> {
@@ -228,6 +340,59 @@ pub mod pallet {
 		#[pallet::constant]
 		type RoyaltyRateLimit: Get<Perbill>;
 
+		/// The basic amount of funds that must be reserved for an attribute.
+		#[pallet::constant]
+		type AttributeDepositBase: Get<BalanceOf<Self, I>>;
+
+		/// The maximum length of attribute key.
+		#[pallet::constant]
+		type KeyLimit: Get<u32>;
+
+		/// The maximum length of attribute value.
+		#[pallet::constant]
+		type ValueLimit: Get<u32>;
+
+		/// The maximum number of attributes of class or of each token.
+		#[pallet::constant]
+		type MaxAttributes: Get<u32>;
+
+		/// The maximum number of sibling hashes in proof of mint allowlist.
+		#[pallet::constant]
+		type MaxProofDepth: Get<u32>;
+
+		/// The maximum length of token metadata in mint voucher.
+		#[pallet::constant]
+		type VoucherMetadataLimit: Get<u32>;
+
+		/// The maximum number of token and class approvals an owner holds.
+		#[pallet::constant]
+		type MaxApprovals: Get<u32>;
+
+		/// The maximum number of items in a batch call.
+		#[pallet::constant]
+		type BatchLimit: Get<u32>;
+
+		/// Handler called after token is transferred, minted or burned.
+		type OnTokenTransfer: OnTokenTransfer<
+			Self::AccountId,
+			Self::ClassId,
+			Self::TokenId,
+			Self::Quantity,
+		>;
+
+		/// Check whether token can be transferred, minted or burned.
+		type CanTransfer: CanTransfer<Self::AccountId, Self::ClassId, Self::TokenId, Self::Quantity>;
+
+		/// Off-chain signature of mint voucher.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+		/// Public key of off-chain signature, identified as class owner account.
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// Helper to sign mint voucher in benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type Helper: BenchmarkHelper<Self::OffchainPublic, Self::OffchainSignature>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -268,6 +433,114 @@ pub mod pallet {
 	pub type OwnersByToken<T: Config<I>, I: 'static = ()> =
 		StorageDoubleMap<_, Twox64Concat, (T::ClassId, T::TokenId), Twox64Concat, T::AccountId, ()>;
 
+	/// Quantity of token operator can transfer on behalf of owner, keyed by owner and
+	/// (class, token, operator)
+	#[pallet::storage]
+	#[pallet::getter(fn token_approvals)]
+	pub type TokenApprovals<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		(T::ClassId, T::TokenId, T::AccountId),
+		T::Quantity,
+	>;
+
+	/// Operators can transfer all tokens of class on behalf of owner, keyed by owner and
+	/// (class, operator)
+	#[pallet::storage]
+	#[pallet::getter(fn class_approvals)]
+	pub type ClassApprovals<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		(T::ClassId, T::AccountId),
+		(),
+	>;
+
+	/// Number of token and class approvals of owner. Approvals are keyed by owner so they are
+	/// kept when class is destroyed, class ID is never reused and owner can still cancel them
+	#[pallet::storage]
+	pub type ApprovalCounts<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Attributes of class or token, value is (attribute value, depositor, deposit)
+	#[pallet::storage]
+	pub type Attributes<T: Config<I>, I: 'static = ()> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, T::ClassId>,
+			NMapKey<Twox64Concat, Option<T::TokenId>>,
+			NMapKey<Blake2_128Concat, Vec<u8>>,
+		),
+		(Vec<u8>, T::AccountId, BalanceOf<T, I>),
+	>;
+
+	/// Number of attributes of class, or of token if token ID is given
+	#[pallet::storage]
+	pub type AttributeCounts<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::ClassId,
+		Twox64Concat,
+		Option<T::TokenId>,
+		u32,
+		ValueQuery,
+	>;
+
+	/// Account can set attributes of class on behalf of class owner
+	#[pallet::storage]
+	#[pallet::getter(fn attribute_managers)]
+	pub type AttributeManagers<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::ClassId, T::AccountId>;
+
+	/// Limits on minting tokens of class
+	#[pallet::storage]
+	#[pallet::getter(fn mint_limits)]
+	pub type MintLimits<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::ClassId, MintLimitOf<T, I>>;
+
+	/// Kinds and amount of tokens ever minted in class with mint limits, burning tokens does not
+	/// decrease them
+	#[pallet::storage]
+	pub type MintedTotals<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::ClassId, (T::Quantity, T::Quantity), ValueQuery>;
+
+	/// Merkle root of `(account, max_quantity, price)` leaves allowed to delegate mint
+	#[pallet::storage]
+	#[pallet::getter(fn mint_allowlists)]
+	pub type MintAllowlists<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::ClassId, H256>;
+
+	/// Quantity minted by account through allowlist of class
+	#[pallet::storage]
+	#[pallet::getter(fn allowlist_minted)]
+	pub type AllowlistMinted<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::ClassId,
+		Twox64Concat,
+		T::AccountId,
+		T::Quantity,
+		ValueQuery,
+	>;
+
+	/// Number of accounts minted through allowlist of class
+	#[pallet::storage]
+	pub type AllowlistMinters<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::ClassId, u32, ValueQuery>;
+
+	/// Nonces of redeemed mint vouchers
+	#[pallet::storage]
+	pub type UsedVoucherNonces<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, u64, ()>;
+
+	/// Number of redeemed voucher nonces of class
+	#[pallet::storage]
+	pub type UsedVoucherCounts<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::ClassId, u32, ValueQuery>;
+
 	/// Next available class ID.
 	#[pallet::storage]
 	#[pallet::getter(fn next_class_id)]
@@ -290,6 +563,21 @@ pub mod pallet {
 	pub enum Event<T: Config<I>, I: 'static = ()> {
 		/// An nft class was created.
 		CreatedClass { class_id: T::ClassId, owner: T::AccountId },
+		/// Ownership of nft class was transferred.
+		TransferredClass { class_id: T::ClassId, from: T::AccountId, to: T::AccountId },
+		/// An empty nft class was destroyed.
+		DestroyedClass { class_id: T::ClassId, owner: T::AccountId },
+		/// Minting tokens of class was locked.
+		MintLocked { class_id: T::ClassId },
+		/// Allowlist of delegate minting was set or removed.
+		MintAllowlistSet { class_id: T::ClassId, root: Option<H256> },
+		/// A mint voucher was redeemed.
+		RedeemedVoucher {
+			class_id: T::ClassId,
+			nonce: u64,
+			buyer: T::AccountId,
+			price: BalanceOf<T, I>,
+		},
 		/// A nft token was minted.
 		MintedToken {
 			class_id: T::ClassId,
@@ -317,6 +605,44 @@ pub mod pallet {
 		},
 		/// token info was updated
 		UpdatedToken { class_id: T::ClassId, token_id: T::TokenId },
+		/// class info was updated
+		UpdatedClass { class_id: T::ClassId },
+		/// Metadata of class and its tokens was frozen.
+		FrozenMetadata { class_id: T::ClassId },
+		/// An attribute of class or token was set.
+		AttributeSet {
+			class_id: T::ClassId,
+			token_id: Option<T::TokenId>,
+			key: Vec<u8>,
+			value: Vec<u8>,
+		},
+		/// An attribute of class or token was cleared.
+		AttributeCleared { class_id: T::ClassId, token_id: Option<T::TokenId>, key: Vec<u8> },
+		/// Attribute manager of class was set or removed.
+		AttributeManagerSet { class_id: T::ClassId, manager: Option<T::AccountId> },
+		/// An operator was approved to transfer quantity of token on behalf of owner.
+		Approved {
+			class_id: T::ClassId,
+			token_id: T::TokenId,
+			owner: T::AccountId,
+			operator: T::AccountId,
+			quantity: T::Quantity,
+		},
+		/// An approval of token was cancelled.
+		ApprovalCancelled {
+			class_id: T::ClassId,
+			token_id: T::TokenId,
+			owner: T::AccountId,
+			operator: T::AccountId,
+		},
+		/// An operator was approved or disapproved to transfer all tokens of class on behalf of
+		/// owner.
+		ApprovalForAll {
+			class_id: T::ClassId,
+			owner: T::AccountId,
+			operator: T::AccountId,
+			approved: bool,
+		},
 	}
 
 	#[pallet::error]
@@ -339,6 +665,46 @@ pub mod pallet {
 		NumOverflow,
 		/// At least one consumer is remaining so the token cannot be burend.
 		ConsumerRemaining,
+		/// Approved quantity is not enough
+		InsufficientApproval,
+		/// Class still has tokens
+		ClassNotEmpty,
+		/// Witness of tokens in class is smaller than the actual
+		BadWitness,
+		/// Attribute key is longer than KeyLimit
+		KeyTooLong,
+		/// Attribute value is longer than ValueLimit
+		ValueTooLong,
+		/// Attribute not found
+		AttributeNotFound,
+		/// Batch is empty or has more items than BatchLimit
+		InvalidBatch,
+		/// Mint start block is not before end block
+		InvalidMintWindow,
+		/// Class reached its max tokens or max issuance
+		MintLimitReached,
+		/// Minting is not open for class
+		MintNotOpen,
+		/// Proof of allowlist is missing, invalid or deeper than MaxProofDepth
+		InvalidProof,
+		/// Account minted more than allowlist allows
+		AllowlistExceeded,
+		/// Voucher is not signed by class owner
+		InvalidSignature,
+		/// Voucher is expired
+		VoucherExpired,
+		/// Voucher nonce was used
+		VoucherUsed,
+		/// Voucher metadata is longer than VoucherMetadataLimit
+		MetadataTooLong,
+		/// Reserved balance of class owner does not cover deposits of class
+		InsufficientDeposit,
+		/// Class or token has MaxAttributes attributes
+		TooManyAttributes,
+		/// Class still has attributes
+		AttributesRemaining,
+		/// Owner holds MaxApprovals approvals
+		TooManyApprovals,
 	}
 
 	#[pallet::genesis_config]
@@ -386,6 +752,8 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// Create NFT(non fungible token) class
+		///
+		/// - `mint_limit`: optional max supply and mint window of class
 		#[pallet::weight(T::WeightInfo::create_class())]
 		#[transactional]
 		pub fn create_class(
@@ -393,34 +761,109 @@ pub mod pallet {
 			metadata: Vec<u8>,
 			#[pallet::compact] royalty_rate: Perbill,
 			permission: ClassPermission,
+			mint_limit: Option<MintLimitOf<T, I>>,
 		) -> DispatchResult {
 			let owner = ensure_signed(origin)?;
-			ensure!(T::RoyaltyRateLimit::get() >= royalty_rate, Error::<T, I>::RoyaltyRateTooHigh);
-
-			let class_id =
-				NextClassId::<T, I>::try_mutate(|id| -> Result<T::ClassId, DispatchError> {
-					let current_id = *id;
-					*id = id.checked_add(&One::one()).ok_or(Error::<T, I>::NoAvailableClassId)?;
-					Ok(current_id)
-				})?;
+			Self::do_create_class(owner, metadata, royalty_rate, permission, mint_limit)?;
+			Ok(())
+		}
 
-			let deposit = Self::create_class_deposit(metadata.len().saturated_into());
+		/// Transfer ownership of class and reserved deposits of class and its tokens to another
+		/// account
+		///
+		/// - `tokens`: witness of tokens in class, must not be less than `total_tokens`
+		#[pallet::weight(T::WeightInfo::transfer_class_ownership(*tokens))]
+		#[transactional]
+		pub fn transfer_class_ownership(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+			to: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] tokens: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			Classes::<T, I>::try_mutate(&class_id, |maybe_class_details| -> DispatchResult {
+				let class_details =
+					maybe_class_details.as_mut().ok_or(Error::<T, I>::ClassNotFound)?;
+				ensure!(&who == &class_details.owner, Error::<T, I>::NoPermission);
+				ensure!(class_details.total_tokens <= tokens.into(), Error::<T, I>::BadWitness);
+				if who == to {
+					return Ok(())
+				}
+				let deposit = Tokens::<T, I>::iter_prefix_values(class_id)
+					.fold(class_details.deposit, |acc, token_details| {
+						acc.saturating_add(token_details.deposit)
+					});
+				let unmoved =
+					T::Currency::repatriate_reserved(&who, &to, deposit, BalanceStatus::Reserved)?;
+				ensure!(unmoved.is_zero(), Error::<T, I>::InsufficientDeposit);
+				class_details.owner = to.clone();
+				Self::deposit_event(Event::TransferredClass { class_id, from: who, to });
+				Ok(())
+			})
+		}
 
-			T::Currency::reserve(&owner, deposit)?;
+		/// Destroy class without tokens and unreserve its deposit
+		///
+		/// - `minters`: witness of accounts minted through allowlist, must not be less than
+		///   `AllowlistMinters`
+		/// - `vouchers`: witness of redeemed voucher nonces, must not be less than
+		///   `UsedVoucherCounts`
+		#[pallet::weight(T::WeightInfo::destroy_class(*minters, *vouchers))]
+		#[transactional]
+		pub fn destroy_class(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+			#[pallet::compact] minters: u32,
+			#[pallet::compact] vouchers: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let class_details =
+				Classes::<T, I>::get(&class_id).ok_or(Error::<T, I>::ClassNotFound)?;
+			ensure!(who == class_details.owner, Error::<T, I>::NoPermission);
+			ensure!(AllowlistMinters::<T, I>::get(&class_id) <= minters, Error::<T, I>::BadWitness);
+			ensure!(
+				UsedVoucherCounts::<T, I>::get(&class_id) <= vouchers,
+				Error::<T, I>::BadWitness
+			);
+			ensure!(class_details.total_tokens.is_zero(), Error::<T, I>::ClassNotEmpty);
+			ensure!(
+				Attributes::<T, I>::iter_prefix((class_id,)).next().is_none(),
+				Error::<T, I>::AttributesRemaining
+			);
 
-			let class_details = ClassDetails {
-				owner: owner.clone(),
-				deposit,
-				permission,
-				metadata,
-				total_tokens: Zero::zero(),
-				total_issuance: Zero::zero(),
-				royalty_rate,
-			};
+			T::Currency::unreserve(&who, class_details.deposit);
+			Classes::<T, I>::remove(&class_id);
+			NextTokenId::<T, I>::remove(&class_id);
+			AttributeManagers::<T, I>::remove(&class_id);
+			MintLimits::<T, I>::remove(&class_id);
+			MintedTotals::<T, I>::remove(&class_id);
+			MintAllowlists::<T, I>::remove(&class_id);
+			AllowlistMinted::<T, I>::drain_prefix(&class_id).for_each(drop);
+			AllowlistMinters::<T, I>::remove(&class_id);
+			UsedVoucherNonces::<T, I>::drain_prefix(&class_id).for_each(drop);
+			UsedVoucherCounts::<T, I>::remove(&class_id);
+			Self::deposit_event(Event::DestroyedClass { class_id, owner: who });
+			Ok(())
+		}
 
-			Classes::<T, I>::insert(class_id, class_details);
-			Self::deposit_event(Event::CreatedClass { class_id, owner });
-			Ok(().into())
+		/// Stop minting tokens of class permanently
+		#[pallet::weight(T::WeightInfo::lock_mint())]
+		pub fn lock_mint(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let class_details =
+				Classes::<T, I>::get(&class_id).ok_or(Error::<T, I>::ClassNotFound)?;
+			ensure!(who == class_details.owner, Error::<T, I>::NoPermission);
+			let now = frame_system::Pallet::<T>::block_number();
+			MintLimits::<T, I>::mutate(&class_id, |maybe_limit| {
+				let limit = maybe_limit.get_or_insert_with(Default::default);
+				limit.end = Some(limit.end.map_or(now, |end| end.min(now)));
+			});
+			Self::deposit_event(Event::MintLocked { class_id });
+			Ok(())
 		}
 
 		/// Mint NFT token by class owner
@@ -455,8 +898,90 @@ pub mod pallet {
 			})
 		}
 
+		/// Mint multiple NFT tokens by class owner, deposits of all tokens are reserved at once
+		///
+		/// - `items`: quantity, metadata and royalty rate of each token
+		#[pallet::weight(T::WeightInfo::mint_batch(items.len() as u32))]
+		#[transactional]
+		pub fn mint_batch(
+			origin: OriginFor<T>,
+			to: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] class_id: T::ClassId,
+			items: Vec<(T::Quantity, Vec<u8>, Option<Perbill>)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				!items.is_empty() && items.len() <= T::BatchLimit::get() as usize,
+				Error::<T, I>::InvalidBatch
+			);
+			ensure!(
+				items.iter().all(|(quantity, ..)| *quantity >= One::one()),
+				Error::<T, I>::InvalidQuantity
+			);
+			let to = T::Lookup::lookup(to)?;
+			Classes::<T, I>::try_mutate(&class_id, |maybe_class_details| -> DispatchResult {
+				let class_details =
+					maybe_class_details.as_mut().ok_or(Error::<T, I>::ClassNotFound)?;
+				ensure!(&who == &class_details.owner, Error::<T, I>::NoPermission);
+
+				let deposits: Vec<BalanceOf<T, I>> = items
+					.iter()
+					.map(|(_, metadata, _)| {
+						Self::mint_token_deposit(metadata.len().saturated_into())
+					})
+					.collect();
+				let total_deposit =
+					deposits.iter().fold(Zero::zero(), |acc: BalanceOf<T, I>, deposit| {
+						acc.saturating_add(*deposit)
+					});
+				T::Currency::reserve(&class_details.owner, total_deposit)?;
+
+				for ((quantity, metadata, royalty_rate), deposit) in
+					items.into_iter().zip(deposits.into_iter())
+				{
+					Self::insert_token(
+						class_details,
+						&who,
+						&to,
+						class_id,
+						quantity,
+						metadata,
+						royalty_rate,
+						None,
+						deposit,
+					)?;
+				}
+				Ok(())
+			})
+		}
+
+		/// Set or remove merkle root of accounts allowed to delegate mint, minted quantities are
+		/// kept when root changes
+		#[pallet::weight(T::WeightInfo::set_mint_allowlist())]
+		pub fn set_mint_allowlist(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+			root: Option<H256>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let class_details =
+				Classes::<T, I>::get(&class_id).ok_or(Error::<T, I>::ClassNotFound)?;
+			ensure!(who == class_details.owner, Error::<T, I>::NoPermission);
+			match root {
+				Some(root) => MintAllowlists::<T, I>::insert(&class_id, root),
+				None => MintAllowlists::<T, I>::remove(&class_id),
+			}
+			Self::deposit_event(Event::MintAllowlistSet { class_id, root });
+			Ok(())
+		}
+
 		/// Mint NFT token anyone else other than class owner
-		#[pallet::weight(T::WeightInfo::delegate_mint())]
+		///
+		/// - `proof`: required if class has an allowlist, price of leaf is paid to class owner
+		/// for each token unit
+		#[pallet::weight(T::WeightInfo::delegate_mint(
+			proof.as_ref().map_or(0, |proof| proof.proof.len() as u32)
+		))]
 		#[transactional]
 		pub fn delegate_mint(
 			origin: OriginFor<T>,
@@ -465,6 +990,7 @@ pub mod pallet {
 			metadata: Vec<u8>,
 			royalty_rate: Option<Perbill>,
 			royalty_beneficiary: Option<T::AccountId>,
+			proof: Option<AllowlistProofOf<T, I>>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			ensure!(quantity >= One::one(), Error::<T, I>::InvalidQuantity);
@@ -476,11 +1002,21 @@ pub mod pallet {
 					Error::<T, I>::NoPermission
 				);
 				let deposit = Self::mint_token_deposit(metadata.len().saturated_into());
+				let price = match MintAllowlists::<T, I>::get(&class_id) {
+					Some(root) => Self::spend_allowlist(
+						class_id,
+						&who,
+						quantity,
+						&root,
+						proof.ok_or(Error::<T, I>::InvalidProof)?,
+					)?,
+					None => Zero::zero(),
+				};
 
 				T::Currency::transfer(
 					&who,
 					&class_details.owner,
-					deposit,
+					deposit.saturating_add(price),
 					ExistenceRequirement::KeepAlive,
 				)?;
 				Self::mint_token(
@@ -496,84 +1032,81 @@ pub mod pallet {
 			})
 		}
 
-		/// Burn NFT token
-		#[pallet::weight(T::WeightInfo::burn())]
+		/// Mint NFT token to caller from voucher signed by class owner
+		///
+		/// Caller pays price and token deposit to class owner. Signed message is
+		/// `voucher_message`, which binds voucher to this chain and pallet instance.
+		#[pallet::weight(T::WeightInfo::redeem_voucher())]
 		#[transactional]
-		pub fn burn(
+		pub fn redeem_voucher(
 			origin: OriginFor<T>,
-			#[pallet::compact] class_id: T::ClassId,
-			#[pallet::compact] token_id: T::TokenId,
-			#[pallet::compact] quantity: T::Quantity,
+			voucher: MintVoucherOf<T, I>,
+			signature: T::OffchainSignature,
 		) -> DispatchResult {
-			let owner = ensure_signed(origin)?;
-			ensure!(quantity >= One::one(), Error::<T, I>::InvalidQuantity);
-
+			let who = ensure_signed(origin)?;
+			let class_id = voucher.class_id;
+			ensure!(voucher.quantity >= One::one(), Error::<T, I>::InvalidQuantity);
+			ensure!(
+				voucher.metadata.len() <= T::VoucherMetadataLimit::get() as usize,
+				Error::<T, I>::MetadataTooLong
+			);
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= voucher.expire_at,
+				Error::<T, I>::VoucherExpired
+			);
+			ensure!(
+				!UsedVoucherNonces::<T, I>::contains_key(class_id, voucher.nonce),
+				Error::<T, I>::VoucherUsed
+			);
 			Classes::<T, I>::try_mutate(&class_id, |maybe_class_details| -> DispatchResult {
 				let class_details =
 					maybe_class_details.as_mut().ok_or(Error::<T, I>::ClassNotFound)?;
-
+				let owner = class_details.owner.clone();
 				ensure!(
-					class_details.permission.0.contains(Permission::Burnable),
-					Error::<T, I>::NoPermission
+					signature.verify(&Self::voucher_message(&voucher)[..], &owner),
+					Error::<T, I>::InvalidSignature
 				);
+				UsedVoucherNonces::<T, I>::insert(class_id, voucher.nonce, ());
+				UsedVoucherCounts::<T, I>::mutate(class_id, |count| {
+					*count = count.saturating_add(1)
+				});
 
-				let token_details = Tokens::<T, I>::try_mutate_exists(
-					&class_id,
-					&token_id,
-					|maybe_token_details| -> Result<TokenDetailsOf<T, I>, DispatchError> {
-						let token_details =
-							maybe_token_details.as_mut().ok_or(Error::<T, I>::TokenNotFound)?;
-						token_details.quantity = token_details
-							.quantity
-							.checked_sub(&quantity)
-							.ok_or(Error::<T, I>::NumOverflow)?;
-						let copyed_token_details = token_details.clone();
-						if token_details.quantity.is_zero() {
-							*maybe_token_details = None;
-						}
-						Ok(copyed_token_details)
-					},
+				let MintVoucher { metadata, quantity, price, royalty_rate, nonce, .. } = voucher;
+				let deposit = Self::mint_token_deposit(metadata.len().saturated_into());
+				T::Currency::transfer(
+					&who,
+					&owner,
+					deposit.saturating_add(price),
+					ExistenceRequirement::KeepAlive,
 				)?;
-
-				ensure!(token_details.consumers == 0, Error::<T, I>::ConsumerRemaining);
-
-				if token_details.quantity.is_zero() {
-					T::Currency::unreserve(&class_details.owner, token_details.deposit);
-					class_details.total_tokens = class_details
-						.total_tokens
-						.checked_sub(&One::one())
-						.ok_or(Error::<T, I>::NumOverflow)?;
-				}
-
-				class_details.total_issuance = class_details
-					.total_issuance
-					.checked_sub(&quantity)
-					.ok_or(Error::<T, I>::NumOverflow)?;
-
-				TokensByOwner::<T, I>::try_mutate_exists(
-					owner.clone(),
-					(class_id, token_id),
-					|maybe_token_amount| -> DispatchResult {
-						let mut token_amount = maybe_token_amount.unwrap_or_default();
-						token_amount.free = token_amount
-							.free
-							.checked_sub(&quantity)
-							.ok_or(Error::<T, I>::NumOverflow)?;
-						if token_amount.free.is_zero() && token_amount.reserved.is_zero() {
-							*maybe_token_amount = None;
-							OwnersByToken::<T, I>::remove((class_id, token_id), owner.clone());
-						} else {
-							*maybe_token_amount = Some(token_amount);
-						}
-						Ok(())
-					},
+				Self::mint_token(
+					class_details,
+					&owner,
+					&who,
+					class_id,
+					quantity,
+					metadata,
+					royalty_rate,
+					Some(owner.clone()),
 				)?;
-
-				Self::deposit_event(Event::BurnedToken { class_id, token_id, quantity, owner });
-				Ok(().into())
+				Self::deposit_event(Event::RedeemedVoucher { class_id, nonce, buyer: who, price });
+				Ok(())
 			})
 		}
 
+		/// Burn NFT token, attributes of token are cleared when its last unit is burned
+		#[pallet::weight(T::WeightInfo::burn(T::MaxAttributes::get()))]
+		#[transactional]
+		pub fn burn(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+			#[pallet::compact] token_id: T::TokenId,
+			#[pallet::compact] quantity: T::Quantity,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			Self::do_burn(owner, class_id, token_id, quantity)
+		}
+
 		/// Update token royalty.
 		#[pallet::weight(T::WeightInfo::update_token_royalty())]
 		pub fn update_token_royalty(
@@ -632,28 +1165,171 @@ pub mod pallet {
 			)
 		}
 
-		/// Transfer NFT tokens to another account
-		///
-		/// - `to`: the token owner's account
-		/// - `class_id`: class id
-		/// - `token_id`: token id
-		/// - `quantity`: quantity
-		#[pallet::weight(T::WeightInfo::transfer())]
+		/// Update class metadata, reserved deposit of class owner is adjusted to new size
+		#[pallet::weight(T::WeightInfo::set_class_metadata())]
 		#[transactional]
-		pub fn transfer(
+		pub fn set_class_metadata(
 			origin: OriginFor<T>,
 			#[pallet::compact] class_id: T::ClassId,
-			#[pallet::compact] token_id: T::TokenId,
-			#[pallet::compact] quantity: T::Quantity,
-			to: <T::Lookup as StaticLookup>::Source,
+			metadata: Vec<u8>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let to = T::Lookup::lookup(to)?;
-			ensure!(quantity >= One::one(), Error::<T, I>::InvalidQuantity);
+			Classes::<T, I>::try_mutate(&class_id, |maybe_class_details| -> DispatchResult {
+				let class_details =
+					maybe_class_details.as_mut().ok_or(Error::<T, I>::ClassNotFound)?;
+				ensure!(who == class_details.owner, Error::<T, I>::NoPermission);
+				ensure!(
+					class_details.permission.0.contains(Permission::Mutable),
+					Error::<T, I>::NoPermission
+				);
+				let deposit = Self::create_class_deposit(metadata.len().saturated_into());
+				Self::adjust_deposit(&who, class_details.deposit, deposit)?;
+				class_details.deposit = deposit;
+				class_details.metadata = metadata;
 
-			Self::transfer_token(
-				class_id,
-				token_id,
+				Self::deposit_event(Event::UpdatedClass { class_id });
+				Ok(())
+			})
+		}
+
+		/// Update token metadata by class owner, reserved deposit of class owner is adjusted to
+		/// new size
+		#[pallet::weight(T::WeightInfo::set_token_metadata())]
+		#[transactional]
+		pub fn set_token_metadata(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+			#[pallet::compact] token_id: T::TokenId,
+			metadata: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let class_details =
+				Classes::<T, I>::get(&class_id).ok_or(Error::<T, I>::ClassNotFound)?;
+			ensure!(who == class_details.owner, Error::<T, I>::NoPermission);
+			ensure!(
+				class_details.permission.0.contains(Permission::Mutable),
+				Error::<T, I>::NoPermission
+			);
+			Tokens::<T, I>::try_mutate(
+				class_id,
+				token_id,
+				|maybe_token_details| -> DispatchResult {
+					let token_details =
+						maybe_token_details.as_mut().ok_or(Error::<T, I>::TokenNotFound)?;
+					let deposit = Self::mint_token_deposit(metadata.len().saturated_into());
+					Self::adjust_deposit(&who, token_details.deposit, deposit)?;
+					token_details.deposit = deposit;
+					token_details.metadata = metadata;
+
+					Self::deposit_event(Event::UpdatedToken { class_id, token_id });
+					Ok(())
+				},
+			)
+		}
+
+		/// Freeze metadata of class and its tokens, can not be undone
+		#[pallet::weight(T::WeightInfo::freeze_metadata())]
+		pub fn freeze_metadata(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Classes::<T, I>::try_mutate(&class_id, |maybe_class_details| -> DispatchResult {
+				let class_details =
+					maybe_class_details.as_mut().ok_or(Error::<T, I>::ClassNotFound)?;
+				ensure!(who == class_details.owner, Error::<T, I>::NoPermission);
+				ensure!(
+					class_details.permission.0.contains(Permission::Mutable),
+					Error::<T, I>::NoPermission
+				);
+				class_details.permission.0.remove(Permission::Mutable);
+
+				Self::deposit_event(Event::FrozenMetadata { class_id });
+				Ok(())
+			})
+		}
+
+		/// Set attribute of class, or of token if `token_id` is given, by class owner or attribute
+		/// manager. Deposit is reserved from caller
+		#[pallet::weight(T::WeightInfo::set_attribute())]
+		#[transactional]
+		pub fn set_attribute(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+			token_id: Option<T::TokenId>,
+			key: Vec<u8>,
+			value: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_attribute_manager(&who, class_id)?;
+			Self::do_set_attribute(who, class_id, token_id, key, value)
+		}
+
+		/// Clear attribute of class or token and unreserve its deposit
+		#[pallet::weight(T::WeightInfo::clear_attribute())]
+		#[transactional]
+		pub fn clear_attribute(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+			token_id: Option<T::TokenId>,
+			key: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_attribute_manager(&who, class_id)?;
+			let (_, depositor, deposit) = Attributes::<T, I>::take((class_id, token_id, &key))
+				.ok_or(Error::<T, I>::AttributeNotFound)?;
+			T::Currency::unreserve(&depositor, deposit);
+			AttributeCounts::<T, I>::mutate_exists(class_id, token_id, |maybe_count| {
+				*maybe_count = maybe_count.map(|count| count.saturating_sub(1)).filter(|c| *c > 0);
+			});
+
+			Self::deposit_event(Event::AttributeCleared { class_id, token_id, key });
+			Ok(())
+		}
+
+		/// Set or remove account can manage attributes of class
+		#[pallet::weight(T::WeightInfo::set_attribute_manager())]
+		pub fn set_attribute_manager(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+			manager: Option<<T::Lookup as StaticLookup>::Source>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let class_details =
+				Classes::<T, I>::get(&class_id).ok_or(Error::<T, I>::ClassNotFound)?;
+			ensure!(who == class_details.owner, Error::<T, I>::NoPermission);
+			let manager = manager.map(T::Lookup::lookup).transpose()?;
+			match &manager {
+				Some(manager) => AttributeManagers::<T, I>::insert(&class_id, manager),
+				None => AttributeManagers::<T, I>::remove(&class_id),
+			}
+
+			Self::deposit_event(Event::AttributeManagerSet { class_id, manager });
+			Ok(())
+		}
+
+		/// Transfer NFT tokens to another account
+		///
+		/// - `to`: the token owner's account
+		/// - `class_id`: class id
+		/// - `token_id`: token id
+		/// - `quantity`: quantity
+		#[pallet::weight(T::WeightInfo::transfer())]
+		#[transactional]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+			#[pallet::compact] token_id: T::TokenId,
+			#[pallet::compact] quantity: T::Quantity,
+			to: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			ensure!(quantity >= One::one(), Error::<T, I>::InvalidQuantity);
+
+			Self::transfer_token(
+				class_id,
+				token_id,
 				quantity,
 				&who,
 				&to,
@@ -662,6 +1338,137 @@ pub mod pallet {
 			)?;
 			Ok(())
 		}
+
+		/// Transfer multiple NFT tokens of caller
+		///
+		/// - `items`: class id, token id, quantity and receiver of each transfer
+		#[pallet::weight(T::WeightInfo::transfer_batch(items.len() as u32))]
+		#[transactional]
+		pub fn transfer_batch(
+			origin: OriginFor<T>,
+			items: Vec<(T::ClassId, T::TokenId, T::Quantity, <T::Lookup as StaticLookup>::Source)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				!items.is_empty() && items.len() <= T::BatchLimit::get() as usize,
+				Error::<T, I>::InvalidBatch
+			);
+			for (class_id, token_id, quantity, to) in items {
+				let to = T::Lookup::lookup(to)?;
+				ensure!(quantity >= One::one(), Error::<T, I>::InvalidQuantity);
+				Self::transfer_token(
+					class_id,
+					token_id,
+					quantity,
+					&who,
+					&to,
+					TransferReason::Direct,
+					Zero::zero(),
+				)?;
+			}
+			Ok(())
+		}
+
+		/// Approve operator to transfer quantity of token on behalf of caller, replaces previous
+		/// approval
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+			#[pallet::compact] token_id: T::TokenId,
+			operator: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] quantity: T::Quantity,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let operator = T::Lookup::lookup(operator)?;
+			ensure!(quantity >= One::one(), Error::<T, I>::InvalidQuantity);
+			ensure!(owner != operator, Error::<T, I>::NoPermission);
+			ensure!(Tokens::<T, I>::contains_key(class_id, token_id), Error::<T, I>::TokenNotFound);
+
+			if !TokenApprovals::<T, I>::contains_key(&owner, (class_id, token_id, &operator)) {
+				Self::inc_approvals(&owner)?;
+			}
+			TokenApprovals::<T, I>::insert(&owner, (class_id, token_id, &operator), quantity);
+			Self::deposit_event(Event::Approved { class_id, token_id, owner, operator, quantity });
+			Ok(())
+		}
+
+		/// Cancel approval of operator to transfer token on behalf of caller
+		#[pallet::weight(T::WeightInfo::cancel_approval())]
+		pub fn cancel_approval(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+			#[pallet::compact] token_id: T::TokenId,
+			operator: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let operator = T::Lookup::lookup(operator)?;
+			TokenApprovals::<T, I>::take(&owner, (class_id, token_id, &operator))
+				.ok_or(Error::<T, I>::InsufficientApproval)?;
+			Self::dec_approvals(&owner);
+			Self::deposit_event(Event::ApprovalCancelled { class_id, token_id, owner, operator });
+			Ok(())
+		}
+
+		/// Approve or disapprove operator to transfer all tokens of class on behalf of caller
+		#[pallet::weight(T::WeightInfo::set_approval_for_all())]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+			operator: <T::Lookup as StaticLookup>::Source,
+			approved: bool,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let operator = T::Lookup::lookup(operator)?;
+			ensure!(owner != operator, Error::<T, I>::NoPermission);
+
+			let exists = ClassApprovals::<T, I>::contains_key(&owner, (class_id, &operator));
+			if approved {
+				ensure!(Classes::<T, I>::contains_key(class_id), Error::<T, I>::ClassNotFound);
+				if !exists {
+					Self::inc_approvals(&owner)?;
+					ClassApprovals::<T, I>::insert(&owner, (class_id, &operator), ());
+				}
+			} else if exists {
+				// Approval of destroyed class can still be removed
+				ClassApprovals::<T, I>::remove(&owner, (class_id, &operator));
+				Self::dec_approvals(&owner);
+			}
+			Self::deposit_event(Event::ApprovalForAll { class_id, owner, operator, approved });
+			Ok(())
+		}
+
+		/// Transfer NFT tokens of another account approved to caller
+		///
+		/// - `from`: the token owner's account
+		/// - `to`: the receiver's account
+		#[pallet::weight(T::WeightInfo::transfer_from())]
+		#[transactional]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			#[pallet::compact] class_id: T::ClassId,
+			#[pallet::compact] token_id: T::TokenId,
+			#[pallet::compact] quantity: T::Quantity,
+			from: <T::Lookup as StaticLookup>::Source,
+			to: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let from = T::Lookup::lookup(from)?;
+			let to = T::Lookup::lookup(to)?;
+			ensure!(quantity >= One::one(), Error::<T, I>::InvalidQuantity);
+
+			Self::spend_approval(class_id, token_id, quantity, &from, &who)?;
+			Self::transfer_token(
+				class_id,
+				token_id,
+				quantity,
+				&from,
+				&to,
+				TransferReason::Direct,
+				Zero::zero(),
+			)?;
+			Ok(())
+		}
 	}
 }
 
@@ -678,6 +1485,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		if from == to || quantity.is_zero() {
 			return Ok(false)
 		}
+		T::CanTransfer::can_transfer(class_id, token_id, quantity, Some(from), Some(to))?;
 		let token = (class_id, token_id);
 
 		TokensByOwner::<T, I>::try_mutate_exists(
@@ -716,6 +1524,13 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					*maybe_from_amount = Some(from_amount);
 				}
 
+				T::OnTokenTransfer::on_token_transfer(
+					class_id,
+					token_id,
+					quantity,
+					Some(from),
+					Some(to),
+				);
 				Self::deposit_event(Event::TransferredToken {
 					class_id,
 					token_id,
@@ -731,6 +1546,265 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		)
 	}
 
+	/// Create class owned by `owner`, returns ID of the new class
+	#[transactional]
+	pub fn do_create_class(
+		owner: T::AccountId,
+		metadata: Vec<u8>,
+		royalty_rate: Perbill,
+		permission: ClassPermission,
+		mint_limit: Option<MintLimitOf<T, I>>,
+	) -> Result<T::ClassId, DispatchError> {
+		ensure!(T::RoyaltyRateLimit::get() >= royalty_rate, Error::<T, I>::RoyaltyRateTooHigh);
+		if let Some(MintLimit { start: Some(start), end: Some(end), .. }) = &mint_limit {
+			ensure!(start < end, Error::<T, I>::InvalidMintWindow);
+		}
+
+		let class_id =
+			NextClassId::<T, I>::try_mutate(|id| -> Result<T::ClassId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(&One::one()).ok_or(Error::<T, I>::NoAvailableClassId)?;
+				Ok(current_id)
+			})?;
+
+		let deposit = Self::create_class_deposit(metadata.len().saturated_into());
+
+		T::Currency::reserve(&owner, deposit)?;
+
+		let class_details = ClassDetails {
+			owner: owner.clone(),
+			deposit,
+			permission,
+			metadata,
+			total_tokens: Zero::zero(),
+			total_issuance: Zero::zero(),
+			royalty_rate,
+		};
+
+		Classes::<T, I>::insert(class_id, class_details);
+		if let Some(mint_limit) = mint_limit {
+			MintLimits::<T, I>::insert(class_id, mint_limit);
+		}
+		Self::deposit_event(Event::CreatedClass { class_id, owner });
+		Ok(class_id)
+	}
+
+	/// Burn quantity of token held by owner
+	#[transactional]
+	pub fn do_burn(
+		owner: T::AccountId,
+		class_id: T::ClassId,
+		token_id: T::TokenId,
+		quantity: T::Quantity,
+	) -> DispatchResult {
+		ensure!(quantity >= One::one(), Error::<T, I>::InvalidQuantity);
+		T::CanTransfer::can_transfer(class_id, token_id, quantity, Some(&owner), None)?;
+
+		Classes::<T, I>::try_mutate(&class_id, |maybe_class_details| -> DispatchResult {
+			let class_details = maybe_class_details.as_mut().ok_or(Error::<T, I>::ClassNotFound)?;
+
+			ensure!(
+				class_details.permission.0.contains(Permission::Burnable),
+				Error::<T, I>::NoPermission
+			);
+
+			let token_details = Tokens::<T, I>::try_mutate_exists(
+				&class_id,
+				&token_id,
+				|maybe_token_details| -> Result<TokenDetailsOf<T, I>, DispatchError> {
+					let token_details =
+						maybe_token_details.as_mut().ok_or(Error::<T, I>::TokenNotFound)?;
+					token_details.quantity = token_details
+						.quantity
+						.checked_sub(&quantity)
+						.ok_or(Error::<T, I>::NumOverflow)?;
+					let copyed_token_details = token_details.clone();
+					if token_details.quantity.is_zero() {
+						*maybe_token_details = None;
+					}
+					Ok(copyed_token_details)
+				},
+			)?;
+
+			ensure!(token_details.consumers == 0, Error::<T, I>::ConsumerRemaining);
+
+			if token_details.quantity.is_zero() {
+				T::Currency::unreserve(&class_details.owner, token_details.deposit);
+				class_details.total_tokens = class_details
+					.total_tokens
+					.checked_sub(&One::one())
+					.ok_or(Error::<T, I>::NumOverflow)?;
+				Self::clear_token_attributes(class_id, token_id);
+			}
+
+			class_details.total_issuance = class_details
+				.total_issuance
+				.checked_sub(&quantity)
+				.ok_or(Error::<T, I>::NumOverflow)?;
+
+			TokensByOwner::<T, I>::try_mutate_exists(
+				owner.clone(),
+				(class_id, token_id),
+				|maybe_token_amount| -> DispatchResult {
+					let mut token_amount = maybe_token_amount.unwrap_or_default();
+					token_amount.free = token_amount
+						.free
+						.checked_sub(&quantity)
+						.ok_or(Error::<T, I>::NumOverflow)?;
+					if token_amount.free.is_zero() && token_amount.reserved.is_zero() {
+						*maybe_token_amount = None;
+						OwnersByToken::<T, I>::remove((class_id, token_id), owner.clone());
+					} else {
+						*maybe_token_amount = Some(token_amount);
+					}
+					Ok(())
+				},
+			)?;
+
+			T::OnTokenTransfer::on_token_transfer(class_id, token_id, quantity, Some(&owner), None);
+			Self::deposit_event(Event::BurnedToken { class_id, token_id, quantity, owner });
+			Ok(().into())
+		})
+	}
+
+	/// Set attribute of class or token, deposit is reserved from depositor
+	#[transactional]
+	pub fn do_set_attribute(
+		depositor: T::AccountId,
+		class_id: T::ClassId,
+		token_id: Option<T::TokenId>,
+		key: Vec<u8>,
+		value: Vec<u8>,
+	) -> DispatchResult {
+		ensure!(key.len() <= T::KeyLimit::get() as usize, Error::<T, I>::KeyTooLong);
+		ensure!(value.len() <= T::ValueLimit::get() as usize, Error::<T, I>::ValueTooLong);
+		if let Some(token_id) = token_id {
+			ensure!(Tokens::<T, I>::contains_key(class_id, token_id), Error::<T, I>::TokenNotFound);
+		}
+
+		let deposit = T::AttributeDepositBase::get().saturating_add(Self::caculate_byes_deposit(
+			key.len().saturating_add(value.len()).saturated_into(),
+		));
+		match Attributes::<T, I>::get((class_id, token_id, &key)) {
+			Some((_, old_depositor, old_deposit)) => {
+				T::Currency::unreserve(&old_depositor, old_deposit);
+			},
+			None => AttributeCounts::<T, I>::try_mutate(
+				class_id,
+				token_id,
+				|count| -> DispatchResult {
+					ensure!(*count < T::MaxAttributes::get(), Error::<T, I>::TooManyAttributes);
+					*count = count.saturating_add(1);
+					Ok(())
+				},
+			)?,
+		}
+		T::Currency::reserve(&depositor, deposit)?;
+		Attributes::<T, I>::insert((class_id, token_id, &key), (value.clone(), depositor, deposit));
+
+		Self::deposit_event(Event::AttributeSet { class_id, token_id, key, value });
+		Ok(())
+	}
+
+	/// Remove all attributes of token and unreserve their deposits
+	fn clear_token_attributes(class_id: T::ClassId, token_id: T::TokenId) {
+		for (_, (_, depositor, deposit)) in
+			Attributes::<T, I>::drain_prefix((class_id, Some(token_id)))
+		{
+			T::Currency::unreserve(&depositor, deposit);
+		}
+		AttributeCounts::<T, I>::remove(class_id, Some(token_id));
+	}
+
+	/// Consume quantity of approval operator holds on owner's token, approvals for whole class are
+	/// not consumed
+	pub fn spend_approval(
+		class_id: T::ClassId,
+		token_id: T::TokenId,
+		quantity: T::Quantity,
+		owner: &T::AccountId,
+		operator: &T::AccountId,
+	) -> DispatchResult {
+		if owner == operator || ClassApprovals::<T, I>::contains_key(owner, (class_id, operator)) {
+			return Ok(())
+		}
+		TokenApprovals::<T, I>::try_mutate_exists(
+			owner,
+			(class_id, token_id, operator),
+			|maybe_approved| -> DispatchResult {
+				let approved = maybe_approved
+					.unwrap_or_default()
+					.checked_sub(&quantity)
+					.ok_or(Error::<T, I>::InsufficientApproval)?;
+				*maybe_approved = if approved.is_zero() {
+					Self::dec_approvals(owner);
+					None
+				} else {
+					Some(approved)
+				};
+				Ok(())
+			},
+		)
+	}
+
+	/// Count new approval of owner, bounded by MaxApprovals
+	fn inc_approvals(owner: &T::AccountId) -> DispatchResult {
+		ApprovalCounts::<T, I>::try_mutate(owner, |count| -> DispatchResult {
+			ensure!(*count < T::MaxApprovals::get(), Error::<T, I>::TooManyApprovals);
+			*count = count.saturating_add(1);
+			Ok(())
+		})
+	}
+
+	/// Uncount removed approval of owner
+	fn dec_approvals(owner: &T::AccountId) {
+		ApprovalCounts::<T, I>::mutate_exists(owner, |maybe_count| {
+			*maybe_count = maybe_count.and_then(|count| count.checked_sub(1)).filter(|c| *c > 0);
+		});
+	}
+
+	/// Quantity of token operator can transfer on behalf of owner and whether operator is approved
+	/// for whole class
+	pub fn allowance(
+		class_id: T::ClassId,
+		token_id: T::TokenId,
+		owner: &T::AccountId,
+		operator: &T::AccountId,
+	) -> (T::Quantity, bool) {
+		(
+			TokenApprovals::<T, I>::get(owner, (class_id, token_id, operator)).unwrap_or_default(),
+			ClassApprovals::<T, I>::contains_key(owner, (class_id, operator)),
+		)
+	}
+
+	/// Message class owner signs for voucher, `(VOUCHER_CONTEXT, genesis hash, pallet index,
+	/// voucher)` so it can not be replayed on other chains or instances
+	pub fn voucher_message(voucher: &MintVoucherOf<T, I>) -> Vec<u8> {
+		let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+		let pallet_index = <Self as PalletInfoAccess>::index() as u32;
+		(VOUCHER_CONTEXT, genesis_hash, pallet_index, voucher).encode()
+	}
+
+	/// All attributes of class, or of token if `token_id` is given
+	pub fn attributes(
+		class_id: T::ClassId,
+		token_id: Option<T::TokenId>,
+	) -> Vec<(Vec<u8>, Vec<u8>)> {
+		Attributes::<T, I>::iter_prefix((class_id, token_id))
+			.map(|(key, (value, ..))| (key, value))
+			.collect()
+	}
+
+	fn ensure_attribute_manager(who: &T::AccountId, class_id: T::ClassId) -> DispatchResult {
+		let class_details = Classes::<T, I>::get(&class_id).ok_or(Error::<T, I>::ClassNotFound)?;
+		ensure!(
+			who == &class_details.owner ||
+				AttributeManagers::<T, I>::get(&class_id).as_ref() == Some(who),
+			Error::<T, I>::NoPermission
+		);
+		Ok(())
+	}
+
 	pub fn ensure_transferable(
 		class_id: T::ClassId,
 		token_id: T::TokenId,
@@ -818,6 +1892,35 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	) -> DispatchResult {
 		let token = Tokens::<T, I>::get(class_id, token_id).ok_or(Error::<T, I>::TokenNotFound)?;
 		Self::transfer_token(class_id, token_id, quantity, from, to, reason, price)?;
+		Self::pay_swap(&token, from, to, price, tax_ratio)
+	}
+
+	/// Swap quantity previously reserved by `from`, `CanTransfer` is checked on settlement
+	#[transactional]
+	pub fn swap_reserved(
+		class_id: T::ClassId,
+		token_id: T::TokenId,
+		quantity: T::Quantity,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		price: BalanceOf<T, I>,
+		tax_ratio: Perbill,
+		reason: TransferReason,
+	) -> DispatchResult {
+		let token = Tokens::<T, I>::get(class_id, token_id).ok_or(Error::<T, I>::TokenNotFound)?;
+		Self::unreserve(class_id, token_id, quantity, from)?;
+		Self::transfer_token(class_id, token_id, quantity, from, to, reason, price)?;
+		Self::pay_swap(&token, from, to, price, tax_ratio)
+	}
+
+	/// Pay royalty and tax of the swap price from buyer, the rest goes to seller
+	fn pay_swap(
+		token: &TokenDetailsOf<T, I>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		price: BalanceOf<T, I>,
+		tax_ratio: Perbill,
+	) -> DispatchResult {
 		let mut royalty_fee = token.royalty_rate * price;
 		if royalty_fee < T::Currency::minimum_balance() &&
 			T::Currency::free_balance(&token.royalty_beneficiary).is_zero()
@@ -858,6 +1961,20 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		T::MetaDataByteDeposit::get().saturating_mul((bytes_len).into())
 	}
 
+	/// Reserve or unreserve the difference when deposit of owner changes
+	fn adjust_deposit(
+		owner: &T::AccountId,
+		old: BalanceOf<T, I>,
+		new: BalanceOf<T, I>,
+	) -> DispatchResult {
+		if new > old {
+			T::Currency::reserve(owner, new.saturating_sub(old))?;
+		} else {
+			T::Currency::unreserve(owner, old.saturating_sub(new));
+		}
+		Ok(())
+	}
+
 	fn mint_token(
 		class_details: &mut ClassDetailsOf<T, I>,
 		who: &T::AccountId,
@@ -867,6 +1984,101 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		metadata: Vec<u8>,
 		royalty_rate: Option<Perbill>,
 		royalty_beneficiary: Option<T::AccountId>,
+	) -> DispatchResult {
+		let deposit = Self::mint_token_deposit(metadata.len().saturated_into());
+		T::Currency::reserve(&class_details.owner, deposit)?;
+		Self::insert_token(
+			class_details,
+			who,
+			to,
+			class_id,
+			quantity,
+			metadata,
+			royalty_rate,
+			royalty_beneficiary,
+			deposit,
+		)
+	}
+
+	/// Verify account is in allowlist and record quantity it mints, returns price to pay
+	fn spend_allowlist(
+		class_id: T::ClassId,
+		who: &T::AccountId,
+		quantity: T::Quantity,
+		root: &H256,
+		proof: AllowlistProofOf<T, I>,
+	) -> Result<BalanceOf<T, I>, DispatchError> {
+		let AllowlistProof { max_quantity, price, proof } = proof;
+		ensure!(proof.len() <= T::MaxProofDepth::get() as usize, Error::<T, I>::InvalidProof);
+		let leaf = H256(blake2_256(&(who, max_quantity, price).encode()));
+		ensure!(Self::verify_merkle_proof(root, leaf, &proof), Error::<T, I>::InvalidProof);
+		AllowlistMinted::<T, I>::try_mutate(class_id, who, |minted| -> DispatchResult {
+			let new_minted = minted.checked_add(&quantity).ok_or(Error::<T, I>::NumOverflow)?;
+			ensure!(new_minted <= max_quantity, Error::<T, I>::AllowlistExceeded);
+			if minted.is_zero() {
+				AllowlistMinters::<T, I>::mutate(class_id, |count| {
+					*count = count.saturating_add(1)
+				});
+			}
+			*minted = new_minted;
+			Ok(())
+		})?;
+		let units: BalanceOf<T, I> = quantity.saturated_into::<u128>().saturated_into();
+		Ok(price.saturating_mul(units))
+	}
+
+	/// Whether leaf is in merkle tree of root, each pair of nodes is hashed in sorted order
+	pub fn verify_merkle_proof(root: &H256, leaf: H256, proof: &[H256]) -> bool {
+		let computed = proof.iter().fold(leaf, |node, sibling| {
+			let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+			H256(blake2_256(&[left.as_bytes(), right.as_bytes()].concat()))
+		});
+		&computed == root
+	}
+
+	/// Check minting a token of quantity is open and within limits of class, then count it in
+	/// minted totals of class
+	fn note_mint(class_id: T::ClassId, quantity: T::Quantity) -> DispatchResult {
+		let limit = match MintLimits::<T, I>::get(class_id) {
+			Some(limit) => limit,
+			None => return Ok(()),
+		};
+		MintedTotals::<T, I>::try_mutate(
+			class_id,
+			|(minted_tokens, minted_issuance)| -> DispatchResult {
+				let new_tokens =
+					minted_tokens.checked_add(&One::one()).ok_or(Error::<T, I>::NumOverflow)?;
+				let new_issuance =
+					minted_issuance.checked_add(&quantity).ok_or(Error::<T, I>::NumOverflow)?;
+				ensure!(
+					limit.max_tokens.map_or(true, |max| new_tokens <= max) &&
+						limit.max_issuance.map_or(true, |max| new_issuance <= max),
+					Error::<T, I>::MintLimitReached
+				);
+				let now = frame_system::Pallet::<T>::block_number();
+				ensure!(
+					limit.start.map_or(true, |start| now >= start) &&
+						limit.end.map_or(true, |end| now < end),
+					Error::<T, I>::MintNotOpen
+				);
+				*minted_tokens = new_tokens;
+				*minted_issuance = new_issuance;
+				Ok(())
+			},
+		)
+	}
+
+	/// Create token whose deposit was reserved from class owner
+	fn insert_token(
+		class_details: &mut ClassDetailsOf<T, I>,
+		who: &T::AccountId,
+		to: &T::AccountId,
+		class_id: T::ClassId,
+		quantity: T::Quantity,
+		metadata: Vec<u8>,
+		royalty_rate: Option<Perbill>,
+		royalty_beneficiary: Option<T::AccountId>,
+		deposit: BalanceOf<T, I>,
 	) -> DispatchResult {
 		NextTokenId::<T, I>::try_mutate(class_id, |id| -> DispatchResult {
 			let royalty_rate = royalty_rate.unwrap_or(class_details.royalty_rate);
@@ -884,13 +2096,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 			let token_id = *id;
 			*id = id.checked_add(&One::one()).ok_or(Error::<T, I>::NoAvailableTokenId)?;
+			T::CanTransfer::can_transfer(class_id, token_id, quantity, None, Some(to))?;
+			Self::note_mint(class_id, quantity)?;
 
 			class_details.total_tokens = total_tokens;
 			class_details.total_issuance = total_issuance;
 
-			let deposit = Self::mint_token_deposit(metadata.len().saturated_into());
-			T::Currency::reserve(&class_details.owner, deposit)?;
-
 			let token_details = TokenDetails {
 				creator: who.clone(),
 				metadata,
@@ -907,6 +2118,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				TokenAmount { free: quantity, reserved: Zero::zero() },
 			);
 			OwnersByToken::<T, I>::insert((class_id, token_id), &to, ());
+			T::OnTokenTransfer::on_token_transfer(class_id, token_id, quantity, None, Some(to));
 
 			Self::deposit_event(Event::MintedToken {
 				class_id,
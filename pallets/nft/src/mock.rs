@@ -6,7 +6,7 @@ use crate as pallet_nft;
 use frame_support::{assert_ok, construct_runtime, parameter_types};
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
+	testing::{Header, TestSignature, UintAuthorityId},
 	traits::{BlakeTwo256, IdentityLookup},
 };
 
@@ -77,6 +77,14 @@ parameter_types! {
 	pub const TokenDeposit: u64 = 1;
 	pub const MetaDataByteDeposit: u64 = 1;
 	pub const RoyaltyRateLimit: Perbill = Perbill::from_percent(20);
+	pub const AttributeDepositBase: u64 = 1;
+	pub const KeyLimit: u32 = 8;
+	pub const ValueLimit: u32 = 16;
+	pub const MaxAttributes: u32 = 4;
+	pub const MaxProofDepth: u32 = 4;
+	pub const VoucherMetadataLimit: u32 = 16;
+	pub const MaxApprovals: u32 = 4;
+	pub const BatchLimit: u32 = 4;
 }
 
 impl Config for Test {
@@ -89,9 +97,68 @@ impl Config for Test {
 	type TokenDeposit = TokenDeposit;
 	type MetaDataByteDeposit = MetaDataByteDeposit;
 	type RoyaltyRateLimit = RoyaltyRateLimit;
+	type AttributeDepositBase = AttributeDepositBase;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type MaxAttributes = MaxAttributes;
+	type MaxProofDepth = MaxProofDepth;
+	type VoucherMetadataLimit = VoucherMetadataLimit;
+	type MaxApprovals = MaxApprovals;
+	type BatchLimit = BatchLimit;
+	type OnTokenTransfer = TestTransferHook;
+	type CanTransfer = TestTransferHook;
+	type OffchainSignature = TestSignature;
+	type OffchainPublic = UintAuthorityId;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Helper = MockBenchmarkHelper;
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub static FrozenToken: Option<(u32, u32)> = None;
+	pub static TransferLog: Vec<(u32, u32, u32, Option<u64>, Option<u64>)> = vec![];
+}
+
+/// Records token moves and vetoes moves of `FrozenToken`
+pub struct TestTransferHook;
+
+impl OnTokenTransfer<u64, u32, u32, u32> for TestTransferHook {
+	fn on_token_transfer(
+		class_id: u32,
+		token_id: u32,
+		quantity: u32,
+		from: Option<&u64>,
+		to: Option<&u64>,
+	) {
+		let mut log = TransferLog::get();
+		log.push((class_id, token_id, quantity, from.copied(), to.copied()));
+		TransferLog::set(log);
+	}
+}
+
+impl CanTransfer<u64, u32, u32, u32> for TestTransferHook {
+	fn can_transfer(
+		class_id: u32,
+		token_id: u32,
+		_quantity: u32,
+		_from: Option<&u64>,
+		_to: Option<&u64>,
+	) -> DispatchResult {
+		ensure!(FrozenToken::get() != Some((class_id, token_id)), DispatchError::Other("Frozen"));
+		Ok(())
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<UintAuthorityId, TestSignature> for MockBenchmarkHelper {
+	fn sign(message: &[u8]) -> (UintAuthorityId, TestSignature) {
+		(UintAuthorityId(1), TestSignature(1, message.to_vec()))
+	}
+}
+
 pub(crate) fn rate(v: u32) -> Perbill {
 	Perbill::from_percent(v)
 }
@@ -102,7 +169,8 @@ pub(crate) fn add_class(caller: u32) -> u32 {
 		Origin::signed(caller.into()),
 		vec![0, 0, 0],
 		rate(5),
-		permission
+		permission,
+		None
 	));
 	NextClassId::<Test>::get() - 1
 }
@@ -2,14 +2,21 @@
 
 use super::*;
 use crate::mock::*;
-use frame_support::{assert_err, assert_ok, traits::Currency};
+use frame_support::{
+	assert_err, assert_noop, assert_ok,
+	traits::{
+		tokens::nonfungibles::{Create, Inspect, InspectEnumerable, Mutate, Transfer},
+		Currency,
+	},
+};
+use sp_runtime::testing::TestSignature;
 
 #[test]
 fn create_class_should_work() {
 	new_test_ext().execute_with(|| {
 		Balances::make_free_balance_be(&1, 100);
 		let permission = ClassPermission(Permission::Burnable | Permission::Transferable);
-		assert_ok!(NFT::create_class(Origin::signed(1), vec![0, 0, 0], rate(5), permission));
+		assert_ok!(NFT::create_class(Origin::signed(1), vec![0, 0, 0], rate(5), permission, None));
 		assert_eq!(Balances::reserved_balance(&1), 5);
 		let c = Classes::<Test>::get(0).unwrap();
 		assert_eq!(c.owner, 1);
@@ -20,12 +27,69 @@ fn create_class_should_work() {
 		assert_eq!(c.total_issuance, 0);
 		assert_eq!(c.royalty_rate, rate(5));
 		assert_err!(
-			NFT::create_class(Origin::signed(1), vec![0, 0, 0], rate(21), permission),
+			NFT::create_class(Origin::signed(1), vec![0, 0, 0], rate(21), permission, None),
 			Error::<Test>::RoyaltyRateTooHigh
 		);
 	});
 }
 
+#[test]
+fn transfer_class_ownership_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		Balances::make_free_balance_be(&2, 100);
+		add_class(1);
+		add_token(1, 0);
+		assert_ok!(NFT::mint(Origin::signed(1), 3, 0, 1, vec![], None, None));
+		assert_eq!(Balances::reserved_balance(&1), 10);
+		assert_err!(
+			NFT::transfer_class_ownership(Origin::signed(2), 0, 3, 2),
+			Error::<Test>::NoPermission
+		);
+		assert_err!(
+			NFT::transfer_class_ownership(Origin::signed(1), 0, 2, 1),
+			Error::<Test>::BadWitness
+		);
+		assert_ok!(NFT::transfer_class_ownership(Origin::signed(1), 0, 2, 2));
+		assert_eq!(Classes::<Test>::get(0).unwrap().owner, 2);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Balances::reserved_balance(&2), 10);
+		assert_eq!(TokensByOwner::<Test>::get(1, (0, 0)).unwrap().free, 2);
+
+		// Tokens burned by new owner unreserve its deposit
+		assert_ok!(NFT::burn(Origin::signed(3), 0, 1, 1));
+		assert_eq!(Balances::reserved_balance(&2), 9);
+
+		// Ownership is not moved when part of deposits is no longer reserved
+		Balances::make_free_balance_be(&3, 100);
+		let _ = Balances::slash_reserved(&2, 1);
+		assert_err!(
+			NFT::transfer_class_ownership(Origin::signed(2), 0, 3, 1),
+			Error::<Test>::InsufficientDeposit
+		);
+		assert_eq!(Classes::<Test>::get(0).unwrap().owner, 2);
+		assert_eq!(Balances::reserved_balance(&2), 8);
+		assert_eq!(Balances::reserved_balance(&3), 0);
+	});
+}
+
+#[test]
+fn destroy_class_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		add_class(1);
+		add_token(1, 0);
+		assert_err!(NFT::destroy_class(Origin::signed(2), 0, 0, 0), Error::<Test>::NoPermission);
+		assert_err!(NFT::destroy_class(Origin::signed(1), 0, 0, 0), Error::<Test>::ClassNotEmpty);
+		assert_ok!(NFT::burn(Origin::signed(1), 0, 0, 2));
+		assert_eq!(Balances::reserved_balance(&1), 5);
+		assert_ok!(NFT::destroy_class(Origin::signed(1), 0, 0, 0));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Classes::<Test>::get(0), None);
+		assert_err!(NFT::destroy_class(Origin::signed(1), 0, 0, 0), Error::<Test>::ClassNotFound);
+	});
+}
+
 #[test]
 fn mint_should_work() {
 	new_test_ext().execute_with(|| {
@@ -78,6 +142,153 @@ fn mint_should_work() {
 	});
 }
 
+#[test]
+fn mint_batch_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		add_class(1);
+		assert_ok!(NFT::mint_batch(
+			Origin::signed(1),
+			2,
+			0,
+			vec![(2, vec![0, 0, 1], None), (1, vec![], Some(rate(10)))]
+		));
+		assert_eq!(Balances::reserved_balance(&1), 10);
+		let t = Tokens::<Test>::get(0, 0).unwrap();
+		assert_eq!(t.deposit, 4);
+		assert_eq!(t.quantity, 2);
+		assert_eq!(t.royalty_rate, rate(5));
+		let t = Tokens::<Test>::get(0, 1).unwrap();
+		assert_eq!(t.deposit, 1);
+		assert_eq!(t.royalty_rate, rate(10));
+		assert_eq!(t.royalty_beneficiary, 2);
+		assert_eq!(TokensByOwner::<Test>::get(2, (0, 1)).unwrap().free, 1);
+		let c = Classes::<Test>::get(0).unwrap();
+		assert_eq!(c.total_tokens, 2);
+		assert_eq!(c.total_issuance, 3);
+
+		assert_err!(NFT::mint_batch(Origin::signed(1), 2, 0, vec![]), Error::<Test>::InvalidBatch);
+		assert_err!(
+			NFT::mint_batch(Origin::signed(1), 2, 0, vec![(1, vec![], None); 5]),
+			Error::<Test>::InvalidBatch
+		);
+		assert_err!(
+			NFT::mint_batch(Origin::signed(1), 2, 0, vec![(1, vec![], None), (0, vec![], None)]),
+			Error::<Test>::InvalidQuantity
+		);
+		assert_err!(
+			NFT::mint_batch(Origin::signed(2), 2, 0, vec![(1, vec![], None)]),
+			Error::<Test>::NoPermission
+		);
+
+		// Nothing is minted when one of items fails
+		assert_err!(
+			NFT::mint_batch(
+				Origin::signed(1),
+				2,
+				0,
+				vec![(1, vec![], None), (1, vec![], Some(rate(21)))]
+			),
+			Error::<Test>::RoyaltyRateTooHigh
+		);
+		assert_eq!(NextTokenId::<Test>::get(0), 2);
+		assert_eq!(Balances::reserved_balance(&1), 10);
+	});
+}
+
+#[test]
+fn mint_limit_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		let permission = ClassPermission(Permission::Transferable);
+		assert_err!(
+			NFT::create_class(
+				Origin::signed(1),
+				vec![],
+				rate(5),
+				permission,
+				Some(MintLimit { start: Some(5), end: Some(5), ..Default::default() })
+			),
+			Error::<Test>::InvalidMintWindow
+		);
+		assert_ok!(NFT::create_class(
+			Origin::signed(1),
+			vec![],
+			rate(5),
+			permission,
+			Some(MintLimit {
+				max_tokens: Some(2),
+				max_issuance: Some(3),
+				start: Some(2),
+				end: Some(10)
+			})
+		));
+		assert_err!(
+			NFT::mint(Origin::signed(1), 1, 0, 1, vec![], None, None),
+			Error::<Test>::MintNotOpen
+		);
+		System::set_block_number(2);
+		assert_err!(
+			NFT::mint(Origin::signed(1), 1, 0, 4, vec![], None, None),
+			Error::<Test>::MintLimitReached
+		);
+		assert_ok!(NFT::mint(Origin::signed(1), 1, 0, 2, vec![], None, None));
+		assert_err!(
+			NFT::mint_batch(Origin::signed(1), 1, 0, vec![(1, vec![], None), (1, vec![], None)]),
+			Error::<Test>::MintLimitReached
+		);
+		assert_ok!(NFT::mint(Origin::signed(1), 1, 0, 1, vec![], None, None));
+		assert_err!(
+			NFT::mint(Origin::signed(1), 1, 0, 1, vec![], None, None),
+			Error::<Test>::MintLimitReached
+		);
+
+		// Minting closes at end block
+		assert_ok!(NFT::create_class(
+			Origin::signed(1),
+			vec![],
+			rate(5),
+			permission,
+			Some(MintLimit { end: Some(10), ..Default::default() })
+		));
+		System::set_block_number(10);
+		assert_err!(
+			NFT::mint(Origin::signed(1), 1, 1, 1, vec![], None, None),
+			Error::<Test>::MintNotOpen
+		);
+
+		// Locked class can not mint any more
+		assert_ok!(NFT::create_class(Origin::signed(1), vec![], rate(5), permission, None));
+		assert_ok!(NFT::mint(Origin::signed(1), 1, 2, 1, vec![], None, None));
+		assert_err!(NFT::lock_mint(Origin::signed(2), 2), Error::<Test>::NoPermission);
+		assert_ok!(NFT::lock_mint(Origin::signed(1), 2));
+		assert_eq!(MintLimits::<Test>::get(2).unwrap().end, Some(10));
+		assert_err!(
+			NFT::mint(Origin::signed(1), 1, 2, 1, vec![], None, None),
+			Error::<Test>::MintNotOpen
+		);
+
+		// Burned tokens still count against limits
+		assert_ok!(NFT::create_class(
+			Origin::signed(1),
+			vec![],
+			rate(5),
+			ClassPermission(Permission::Burnable.into()),
+			Some(MintLimit { max_tokens: Some(1), max_issuance: Some(2), ..Default::default() })
+		));
+		assert_ok!(NFT::mint(Origin::signed(1), 1, 3, 2, vec![], None, None));
+		assert_ok!(NFT::burn(Origin::signed(1), 3, 0, 2));
+		assert_eq!(Classes::<Test>::get(3).unwrap().total_tokens, 0);
+		assert_eq!(MintedTotals::<Test>::get(3), (1, 2));
+		assert_err!(
+			NFT::mint(Origin::signed(1), 1, 3, 1, vec![], None, None),
+			Error::<Test>::MintLimitReached
+		);
+		assert_ok!(NFT::destroy_class(Origin::signed(1), 3, 0, 0));
+		assert_eq!(MintedTotals::<Test>::get(3), (0, 0));
+	});
+}
+
 #[test]
 fn delegate_mint_should_work() {
 	new_test_ext().execute_with(|| {
@@ -87,8 +298,8 @@ fn delegate_mint_should_work() {
 			Permission::Burnable | Permission::Transferable | Permission::DelegateMintable,
 		);
 
-		assert_ok!(NFT::create_class(Origin::signed(1), vec![0, 0, 0], rate(5), permission));
-		assert_ok!(NFT::delegate_mint(Origin::signed(2), 0, 2, vec![0, 0, 1], None, None));
+		assert_ok!(NFT::create_class(Origin::signed(1), vec![0, 0, 0], rate(5), permission, None));
+		assert_ok!(NFT::delegate_mint(Origin::signed(2), 0, 2, vec![0, 0, 1], None, None, None));
 		let t = Tokens::<Test>::get(0, 0).unwrap();
 		assert_eq!(t.creator, 2);
 		assert_eq!(Balances::free_balance(&1), 95);
@@ -101,6 +312,161 @@ fn delegate_mint_should_work() {
 	})
 }
 
+#[test]
+fn delegate_mint_with_allowlist_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		Balances::make_free_balance_be(&2, 100);
+		Balances::make_free_balance_be(&3, 100);
+		let permission = ClassPermission(
+			Permission::Transferable | Permission::DelegateMintable | Permission::Burnable,
+		);
+		assert_ok!(NFT::create_class(Origin::signed(1), vec![], rate(5), permission, None));
+
+		let leaf = |who: u64, max_quantity: u32, price: u64| {
+			H256(blake2_256(&(who, max_quantity, price).encode()))
+		};
+		let (leaf2, leaf3) = (leaf(2, 3, 10), leaf(3, 1, 0));
+		let (left, right) = if leaf2 <= leaf3 { (leaf2, leaf3) } else { (leaf3, leaf2) };
+		let root = H256(blake2_256(&[left.as_bytes(), right.as_bytes()].concat()));
+		assert_err!(
+			NFT::set_mint_allowlist(Origin::signed(2), 0, Some(root)),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NFT::set_mint_allowlist(Origin::signed(1), 0, Some(root)));
+		assert!(NFT::verify_merkle_proof(&root, leaf2, &[leaf3]));
+
+		let proof2 =
+			|max_quantity, price| AllowlistProof { max_quantity, price, proof: vec![leaf3] };
+		assert_err!(
+			NFT::delegate_mint(Origin::signed(2), 0, 1, vec![], None, None, None),
+			Error::<Test>::InvalidProof
+		);
+		assert_err!(
+			NFT::delegate_mint(Origin::signed(2), 0, 1, vec![], None, None, Some(proof2(3, 0))),
+			Error::<Test>::InvalidProof
+		);
+		let too_deep = AllowlistProof { max_quantity: 3, price: 10, proof: vec![leaf3; 5] };
+		assert_err!(
+			NFT::delegate_mint(Origin::signed(2), 0, 1, vec![], None, None, Some(too_deep)),
+			Error::<Test>::InvalidProof
+		);
+		assert_ok!(NFT::delegate_mint(
+			Origin::signed(2),
+			0,
+			2,
+			vec![],
+			None,
+			None,
+			Some(proof2(3, 10))
+		));
+		assert_eq!(AllowlistMinted::<Test>::get(0, 2), 2);
+		// deposit and price of 2 units are paid to class owner
+		assert_eq!(Balances::free_balance(&2), 79);
+		assert_eq!(Balances::free_balance(&1), 118);
+		assert_err!(
+			NFT::delegate_mint(Origin::signed(2), 0, 2, vec![], None, None, Some(proof2(3, 10))),
+			Error::<Test>::AllowlistExceeded
+		);
+		assert_ok!(NFT::delegate_mint(
+			Origin::signed(3),
+			0,
+			1,
+			vec![],
+			None,
+			None,
+			Some(AllowlistProof { max_quantity: 1, price: 0, proof: vec![leaf2] })
+		));
+
+		assert_eq!(AllowlistMinters::<Test>::get(0), 2);
+
+		// Anyone can delegate mint after allowlist is removed
+		assert_ok!(NFT::set_mint_allowlist(Origin::signed(1), 0, None));
+		assert_ok!(NFT::delegate_mint(Origin::signed(3), 0, 1, vec![], None, None, None));
+
+		// Destroying class clears minted quantities of allowlist
+		assert_ok!(NFT::burn(Origin::signed(2), 0, 0, 2));
+		assert_ok!(NFT::burn(Origin::signed(3), 0, 1, 1));
+		assert_ok!(NFT::burn(Origin::signed(3), 0, 2, 1));
+		assert_err!(NFT::destroy_class(Origin::signed(1), 0, 1, 0), Error::<Test>::BadWitness);
+		assert_ok!(NFT::destroy_class(Origin::signed(1), 0, 2, 0));
+		assert_eq!(AllowlistMinted::<Test>::get(0, 2), 0);
+		assert_eq!(AllowlistMinted::<Test>::get(0, 3), 0);
+		assert_eq!(AllowlistMinters::<Test>::get(0), 0);
+	});
+}
+
+#[test]
+fn redeem_voucher_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		Balances::make_free_balance_be(&2, 100);
+		add_class(1);
+		let voucher = |nonce| MintVoucher {
+			class_id: 0,
+			metadata: vec![0, 0, 1],
+			quantity: 2,
+			price: 10,
+			royalty_rate: None,
+			expire_at: 5,
+			nonce,
+		};
+		let sign = |signer, voucher: &MintVoucherOf<Test, ()>| {
+			TestSignature(signer, NFT::voucher_message(voucher))
+		};
+
+		assert_err!(
+			NFT::redeem_voucher(Origin::signed(2), voucher(0), sign(2, &voucher(0))),
+			Error::<Test>::InvalidSignature
+		);
+		assert_err!(
+			NFT::redeem_voucher(Origin::signed(2), voucher(0), sign(1, &voucher(1))),
+			Error::<Test>::InvalidSignature
+		);
+		// Voucher signed without context can not be redeemed
+		assert_err!(
+			NFT::redeem_voucher(
+				Origin::signed(2),
+				voucher(0),
+				TestSignature(1, voucher(0).encode())
+			),
+			Error::<Test>::InvalidSignature
+		);
+		let long_voucher = MintVoucher { metadata: vec![0; 17], ..voucher(2) };
+		assert_err!(
+			NFT::redeem_voucher(Origin::signed(2), long_voucher.clone(), sign(1, &long_voucher)),
+			Error::<Test>::MetadataTooLong
+		);
+		assert_ok!(NFT::redeem_voucher(Origin::signed(2), voucher(0), sign(1, &voucher(0))));
+		let t = Tokens::<Test>::get(0, 0).unwrap();
+		assert_eq!(t.creator, 1);
+		assert_eq!(t.royalty_beneficiary, 1);
+		assert_eq!(TokensByOwner::<Test>::get(2, (0, 0)).unwrap().free, 2);
+		// buyer pays price and deposit, deposit is reserved from class owner
+		assert_eq!(Balances::free_balance(&2), 86);
+		assert_eq!(Balances::free_balance(&1), 105);
+		assert_eq!(Balances::reserved_balance(&1), 9);
+
+		assert_err!(
+			NFT::redeem_voucher(Origin::signed(2), voucher(0), sign(1, &voucher(0))),
+			Error::<Test>::VoucherUsed
+		);
+		System::set_block_number(6);
+		assert_err!(
+			NFT::redeem_voucher(Origin::signed(2), voucher(1), sign(1, &voucher(1))),
+			Error::<Test>::VoucherExpired
+		);
+
+		// Used nonces are cleared with class
+		assert_eq!(UsedVoucherCounts::<Test>::get(0), 1);
+		assert_ok!(NFT::burn(Origin::signed(2), 0, 0, 2));
+		assert_err!(NFT::destroy_class(Origin::signed(1), 0, 0, 0), Error::<Test>::BadWitness);
+		assert_ok!(NFT::destroy_class(Origin::signed(1), 0, 0, 1));
+		assert!(!UsedVoucherNonces::<Test>::contains_key(0, 0));
+		assert_eq!(UsedVoucherCounts::<Test>::get(0), 0);
+	});
+}
+
 #[test]
 fn burn_should_works() {
 	new_test_ext().execute_with(|| {
@@ -188,6 +554,180 @@ fn update_token_royalty_beneficiary_should_work() {
 	})
 }
 
+#[test]
+fn set_metadata_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		let permission = ClassPermission(Permission::Transferable | Permission::Mutable);
+		assert_ok!(NFT::create_class(Origin::signed(1), vec![0, 0, 0], rate(5), permission, None));
+		add_token(1, 0);
+		assert_eq!(Balances::reserved_balance(&1), 9);
+
+		assert_ok!(NFT::set_class_metadata(Origin::signed(1), 0, vec![0; 5]));
+		let c = Classes::<Test>::get(0).unwrap();
+		assert_eq!(c.metadata, vec![0; 5]);
+		assert_eq!(c.deposit, 7);
+		assert_eq!(Balances::reserved_balance(&1), 11);
+
+		assert_ok!(NFT::set_token_metadata(Origin::signed(1), 0, 0, vec![1]));
+		let t = Tokens::<Test>::get(0, 0).unwrap();
+		assert_eq!(t.metadata, vec![1]);
+		assert_eq!(t.deposit, 2);
+		assert_eq!(Balances::reserved_balance(&1), 9);
+
+		assert_err!(
+			NFT::set_class_metadata(Origin::signed(2), 0, vec![]),
+			Error::<Test>::NoPermission
+		);
+		assert_err!(
+			NFT::set_token_metadata(Origin::signed(1), 0, 1, vec![]),
+			Error::<Test>::TokenNotFound
+		);
+
+		assert_ok!(NFT::freeze_metadata(Origin::signed(1), 0));
+		assert!(!Classes::<Test>::get(0).unwrap().permission.0.contains(Permission::Mutable));
+		assert_err!(
+			NFT::set_class_metadata(Origin::signed(1), 0, vec![]),
+			Error::<Test>::NoPermission
+		);
+		assert_err!(
+			NFT::set_token_metadata(Origin::signed(1), 0, 0, vec![]),
+			Error::<Test>::NoPermission
+		);
+		assert_err!(NFT::freeze_metadata(Origin::signed(1), 0), Error::<Test>::NoPermission);
+
+		// Class without mutable permission
+		add_class(1);
+		assert_err!(
+			NFT::set_class_metadata(Origin::signed(1), 1, vec![]),
+			Error::<Test>::NoPermission
+		);
+	});
+}
+
+#[test]
+fn attributes_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		Balances::make_free_balance_be(&2, 100);
+		add_class(1);
+		add_token(1, 0);
+		assert_eq!(Balances::reserved_balance(&1), 9);
+
+		assert_ok!(NFT::set_attribute(
+			Origin::signed(1),
+			0,
+			None,
+			b"kind".to_vec(),
+			b"sword".to_vec()
+		));
+		assert_eq!(Balances::reserved_balance(&1), 19);
+		assert_ok!(NFT::set_attribute(
+			Origin::signed(1),
+			0,
+			Some(0),
+			b"level".to_vec(),
+			b"1".to_vec()
+		));
+		assert_eq!(Balances::reserved_balance(&1), 26);
+		assert_eq!(
+			Attributes::<Test>::get((0, Some(0), b"level".to_vec())),
+			Some((b"1".to_vec(), 1, 7))
+		);
+		assert_eq!(NFT::attributes(0, Some(0)), vec![(b"level".to_vec(), b"1".to_vec())]);
+		assert_eq!(NFT::attributes(0, None), vec![(b"kind".to_vec(), b"sword".to_vec())]);
+
+		// Attribute manager pays for attributes it sets
+		assert_err!(
+			NFT::set_attribute(Origin::signed(2), 0, Some(0), b"level".to_vec(), b"2".to_vec()),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NFT::set_attribute_manager(Origin::signed(1), 0, Some(2)));
+		assert_ok!(NFT::set_attribute(
+			Origin::signed(2),
+			0,
+			Some(0),
+			b"level".to_vec(),
+			b"2".to_vec()
+		));
+		assert_eq!(Balances::reserved_balance(&1), 19);
+		assert_eq!(Balances::reserved_balance(&2), 7);
+		assert_eq!(NFT::attributes(0, Some(0)), vec![(b"level".to_vec(), b"2".to_vec())]);
+
+		assert_err!(
+			NFT::set_attribute(Origin::signed(1), 0, Some(1), b"level".to_vec(), vec![]),
+			Error::<Test>::TokenNotFound
+		);
+		assert_err!(
+			NFT::set_attribute(Origin::signed(1), 0, None, vec![0; 9], vec![]),
+			Error::<Test>::KeyTooLong
+		);
+		assert_err!(
+			NFT::set_attribute(Origin::signed(1), 0, None, vec![], vec![0; 17]),
+			Error::<Test>::ValueTooLong
+		);
+
+		assert_ok!(NFT::clear_attribute(Origin::signed(1), 0, Some(0), b"level".to_vec()));
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_err!(
+			NFT::clear_attribute(Origin::signed(1), 0, Some(0), b"level".to_vec()),
+			Error::<Test>::AttributeNotFound
+		);
+
+		// Burning last unit of token clears its attributes
+		assert_ok!(NFT::set_attribute(
+			Origin::signed(2),
+			0,
+			Some(0),
+			b"level".to_vec(),
+			b"3".to_vec()
+		));
+		assert_eq!(Balances::reserved_balance(&2), 7);
+		assert_ok!(NFT::burn(Origin::signed(1), 0, 0, 1));
+		assert_eq!(NFT::attributes(0, Some(0)), vec![(b"level".to_vec(), b"3".to_vec())]);
+		assert_ok!(NFT::burn(Origin::signed(1), 0, 0, 1));
+		assert!(NFT::attributes(0, Some(0)).is_empty());
+		assert_eq!(AttributeCounts::<Test>::get(0, Some(0)), 0);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+
+		// Class with attributes can not be destroyed
+		assert_err!(
+			NFT::destroy_class(Origin::signed(1), 0, 0, 0),
+			Error::<Test>::AttributesRemaining
+		);
+		assert_ok!(NFT::set_attribute_manager(Origin::signed(1), 0, None));
+		assert_err!(
+			NFT::clear_attribute(Origin::signed(2), 0, None, b"kind".to_vec()),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NFT::clear_attribute(Origin::signed(1), 0, None, b"kind".to_vec()));
+		assert_eq!(AttributeCounts::<Test>::get(0, None), 0);
+		assert_ok!(NFT::destroy_class(Origin::signed(1), 0, 0, 0));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+	});
+}
+
+#[test]
+fn max_attributes_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		add_class(1);
+		for key in 0..MaxAttributes::get() as u8 {
+			assert_ok!(NFT::set_attribute(Origin::signed(1), 0, None, vec![key], vec![]));
+		}
+		assert_eq!(AttributeCounts::<Test>::get(0, None), 4);
+		assert_err!(
+			NFT::set_attribute(Origin::signed(1), 0, None, vec![9], vec![]),
+			Error::<Test>::TooManyAttributes
+		);
+		// Existing attribute can be updated
+		assert_ok!(NFT::set_attribute(Origin::signed(1), 0, None, vec![0], vec![1]));
+		assert_ok!(NFT::clear_attribute(Origin::signed(1), 0, None, vec![0]));
+		assert_eq!(AttributeCounts::<Test>::get(0, None), 3);
+		assert_ok!(NFT::set_attribute(Origin::signed(1), 0, None, vec![9], vec![]));
+	});
+}
+
 #[test]
 fn transfer_should_work() {
 	new_test_ext().execute_with(|| {
@@ -214,6 +754,34 @@ fn transfer_should_work() {
 	});
 }
 
+#[test]
+fn transfer_batch_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		add_class(1);
+		add_token(1, 0);
+		add_token(1, 0);
+		assert_ok!(NFT::transfer_batch(Origin::signed(1), vec![(0, 0, 1, 2), (0, 1, 2, 3)]));
+		assert_eq!(TokensByOwner::<Test>::get(1, (0, 0)).unwrap().free, 1);
+		assert_eq!(TokensByOwner::<Test>::get(2, (0, 0)).unwrap().free, 1);
+		assert_eq!(TokensByOwner::<Test>::get(1, (0, 1)), None);
+		assert_eq!(TokensByOwner::<Test>::get(3, (0, 1)).unwrap().free, 2);
+
+		assert_err!(NFT::transfer_batch(Origin::signed(1), vec![]), Error::<Test>::InvalidBatch);
+		assert_err!(
+			NFT::transfer_batch(Origin::signed(1), vec![(0, 0, 0, 2)]),
+			Error::<Test>::InvalidQuantity
+		);
+
+		// Nothing is transferred when one of items fails
+		assert_err!(
+			NFT::transfer_batch(Origin::signed(1), vec![(0, 0, 1, 2), (0, 1, 1, 2)]),
+			Error::<Test>::TokenNotFound
+		);
+		assert_eq!(TokensByOwner::<Test>::get(1, (0, 0)).unwrap().free, 1);
+	});
+}
+
 #[test]
 fn reserve_unreserve_should_work() {
 	new_test_ext().execute_with(|| {
@@ -269,3 +837,205 @@ fn swap_should_work() {
 		assert_eq!(free3 - Balances::free_balance(&3), 100);
 	});
 }
+
+#[test]
+fn swap_reserved_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 1000);
+		Balances::make_free_balance_be(&2, 1000);
+		Balances::make_free_balance_be(&3, 1000);
+		add_class(1);
+		assert_ok!(NFT::mint(Origin::signed(1), 2, 0, 2, vec![0, 0, 1], None, None));
+		assert_ok!(NFT::update_token_royalty_beneficiary(Origin::signed(2), 0, 0, 1));
+		assert_ok!(NFT::reserve(0, 0, 2, &2));
+
+		// Veto raised after reserve still blocks settlement
+		FrozenToken::set(Some((0, 0)));
+		assert_noop!(
+			NFT::swap_reserved(0, 0, 2, &2, &3, 100, rate(1), TransferReason::Order),
+			DispatchError::Other("Frozen")
+		);
+		assert_eq!(TokensByOwner::<Test>::get(2, (0, 0)).unwrap().reserved, 2);
+		FrozenToken::set(None);
+
+		let free1 = Balances::free_balance(&1);
+		let free2 = Balances::free_balance(&2);
+		let free3 = Balances::free_balance(&3);
+		assert_ok!(NFT::swap_reserved(0, 0, 2, &2, &3, 100, rate(1), TransferReason::Order));
+		assert_eq!(TokensByOwner::<Test>::get(2, (0, 0)), None);
+		assert_eq!(TokensByOwner::<Test>::get(3, (0, 0)).unwrap().free, 2);
+		assert_eq!(Balances::free_balance(&1) - free1, 5);
+		assert_eq!(Balances::free_balance(&2) - free2, 94);
+		assert_eq!(free3 - Balances::free_balance(&3), 100);
+		assert_eq!(TransferLog::get(), vec![(0, 0, 2, None, Some(2)), (0, 0, 2, Some(2), Some(3))]);
+	});
+}
+
+#[test]
+fn approve_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		add_class(1);
+		add_token(1, 0);
+		assert_ok!(NFT::approve(Origin::signed(1), 0, 0, 2, 1));
+		assert_eq!(TokenApprovals::<Test>::get(1, (0, 0, 2)), Some(1));
+		assert_eq!(NFT::allowance(0, 0, &1, &2), (1, false));
+		assert_ok!(NFT::approve(Origin::signed(1), 0, 0, 2, 2));
+		assert_eq!(NFT::allowance(0, 0, &1, &2), (2, false));
+		assert_ok!(NFT::cancel_approval(Origin::signed(1), 0, 0, 2));
+		assert_eq!(TokenApprovals::<Test>::get(1, (0, 0, 2)), None);
+
+		assert_ok!(NFT::set_approval_for_all(Origin::signed(1), 0, 3, true));
+		assert_eq!(NFT::allowance(0, 0, &1, &3), (0, true));
+		assert_ok!(NFT::set_approval_for_all(Origin::signed(1), 0, 3, false));
+		assert_eq!(ClassApprovals::<Test>::get(1, (0, 3)), None);
+
+		assert_err!(NFT::approve(Origin::signed(1), 0, 0, 2, 0), Error::<Test>::InvalidQuantity);
+		assert_err!(NFT::approve(Origin::signed(1), 0, 0, 1, 1), Error::<Test>::NoPermission);
+		assert_err!(NFT::approve(Origin::signed(1), 0, 1, 2, 1), Error::<Test>::TokenNotFound);
+		assert_err!(
+			NFT::cancel_approval(Origin::signed(1), 0, 0, 2),
+			Error::<Test>::InsufficientApproval
+		);
+		assert_err!(
+			NFT::set_approval_for_all(Origin::signed(1), 1, 3, true),
+			Error::<Test>::ClassNotFound
+		);
+		assert_eq!(ApprovalCounts::<Test>::get(1), 0);
+
+		// Approvals of owner are bounded, approving again is not counted
+		for operator in 2..6 {
+			assert_ok!(NFT::set_approval_for_all(Origin::signed(1), 0, operator, true));
+		}
+		assert_ok!(NFT::set_approval_for_all(Origin::signed(1), 0, 2, true));
+		assert_eq!(ApprovalCounts::<Test>::get(1), 4);
+		assert_err!(NFT::approve(Origin::signed(1), 0, 0, 6, 1), Error::<Test>::TooManyApprovals);
+		assert_err!(
+			NFT::set_approval_for_all(Origin::signed(1), 0, 6, true),
+			Error::<Test>::TooManyApprovals
+		);
+
+		// Approvals are kept when class is destroyed and owner can still remove them
+		assert_ok!(NFT::burn(Origin::signed(1), 0, 0, 2));
+		assert_ok!(NFT::destroy_class(Origin::signed(1), 0, 0, 0));
+		assert_ok!(NFT::set_approval_for_all(Origin::signed(1), 0, 2, false));
+		assert_eq!(ClassApprovals::<Test>::get(1, (0, 2)), None);
+		assert_eq!(ApprovalCounts::<Test>::get(1), 3);
+	});
+}
+
+#[test]
+fn transfer_from_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		add_class(1);
+		add_token(1, 0);
+		assert_err!(
+			NFT::transfer_from(Origin::signed(2), 0, 0, 1, 1, 3),
+			Error::<Test>::InsufficientApproval
+		);
+		assert_ok!(NFT::approve(Origin::signed(1), 0, 0, 2, 1));
+		assert_err!(
+			NFT::transfer_from(Origin::signed(2), 0, 0, 2, 1, 3),
+			Error::<Test>::InsufficientApproval
+		);
+		assert_ok!(NFT::transfer_from(Origin::signed(2), 0, 0, 1, 1, 3));
+		assert_eq!(TokensByOwner::<Test>::get(1, (0, 0)).unwrap().free, 1);
+		assert_eq!(TokensByOwner::<Test>::get(3, (0, 0)).unwrap().free, 1);
+		assert_eq!(TokenApprovals::<Test>::get(1, (0, 0, 2)), None);
+		assert_eq!(ApprovalCounts::<Test>::get(1), 0);
+
+		// Approval for whole class is not consumed
+		assert_ok!(NFT::set_approval_for_all(Origin::signed(1), 0, 2, true));
+		assert_ok!(NFT::transfer_from(Origin::signed(2), 0, 0, 1, 1, 2));
+		assert_eq!(TokensByOwner::<Test>::get(1, (0, 0)), None);
+		assert_eq!(TokensByOwner::<Test>::get(2, (0, 0)).unwrap().free, 1);
+		assert_eq!(ClassApprovals::<Test>::get(1, (0, 2)), Some(()));
+		assert_err!(
+			NFT::transfer_from(Origin::signed(2), 0, 0, 1, 1, 3),
+			Error::<Test>::TokenNotFound
+		);
+	});
+}
+
+#[test]
+fn nonfungibles_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		Balances::make_free_balance_be(&2, 100);
+		assert_err!(
+			<NFT as Create<u64>>::create_collection(&1, &1, &1),
+			Error::<Test>::NoAvailableClassId
+		);
+		assert_ok!(<NFT as Create<u64>>::create_collection(&0, &1, &1));
+		assert_eq!(<NFT as Inspect<u64>>::collection_owner(&0), Some(1));
+		assert_err!(<NFT as Mutate<u64>>::mint_into(&0, &1, &2), Error::<Test>::NoAvailableTokenId);
+		assert_ok!(<NFT as Mutate<u64>>::mint_into(&0, &0, &2));
+		assert_eq!(Balances::reserved_balance(&1), 3);
+		assert_eq!(<NFT as Inspect<u64>>::owner(&0, &0), Some(2));
+		assert!(<NFT as Inspect<u64>>::can_transfer(&0, &0));
+
+		assert_ok!(<NFT as Mutate<u64>>::set_attribute(&0, &0, b"k", b"v"));
+		assert_ok!(<NFT as Mutate<u64>>::set_collection_attribute(&0, b"c", b"w"));
+		assert_eq!(Balances::reserved_balance(&1), 9);
+		assert_eq!(<NFT as Inspect<u64>>::attribute(&0, &0, b"k"), Some(b"v".to_vec()));
+		assert_eq!(<NFT as Inspect<u64>>::collection_attribute(&0, b"c"), Some(b"w".to_vec()));
+
+		assert_eq!(<NFT as InspectEnumerable<u64>>::collections().collect::<Vec<_>>(), vec![0]);
+		assert_eq!(<NFT as InspectEnumerable<u64>>::items(&0).collect::<Vec<_>>(), vec![0]);
+		assert_eq!(<NFT as InspectEnumerable<u64>>::owned(&2).collect::<Vec<_>>(), vec![(0, 0)]);
+		assert_eq!(
+			<NFT as InspectEnumerable<u64>>::owned_in_collection(&0, &2).collect::<Vec<_>>(),
+			vec![0]
+		);
+
+		assert_ok!(<NFT as Transfer<u64>>::transfer(&0, &0, &3));
+		assert_eq!(<NFT as Inspect<u64>>::owner(&0, &0), Some(3));
+		assert_err!(<NFT as Mutate<u64>>::burn(&0, &0, Some(&2)), Error::<Test>::NoPermission);
+		assert_ok!(<NFT as Mutate<u64>>::burn(&0, &0, Some(&3)));
+		assert_eq!(<NFT as Inspect<u64>>::owner(&0, &0), None);
+		assert_eq!(Balances::reserved_balance(&1), 8);
+
+		// Token held by several accounts has no owner
+		add_token(1, 0);
+		assert_ok!(NFT::transfer(Origin::signed(1), 0, 1, 1, 2));
+		assert_eq!(<NFT as Inspect<u64>>::owner(&0, &1), None);
+		assert!(!<NFT as Inspect<u64>>::can_transfer(&0, &1));
+		assert_err!(<NFT as Transfer<u64>>::transfer(&0, &1, &3), Error::<Test>::NoPermission);
+		assert!(<NFT as InspectEnumerable<u64>>::owned(&1).next().is_none());
+		assert!(<NFT as InspectEnumerable<u64>>::owned_in_collection(&0, &2).next().is_none());
+
+		// Token with reserved quantity has no owner
+		assert_ok!(NFT::transfer(Origin::signed(2), 0, 1, 1, 1));
+		assert_eq!(<NFT as InspectEnumerable<u64>>::owned(&1).collect::<Vec<_>>(), vec![(0, 1)]);
+		assert_ok!(NFT::reserve(0, 1, 1, &1));
+		assert_eq!(<NFT as Inspect<u64>>::owner(&0, &1), None);
+		assert!(<NFT as InspectEnumerable<u64>>::owned(&1).next().is_none());
+	});
+}
+
+#[test]
+fn transfer_hooks_should_work() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		add_class(1);
+		FrozenToken::set(Some((0, 0)));
+		assert_err!(
+			NFT::mint(Origin::signed(1), 1, 0, 2, vec![], None, None),
+			DispatchError::Other("Frozen")
+		);
+		FrozenToken::set(None);
+		add_token(1, 0);
+
+		FrozenToken::set(Some((0, 0)));
+		assert_err!(NFT::transfer(Origin::signed(1), 0, 0, 1, 2), DispatchError::Other("Frozen"));
+		assert_err!(NFT::burn(Origin::signed(1), 0, 0, 1), DispatchError::Other("Frozen"));
+		FrozenToken::set(None);
+		assert_ok!(NFT::transfer(Origin::signed(1), 0, 0, 1, 2));
+		assert_ok!(NFT::burn(Origin::signed(1), 0, 0, 1));
+		assert_eq!(
+			TransferLog::get(),
+			vec![(0, 0, 2, None, Some(1)), (0, 0, 1, Some(1), Some(2)), (0, 0, 1, Some(1), None)]
+		);
+	});
+}
@@ -46,11 +46,28 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn create_class() -> Weight;
 	fn mint() -> Weight;
-	fn delegate_mint() -> Weight;
-	fn burn() -> Weight;
+	fn delegate_mint(p: u32, ) -> Weight;
+	fn burn(a: u32, ) -> Weight;
 	fn update_token_royalty() -> Weight;
 	fn update_token_royalty_beneficiary() -> Weight;
 	fn transfer() -> Weight;
+	fn approve() -> Weight;
+	fn cancel_approval() -> Weight;
+	fn set_approval_for_all() -> Weight;
+	fn transfer_from() -> Weight;
+	fn transfer_class_ownership(t: u32, ) -> Weight;
+	fn destroy_class(m: u32, v: u32, ) -> Weight;
+	fn set_class_metadata() -> Weight;
+	fn set_token_metadata() -> Weight;
+	fn freeze_metadata() -> Weight;
+	fn set_attribute() -> Weight;
+	fn clear_attribute() -> Weight;
+	fn set_attribute_manager() -> Weight;
+	fn mint_batch(n: u32, ) -> Weight;
+	fn transfer_batch(n: u32, ) -> Weight;
+	fn lock_mint() -> Weight;
+	fn set_mint_allowlist() -> Weight;
+	fn redeem_voucher() -> Weight;
 }
 
 /// Weights for pallet_nft using the Substrate node and recommended hardware.
@@ -58,39 +75,55 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: NFT NextClassId (r:1 w:1)
 	// Storage: NFT Classes (r:0 w:1)
+	// Storage: NFT MintLimits (r:0 w:1)
 	fn create_class() -> Weight {
-		(36_131_000 as Weight)
+		(38_942_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	// Storage: NFT Classes (r:1 w:1)
 	// Storage: NFT NextTokenId (r:1 w:1)
+	// Storage: NFT MintLimits (r:1 w:0)
+	// Storage: NFT MintedTotals (r:1 w:1)
 	// Storage: NFT Tokens (r:0 w:1)
 	// Storage: NFT OwnersByToken (r:0 w:1)
 	// Storage: NFT TokensByOwner (r:0 w:1)
 	fn mint() -> Weight {
-		(48_478_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+		(51_102_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
 	// Storage: NFT Classes (r:1 w:1)
+	// Storage: NFT MintAllowlists (r:1 w:0)
+	// Storage: NFT AllowlistMinted (r:1 w:1)
+	// Storage: NFT AllowlistMinters (r:1 w:1)
 	// Storage: NFT NextTokenId (r:1 w:1)
+	// Storage: NFT MintLimits (r:1 w:0)
+	// Storage: NFT MintedTotals (r:1 w:1)
 	// Storage: NFT Tokens (r:0 w:1)
 	// Storage: NFT OwnersByToken (r:0 w:1)
 	// Storage: NFT TokensByOwner (r:0 w:1)
-	fn delegate_mint() -> Weight {
-		(49_416_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	fn delegate_mint(p: u32, ) -> Weight {
+		(68_914_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((1_052_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
 	// Storage: NFT Classes (r:1 w:1)
 	// Storage: NFT Tokens (r:1 w:1)
 	// Storage: NFT TokensByOwner (r:1 w:1)
 	// Storage: NFT OwnersByToken (r:0 w:1)
-	fn burn() -> Weight {
-		(51_577_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	// Storage: NFT Attributes (r:1 w:1)
+	// Storage: NFT AttributeCounts (r:0 w:1)
+	fn burn(a: u32, ) -> Weight {
+		(53_102_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((12_437_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
 	}
 	// Storage: NFT Tokens (r:1 w:1)
 	// Storage: NFT TokensByOwner (r:1 w:0)
@@ -112,45 +145,225 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// Storage: NFT Tokens (r:1 w:0)
+	// Storage: NFT TokenApprovals (r:1 w:1)
+	// Storage: NFT ApprovalCounts (r:1 w:1)
+	fn approve() -> Weight {
+		(26_031_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: NFT TokenApprovals (r:1 w:1)
+	// Storage: NFT ApprovalCounts (r:1 w:1)
+	fn cancel_approval() -> Weight {
+		(22_417_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: NFT ClassApprovals (r:1 w:1)
+	// Storage: NFT Classes (r:1 w:0)
+	// Storage: NFT ApprovalCounts (r:1 w:1)
+	fn set_approval_for_all() -> Weight {
+		(24_652_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: NFT ClassApprovals (r:1 w:0)
+	// Storage: NFT TokenApprovals (r:1 w:1)
+	// Storage: NFT ApprovalCounts (r:1 w:1)
+	// Storage: NFT TokensByOwner (r:2 w:2)
+	// Storage: NFT OwnersByToken (r:0 w:2)
+	fn transfer_from() -> Weight {
+		(43_207_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:1)
+	// Storage: NFT Tokens (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn transfer_class_ownership(t: u32, ) -> Weight {
+		(42_306_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((3_127_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:1)
+	// Storage: NFT AllowlistMinters (r:1 w:1)
+	// Storage: NFT Attributes (r:1 w:0)
+	// Storage: NFT NextTokenId (r:0 w:1)
+	// Storage: NFT AttributeManagers (r:0 w:1)
+	// Storage: NFT MintedTotals (r:0 w:1)
+	// Storage: NFT AllowlistMinted (r:1 w:1)
+	// Storage: NFT UsedVoucherCounts (r:1 w:1)
+	// Storage: NFT UsedVoucherNonces (r:1 w:1)
+	fn destroy_class(m: u32, v: u32, ) -> Weight {
+		(38_412_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((1_274_000 as Weight).saturating_mul(m as Weight))
+			// Standard Error: 1_000
+			.saturating_add((1_198_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(v as Weight)))
+	}
+	// Storage: NFT Classes (r:1 w:1)
+	fn set_class_metadata() -> Weight {
+		(27_735_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:0)
+	// Storage: NFT Tokens (r:1 w:1)
+	fn set_token_metadata() -> Weight {
+		(30_164_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:1)
+	fn freeze_metadata() -> Weight {
+		(19_871_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:0)
+	// Storage: NFT AttributeManagers (r:1 w:0)
+	// Storage: NFT Tokens (r:1 w:0)
+	// Storage: NFT Attributes (r:1 w:1)
+	// Storage: NFT AttributeCounts (r:1 w:1)
+	fn set_attribute() -> Weight {
+		(47_031_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:0)
+	// Storage: NFT AttributeManagers (r:1 w:0)
+	// Storage: NFT Attributes (r:1 w:1)
+	// Storage: NFT AttributeCounts (r:1 w:1)
+	fn clear_attribute() -> Weight {
+		(40_684_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:0)
+	// Storage: NFT AttributeManagers (r:0 w:1)
+	fn set_attribute_manager() -> Weight {
+		(21_648_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:1)
+	// Storage: NFT NextTokenId (r:1 w:1)
+	// Storage: NFT MintLimits (r:1 w:0)
+	// Storage: NFT MintedTotals (r:1 w:1)
+	// Storage: NFT Tokens (r:0 w:1)
+	// Storage: NFT OwnersByToken (r:0 w:1)
+	// Storage: NFT TokensByOwner (r:0 w:1)
+	fn mint_batch(n: u32, ) -> Weight {
+		(33_870_000 as Weight)
+			// Standard Error: 11_000
+			.saturating_add((18_344_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: NFT TokensByOwner (r:2 w:2)
+	// Storage: NFT OwnersByToken (r:0 w:2)
+	fn transfer_batch(n: u32, ) -> Weight {
+		(3_482_000 as Weight)
+			// Standard Error: 14_000
+			.saturating_add((29_760_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: NFT Classes (r:1 w:0)
+	// Storage: NFT MintLimits (r:1 w:1)
+	fn lock_mint() -> Weight {
+		(20_553_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:0)
+	// Storage: NFT MintAllowlists (r:0 w:1)
+	fn set_mint_allowlist() -> Weight {
+		(20_917_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: NFT UsedVoucherNonces (r:1 w:1)
+	// Storage: NFT Classes (r:1 w:1)
+	// Storage: NFT UsedVoucherCounts (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: NFT NextTokenId (r:1 w:1)
+	// Storage: NFT MintLimits (r:1 w:0)
+	// Storage: NFT MintedTotals (r:1 w:1)
+	// Storage: NFT Tokens (r:0 w:1)
+	// Storage: NFT OwnersByToken (r:0 w:1)
+	// Storage: NFT TokensByOwner (r:0 w:1)
+	fn redeem_voucher() -> Weight {
+		(124_318_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
 	// Storage: NFT NextClassId (r:1 w:1)
 	// Storage: NFT Classes (r:0 w:1)
+	// Storage: NFT MintLimits (r:0 w:1)
 	fn create_class() -> Weight {
-		(36_131_000 as Weight)
+		(38_942_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
 	// Storage: NFT Classes (r:1 w:1)
 	// Storage: NFT NextTokenId (r:1 w:1)
+	// Storage: NFT MintLimits (r:1 w:0)
+	// Storage: NFT MintedTotals (r:1 w:1)
 	// Storage: NFT Tokens (r:0 w:1)
 	// Storage: NFT OwnersByToken (r:0 w:1)
 	// Storage: NFT TokensByOwner (r:0 w:1)
 	fn mint() -> Weight {
-		(48_478_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+		(51_102_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
 	}
 	// Storage: NFT Classes (r:1 w:1)
+	// Storage: NFT MintAllowlists (r:1 w:0)
+	// Storage: NFT AllowlistMinted (r:1 w:1)
+	// Storage: NFT AllowlistMinters (r:1 w:1)
 	// Storage: NFT NextTokenId (r:1 w:1)
+	// Storage: NFT MintLimits (r:1 w:0)
+	// Storage: NFT MintedTotals (r:1 w:1)
 	// Storage: NFT Tokens (r:0 w:1)
 	// Storage: NFT OwnersByToken (r:0 w:1)
 	// Storage: NFT TokensByOwner (r:0 w:1)
-	fn delegate_mint() -> Weight {
-		(49_416_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	fn delegate_mint(p: u32, ) -> Weight {
+		(68_914_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((1_052_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
 	}
 	// Storage: NFT Classes (r:1 w:1)
 	// Storage: NFT Tokens (r:1 w:1)
 	// Storage: NFT TokensByOwner (r:1 w:1)
 	// Storage: NFT OwnersByToken (r:0 w:1)
-	fn burn() -> Weight {
-		(51_577_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	// Storage: NFT Attributes (r:1 w:1)
+	// Storage: NFT AttributeCounts (r:0 w:1)
+	fn burn(a: u32, ) -> Weight {
+		(53_102_000 as Weight)
+			// Standard Error: 9_000
+			.saturating_add((12_437_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
 	}
 	// Storage: NFT Tokens (r:1 w:1)
 	// Storage: NFT TokensByOwner (r:1 w:0)
@@ -172,4 +385,168 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
+	// Storage: NFT Tokens (r:1 w:0)
+	// Storage: NFT TokenApprovals (r:1 w:1)
+	// Storage: NFT ApprovalCounts (r:1 w:1)
+	fn approve() -> Weight {
+		(26_031_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: NFT TokenApprovals (r:1 w:1)
+	// Storage: NFT ApprovalCounts (r:1 w:1)
+	fn cancel_approval() -> Weight {
+		(22_417_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: NFT ClassApprovals (r:1 w:1)
+	// Storage: NFT Classes (r:1 w:0)
+	// Storage: NFT ApprovalCounts (r:1 w:1)
+	fn set_approval_for_all() -> Weight {
+		(24_652_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: NFT ClassApprovals (r:1 w:0)
+	// Storage: NFT TokenApprovals (r:1 w:1)
+	// Storage: NFT ApprovalCounts (r:1 w:1)
+	// Storage: NFT TokensByOwner (r:2 w:2)
+	// Storage: NFT OwnersByToken (r:0 w:2)
+	fn transfer_from() -> Weight {
+		(43_207_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:1)
+	// Storage: NFT Tokens (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn transfer_class_ownership(t: u32, ) -> Weight {
+		(42_306_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((3_127_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:1)
+	// Storage: NFT AllowlistMinters (r:1 w:1)
+	// Storage: NFT Attributes (r:1 w:0)
+	// Storage: NFT NextTokenId (r:0 w:1)
+	// Storage: NFT AttributeManagers (r:0 w:1)
+	// Storage: NFT MintedTotals (r:0 w:1)
+	// Storage: NFT AllowlistMinted (r:1 w:1)
+	// Storage: NFT UsedVoucherCounts (r:1 w:1)
+	// Storage: NFT UsedVoucherNonces (r:1 w:1)
+	fn destroy_class(m: u32, v: u32, ) -> Weight {
+		(38_412_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((1_274_000 as Weight).saturating_mul(m as Weight))
+			// Standard Error: 1_000
+			.saturating_add((1_198_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(v as Weight)))
+	}
+	// Storage: NFT Classes (r:1 w:1)
+	fn set_class_metadata() -> Weight {
+		(27_735_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:0)
+	// Storage: NFT Tokens (r:1 w:1)
+	fn set_token_metadata() -> Weight {
+		(30_164_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:1)
+	fn freeze_metadata() -> Weight {
+		(19_871_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:0)
+	// Storage: NFT AttributeManagers (r:1 w:0)
+	// Storage: NFT Tokens (r:1 w:0)
+	// Storage: NFT Attributes (r:1 w:1)
+	// Storage: NFT AttributeCounts (r:1 w:1)
+	fn set_attribute() -> Weight {
+		(47_031_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:0)
+	// Storage: NFT AttributeManagers (r:1 w:0)
+	// Storage: NFT Attributes (r:1 w:1)
+	// Storage: NFT AttributeCounts (r:1 w:1)
+	fn clear_attribute() -> Weight {
+		(40_684_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:0)
+	// Storage: NFT AttributeManagers (r:0 w:1)
+	fn set_attribute_manager() -> Weight {
+		(21_648_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:1)
+	// Storage: NFT NextTokenId (r:1 w:1)
+	// Storage: NFT MintLimits (r:1 w:0)
+	// Storage: NFT MintedTotals (r:1 w:1)
+	// Storage: NFT Tokens (r:0 w:1)
+	// Storage: NFT OwnersByToken (r:0 w:1)
+	// Storage: NFT TokensByOwner (r:0 w:1)
+	fn mint_batch(n: u32, ) -> Weight {
+		(33_870_000 as Weight)
+			// Standard Error: 11_000
+			.saturating_add((18_344_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: NFT TokensByOwner (r:2 w:2)
+	// Storage: NFT OwnersByToken (r:0 w:2)
+	fn transfer_batch(n: u32, ) -> Weight {
+		(3_482_000 as Weight)
+			// Standard Error: 14_000
+			.saturating_add((29_760_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: NFT Classes (r:1 w:0)
+	// Storage: NFT MintLimits (r:1 w:1)
+	fn lock_mint() -> Weight {
+		(20_553_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: NFT Classes (r:1 w:0)
+	// Storage: NFT MintAllowlists (r:0 w:1)
+	fn set_mint_allowlist() -> Weight {
+		(20_917_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: NFT UsedVoucherNonces (r:1 w:1)
+	// Storage: NFT Classes (r:1 w:1)
+	// Storage: NFT UsedVoucherCounts (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: NFT NextTokenId (r:1 w:1)
+	// Storage: NFT MintLimits (r:1 w:0)
+	// Storage: NFT MintedTotals (r:1 w:1)
+	// Storage: NFT Tokens (r:0 w:1)
+	// Storage: NFT OwnersByToken (r:0 w:1)
+	// Storage: NFT TokensByOwner (r:0 w:1)
+	fn redeem_voucher() -> Weight {
+		(124_318_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
 }
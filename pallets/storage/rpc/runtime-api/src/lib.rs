@@ -2,6 +2,7 @@
 
 use codec::{Codec, Decode, Encode};
 use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
 use sp_std::prelude::*;
 
 #[cfg(feature = "std")]
@@ -29,6 +30,95 @@ pub struct NodeDepositInfo<Balance> {
 	pub slash_deposit: Balance,
 	#[cfg_attr(feature = "std", serde(with = "serde_balance"))]
 	pub used_deposit: Balance,
+	#[cfg_attr(feature = "std", serde(with = "serde_balance"))]
+	pub backing_deposit: Balance,
+}
+
+/// Node deposit returned by `FileStorageApi` before version 2
+#[derive(Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+pub struct OldNodeDepositInfo<Balance> {
+	pub current_deposit: Balance,
+	pub slash_deposit: Balance,
+	pub used_deposit: Balance,
+}
+
+#[derive(Eq, PartialEq, Encode, Decode, Default, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+	feature = "std",
+	serde(bound(serialize = "AccountId: Serialize, Balance: std::fmt::Display"))
+)]
+#[cfg_attr(
+	feature = "std",
+	serde(bound(deserialize = "AccountId: Deserialize<'de>, Balance: std::str::FromStr"))
+)]
+pub struct StashDepositInfo<AccountId, Balance> {
+	pub controllers: Vec<AccountId>,
+	#[cfg_attr(feature = "std", serde(with = "serde_balance"))]
+	pub pool_deposit: Balance,
+	#[cfg_attr(feature = "std", serde(with = "serde_balance"))]
+	pub nodes_deposit: Balance,
+	#[cfg_attr(feature = "std", serde(with = "serde_balance"))]
+	pub required_deposit: Balance,
+	#[cfg_attr(feature = "std", serde(with = "serde_balance"))]
+	pub pending_rewards: Balance,
+}
+
+/// Remaining budget of sponsor
+#[derive(Eq, PartialEq, Encode, Decode, Default, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+pub struct SponsorshipBudgetInfo<Balance> {
+	#[cfg_attr(feature = "std", serde(with = "serde_balance"))]
+	pub budget: Balance,
+	#[cfg_attr(feature = "std", serde(with = "serde_balance"))]
+	pub period_remaining: Balance,
+	#[cfg_attr(feature = "std", serde(with = "serde_balance"))]
+	pub max_per_file: Balance,
+}
+
+/// Storage usage and quota of account
+#[derive(Eq, PartialEq, Encode, Decode, Default, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+pub struct AccountUsageInfo<Balance> {
+	pub files: u32,
+	pub bytes: u64,
+	#[cfg_attr(feature = "std", serde(with = "serde_balance"))]
+	pub paid: Balance,
+	pub quota: Option<u64>,
+}
+
+/// Diversity of file's replicas
+#[derive(Eq, PartialEq, Encode, Decode, Default, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FileDiversityInfo {
+	pub replicas: u32,
+	pub regions: u32,
+	pub operators: u32,
+}
+
+/// Storage class of file, each class has its own price, liquidate duration and reward pool
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum StorageClass {
+	/// Frequently accessed file
+	Hot,
+	/// Archival file with slower retrieval terms
+	Cold,
+}
+
+impl Default for StorageClass {
+	fn default() -> Self {
+		StorageClass::Hot
+	}
 }
 
 #[cfg(feature = "std")]
@@ -51,14 +141,27 @@ mod serde_balance {
 }
 
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait FileStorageApi<AccountId, Balance, BlockNumber> where
 		Balance: Codec,
 		BlockNumber: Codec,
 		AccountId: Codec,
 	 {
-		/// Get fee for store ipfs file.
+		/// Get fee for store ipfs file in a storage class.
+		fn store_fee(file_size: u64, time: BlockNumber, class: StorageClass) -> StoreFeeInfo<Balance>;
+		#[changed_in(2)]
 		fn store_fee(file_size: u64, time: BlockNumber) -> StoreFeeInfo<Balance>;
 		/// Get node deposit.
 		fn node_deposit(controller: &AccountId) -> NodeDepositInfo<Balance>;
+		#[changed_in(2)]
+		fn node_deposit(controller: &AccountId) -> OldNodeDepositInfo<Balance>;
+		/// Get deposit pool and nodes of stash.
+		fn stash_info(stash: &AccountId) -> StashDepositInfo<AccountId, Balance>;
+		/// Get diversity of file's replicas.
+		fn file_diversity(cid: Vec<u8>) -> Option<FileDiversityInfo>;
+		/// Get remaining budget of sponsor.
+		fn sponsorship_budget(sponsor: &AccountId) -> SponsorshipBudgetInfo<Balance>;
+		/// Get storage usage and quota of account.
+		fn account_usage(account: &AccountId) -> AccountUsageInfo<Balance>;
 	}
 }
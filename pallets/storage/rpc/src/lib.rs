@@ -7,8 +7,11 @@ use jsonrpsee::{
 	types::error::{CallError, ErrorObject},
 };
 pub use pallet_storage_rpc_runtime_api::FileStorageApi as FileStorageRuntimeApi;
-use pallet_storage_rpc_runtime_api::{NodeDepositInfo, StoreFeeInfo};
-use sp_api::ProvideRuntimeApi;
+use pallet_storage_rpc_runtime_api::{
+	AccountUsageInfo, FileDiversityInfo, NodeDepositInfo, SponsorshipBudgetInfo, StashDepositInfo,
+	StorageClass, StoreFeeInfo,
+};
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
@@ -16,11 +19,34 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 const RUNTIME_ERROR: i32 = 1;
 
 #[rpc(client, server)]
-pub trait FileStorageApi<AccountId, Balance, BlockNumber, ResponseFeeType, ResponseDepsoitType> {
+pub trait FileStorageApi<
+	AccountId,
+	Balance,
+	BlockNumber,
+	ResponseFeeType,
+	ResponseDepsoitType,
+	ResponseStashType,
+	ResponseSponsorshipType,
+	ResponseUsageType,
+>
+{
 	#[method(name = "fileStorage_storeFee")]
-	fn store_fee(&self, file_size: u64, time: BlockNumber) -> RpcResult<ResponseFeeType>;
+	fn store_fee(
+		&self,
+		file_size: u64,
+		time: BlockNumber,
+		class: Option<StorageClass>,
+	) -> RpcResult<ResponseFeeType>;
 	#[method(name = "fileStorage_nodeDeposit")]
 	fn node_deposit(&self, controller: AccountId) -> RpcResult<ResponseDepsoitType>;
+	#[method(name = "fileStorage_stashInfo")]
+	fn stash_info(&self, stash: AccountId) -> RpcResult<ResponseStashType>;
+	#[method(name = "fileStorage_fileDiversity")]
+	fn file_diversity(&self, cid: String) -> RpcResult<Option<FileDiversityInfo>>;
+	#[method(name = "fileStorage_sponsorshipBudget")]
+	fn sponsorship_budget(&self, sponsor: AccountId) -> RpcResult<ResponseSponsorshipType>;
+	#[method(name = "fileStorage_accountUsage")]
+	fn account_usage(&self, account: AccountId) -> RpcResult<ResponseUsageType>;
 }
 
 /// A struct that implements the [`FileStorageApi`].
@@ -60,26 +86,88 @@ impl<Client, Block, AccountId, Balance, BlockNumber>
 		BlockNumber,
 		StoreFeeInfo<Balance>,
 		NodeDepositInfo<Balance>,
+		StashDepositInfo<AccountId, Balance>,
+		SponsorshipBudgetInfo<Balance>,
+		AccountUsageInfo<Balance>,
 	> for FileStorage<Client, Block>
 where
 	Block: BlockT,
 	Client: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
 	Client::Api: FileStorageRuntimeApi<Block, AccountId, Balance, BlockNumber>,
-	Balance: Codec + Copy + TryFrom<NumberOrHex> + Into<NumberOrHex>,
+	Balance: Codec + Copy + Default + TryFrom<NumberOrHex> + Into<NumberOrHex>,
 	AccountId: Codec,
 	BlockNumber: Codec,
 {
-	fn store_fee(&self, file_size: u64, time: BlockNumber) -> RpcResult<StoreFeeInfo<Balance>> {
+	fn store_fee(
+		&self,
+		file_size: u64,
+		time: BlockNumber,
+		class: Option<StorageClass>,
+	) -> RpcResult<StoreFeeInfo<Balance>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(self.client.info().best_hash);
-		api.store_fee(&at, file_size, time).map_err(runtime_error_into_rpc_err)
+		let class = class.unwrap_or_default();
+		let version = api
+			.api_version::<dyn FileStorageRuntimeApi<Block, AccountId, Balance, BlockNumber>>(&at)
+			.map_err(runtime_error_into_rpc_err)?
+			.unwrap_or(1);
+		if version < 2 {
+			// Runtime before storage classes only prices hot storage
+			if class != StorageClass::Hot {
+				return Err(runtime_error_into_rpc_err("Storage class is not supported"))
+			}
+			#[allow(deprecated)]
+			return api
+				.store_fee_before_version_2(&at, file_size, time)
+				.map_err(runtime_error_into_rpc_err)
+		}
+		api.store_fee(&at, file_size, time, class).map_err(runtime_error_into_rpc_err)
 	}
 
 	fn node_deposit(&self, controller: AccountId) -> RpcResult<NodeDepositInfo<Balance>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(self.client.info().best_hash);
+		let version = api
+			.api_version::<dyn FileStorageRuntimeApi<Block, AccountId, Balance, BlockNumber>>(&at)
+			.map_err(runtime_error_into_rpc_err)?
+			.unwrap_or(1);
+		if version < 2 {
+			#[allow(deprecated)]
+			let old = api.node_deposit_before_version_2(&at, &controller)
+				.map_err(runtime_error_into_rpc_err)?;
+			return Ok(NodeDepositInfo {
+				current_deposit: old.current_deposit,
+				slash_deposit: old.slash_deposit,
+				used_deposit: old.used_deposit,
+				backing_deposit: Default::default(),
+			})
+		}
 		api.node_deposit(&at, &controller).map_err(runtime_error_into_rpc_err)
 	}
+
+	fn stash_info(&self, stash: AccountId) -> RpcResult<StashDepositInfo<AccountId, Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.stash_info(&at, &stash).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn file_diversity(&self, cid: String) -> RpcResult<Option<FileDiversityInfo>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.file_diversity(&at, cid.into_bytes()).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn sponsorship_budget(&self, sponsor: AccountId) -> RpcResult<SponsorshipBudgetInfo<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.sponsorship_budget(&at, &sponsor).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn account_usage(&self, account: AccountId) -> RpcResult<AccountUsageInfo<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+		api.account_usage(&at, &account).map_err(runtime_error_into_rpc_err)
+	}
 }
 
 /// Converts a runtime trap into an RPC error.
@@ -49,6 +49,48 @@ fn fund_storage_pot<T: Config>(balance_factor: u32) {
 	whitelist_account!(storage_pot);
 }
 
+fn allow_quote_status<T: Config>() {
+	AllowedQuoteStatuses::<T>::put(vec![str2bytes("GROUP_OUT_OF_DATE")]);
+	AllowedAdvisoryIds::<T>::put(
+		[
+			"INTEL-SA-00161",
+			"INTEL-SA-00477",
+			"INTEL-SA-00381",
+			"INTEL-SA-00389",
+			"INTEL-SA-00320",
+			"INTEL-SA-00329",
+			"INTEL-SA-00220",
+			"INTEL-SA-00270",
+			"INTEL-SA-00293",
+			"INTEL-SA-00233",
+		]
+		.iter()
+		.map(|v| str2bytes(v))
+		.collect::<Vec<_>>(),
+	);
+}
+
+fn add_trust_anchor<T: Config>() {
+	TrustAnchors::<T>::insert(
+		IAS_ROOT_CA_SPKI.to_vec(),
+		TrustAnchorInfo {
+			subject: IAS_ROOT_CA_SUBJECT.to_vec(),
+			not_before: IAS_ROOT_CA_NOT_BEFORE,
+			not_after: IAS_ROOT_CA_NOT_AFTER,
+		},
+	);
+}
+
+fn stash_node<T: Config>() -> T::AccountId {
+	let stasher = create_funded_user::<T>("stasher", 20000);
+	let controller: T::AccountId = account("controller", 0, SEED);
+	whitelist_account!(controller);
+	let controller_lookup: <T::Lookup as StaticLookup>::Source =
+		T::Lookup::unlookup(controller.clone());
+	assert_ok!(FileStorage::<T>::stash(SystemOrigin::Signed(stasher).into(), controller_lookup));
+	controller
+}
+
 fn create_file<T: Config>(
 	cid: &FileId,
 	no_reserved: bool,
@@ -58,7 +100,7 @@ fn create_file<T: Config>(
 	let reserved = if no_reserved {
 		0u32.saturated_into()
 	} else {
-		FileStorage::<T>::store_file_bytes_fee(1_000_000)
+		FileStorage::<T>::store_file_bytes_fee(1_000_000, StorageClass::Hot)
 	};
 	Files::<T>::insert(
 		cid.clone(),
@@ -67,9 +109,10 @@ fn create_file<T: Config>(
 			base_fee: T::FileBaseFee::get(),
 			file_size: 1_000_000u64,
 			add_at: 99u32.saturated_into(),
-			fee: FileStorage::<T>::store_file_bytes_fee(1_000_000),
+			fee: FileStorage::<T>::store_file_bytes_fee(1_000_000, StorageClass::Hot),
 			liquidate_at,
 			replicas: replicas.to_vec(),
+			class: StorageClass::Hot,
 		},
 	);
 }
@@ -93,6 +136,7 @@ fn create_replica_nodes<T: Config>(
 				machine_id: Some(vec![0u8; 16]),
 				rid: 0,
 				used: 10000000,
+				cold_used: 0,
 				slash_used: 0,
 				reward: 0u32.into(),
 				power: 10000000000,
@@ -114,6 +158,36 @@ benchmarks! {
 		assert_last_event::<T>(Event::<T>::SetEnclave { enclave_id, expire_at }.into());
 	}
 
+	set_attestation_policy {
+		let mr_signer: MrSigner = vec![1u8; 32];
+		let policy = AttestationPolicy { min_isv_svn: 1, expire_at: 1000000u32.into() };
+	}: _(SystemOrigin::Root, mr_signer.clone(), 0, Some(policy))
+	verify {
+		assert!(AttestationPolicies::<T>::contains_key(&mr_signer, 0));
+	}
+
+	set_trust_anchor {
+		let spki = IAS_ROOT_CA_SPKI.to_vec();
+		let anchor = TrustAnchorInfo {
+			subject: IAS_ROOT_CA_SUBJECT.to_vec(),
+			not_before: IAS_ROOT_CA_NOT_BEFORE,
+			not_after: IAS_ROOT_CA_NOT_AFTER,
+		};
+	}: _(SystemOrigin::Root, spki.clone(), Some(anchor))
+	verify {
+		assert!(TrustAnchors::<T>::contains_key(&spki));
+	}
+
+	set_quote_policy {
+		let s in 0 .. 10;
+		let a in 0 .. 100;
+		let statuses: Vec<Vec<u8>> = (0..s).map(|_| str2bytes("GROUP_OUT_OF_DATE")).collect();
+		let advisory_ids: Vec<Vec<u8>> = (0..a).map(|_| str2bytes("INTEL-SA-00161")).collect();
+	}: _(SystemOrigin::Root, statuses, advisory_ids)
+	verify {
+		assert_eq!(AllowedAdvisoryIds::<T>::get().len() as u32, a);
+	}
+
 	stash {
 		let stasher = create_funded_user::<T>("stasher", 20000);
 		let controller: T::AccountId = account("controller", 0, SEED);
@@ -143,6 +217,8 @@ benchmarks! {
 	}
 
 	register {
+		allow_quote_status::<T>();
+		add_trust_anchor::<T>();
 		let enclave = get_enclave();
 		assert_ok!(FileStorage::<T>::set_enclave(SystemOrigin::Root.into(), enclave, 1000000u32.into()));
 
@@ -167,6 +243,8 @@ benchmarks! {
 
 		System::<T>::set_block_number(50000u32.into());
 
+		allow_quote_status::<T>();
+		add_trust_anchor::<T>();
 		let enclave = get_enclave();
 		assert_ok!(FileStorage::<T>::set_enclave(SystemOrigin::Root.into(), enclave, 1000000u32.into()));
 
@@ -229,8 +307,14 @@ benchmarks! {
 	store {
 		let cid = str2bytes("QmS9ErDVxHXRNMJRJ5i3bp1zxCZzKP8QXXNH1yeeeeeeeA");
 		let caller = create_funded_user::<T>("caller", 10000);
+		let sponsor = create_funded_user::<T>("sponsor", 20000);
 		let fee = T::Currency::minimum_balance().saturating_mul(2000u32.saturated_into());
-	}: _(SystemOrigin::Signed(caller.clone()), cid.clone(), 100u64, fee)
+		assert_ok!(FileStorage::<T>::fund_sponsorship(SystemOrigin::Signed(sponsor.clone()).into(), fee));
+		assert_ok!(FileStorage::<T>::set_sponsorship_caps(SystemOrigin::Signed(sponsor.clone()).into(), fee, fee, 100u32.into()));
+		let caller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
+		assert_ok!(FileStorage::<T>::set_beneficiary(SystemOrigin::Signed(sponsor.clone()).into(), caller_lookup.clone(), true));
+		assert_ok!(FileStorage::<T>::set_account_quota(SystemOrigin::Root.into(), caller_lookup, Some(1000u64)));
+	}: store_with_options(SystemOrigin::Signed(caller.clone()), cid.clone(), 100u64, fee, StoreOptions { class: StorageClass::Hot, sponsor: Some(T::Lookup::unlookup(sponsor.clone())), chunks: None })
 	verify {
 		assert_last_event::<T>(Event::<T>::FileAdded { cid, caller, fee, first: true }.into());
 	}
@@ -246,6 +330,300 @@ benchmarks! {
 		assert_last_event::<T>(Event::<T>::FileForceDeleted { cid }.into());
 	}
 
+	refund_file {
+		let c in 1 .. T::MaxFileOwners::get();
+		let cid = str2bytes("QmS9ErDVxHXRNMJRJ5i3bp1zxCZzKP8QXXNH1yeeeeeeeA");
+		let fee = T::Currency::minimum_balance().saturating_mul(2000u32.saturated_into());
+		for i in 0 .. c {
+			let payer: T::AccountId = account("payer", i, SEED);
+			T::Currency::make_free_balance_be(&payer, fee.saturating_mul(2u32.into()));
+			assert_ok!(FileStorage::<T>::store(SystemOrigin::Signed(payer).into(), cid.clone(), 100u64, fee));
+		}
+		let caller = create_funded_user::<T>("caller", 100);
+		System::<T>::set_block_number(50000u32.into());
+	}: _(SystemOrigin::Signed(caller), cid.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::FileRefunded { cid, amount: fee.saturating_mul(c.into()) }.into());
+	}
+
+	set_node_classes {
+		let controller = stash_node::<T>();
+	}: _(SystemOrigin::Signed(controller.clone()), vec![StorageClass::Hot, StorageClass::Cold])
+	verify {
+		assert_eq!(NodeClasses::<T>::get(&controller).len(), 2);
+	}
+
+	set_node_tag {
+		let controller = stash_node::<T>();
+		let max_len = T::MaxNodeTagLength::get() as usize;
+	}: _(SystemOrigin::Signed(controller.clone()), vec![1u8; max_len], vec![2u8; max_len])
+	verify {
+		assert!(NodeTags::<T>::contains_key(&controller));
+	}
+
+	force_set_node_tag {
+		let controller = stash_node::<T>();
+		let controller_lookup: <T::Lookup as StaticLookup>::Source =
+			T::Lookup::unlookup(controller.clone());
+		let max_len = T::MaxNodeTagLength::get() as usize;
+	}: _(SystemOrigin::Root, controller_lookup, Some((vec![1u8; max_len], vec![2u8; max_len])))
+	verify {
+		assert!(NodeTags::<T>::get(&controller).unwrap().attested);
+	}
+
+	fund_sponsorship {
+		let sponsor = create_funded_user::<T>("sponsor", 20000);
+		let amount = T::Currency::minimum_balance().saturating_mul(1000u32.saturated_into());
+	}: _(SystemOrigin::Signed(sponsor.clone()), amount)
+	verify {
+		assert_last_event::<T>(Event::<T>::SponsorshipFunded { sponsor, amount }.into());
+	}
+
+	withdraw_sponsorship {
+		let sponsor = create_funded_user::<T>("sponsor", 20000);
+		let amount = T::Currency::minimum_balance().saturating_mul(1000u32.saturated_into());
+		assert_ok!(FileStorage::<T>::fund_sponsorship(SystemOrigin::Signed(sponsor.clone()).into(), amount));
+	}: _(SystemOrigin::Signed(sponsor.clone()), amount)
+	verify {
+		assert_last_event::<T>(Event::<T>::SponsorshipWithdrawn { sponsor, amount }.into());
+	}
+
+	set_sponsorship_caps {
+		let sponsor = create_funded_user::<T>("sponsor", 20000);
+		let max_per_file = T::Currency::minimum_balance().saturating_mul(10u32.saturated_into());
+		let max_per_period = T::Currency::minimum_balance().saturating_mul(100u32.saturated_into());
+		let period: BlockNumberFor<T> = 100u32.into();
+	}: _(SystemOrigin::Signed(sponsor.clone()), max_per_file, max_per_period, period)
+	verify {
+		assert_last_event::<T>(Event::<T>::SponsorshipCapsSet { sponsor, max_per_file, max_per_period, period }.into());
+	}
+
+	set_beneficiary {
+		let sponsor = create_funded_user::<T>("sponsor", 20000);
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+		let beneficiary_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(beneficiary.clone());
+	}: _(SystemOrigin::Signed(sponsor.clone()), beneficiary_lookup, true)
+	verify {
+		assert!(SponsoredAccounts::<T>::contains_key(&sponsor, &beneficiary));
+	}
+
+	set_account_quota {
+		let who: T::AccountId = account("who", 0, SEED);
+		let who_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(who.clone());
+	}: _(SystemOrigin::Root, who_lookup, Some(1000u64))
+	verify {
+		assert_eq!(AccountQuotas::<T>::get(&who), Some(1000u64));
+	}
+
+	heartbeat {
+		let controller = stash_node::<T>();
+		let machine_id: Vec<u8> = hex!("2663554671a5f2c3050e1cec37f31e55").into();
+		let priv_k: Vec<u8> = hex!("e394cf1de366242a772f44904ba475f5317ce8baedac5485ccd812db2ccf28ab").into();
+		let pub_k: Vec<u8> = hex!("87f66db5fe0888c65ddab6940020492fd2fe615413f13d8d9131c478c68c6c80dfa47365bf9fefac29003cf8f169a07662b3c5907511e99e439cde69f396ff82").into();
+		assert_ok!(FileStorage::<T>::set_enclave(SystemOrigin::Root.into(), get_enclave(), 1000000u32.into()));
+		Registers::<T>::insert(&machine_id, RegisterInfo { key: pub_k.clone(), enclave: get_enclave(), ..Default::default() });
+		AttestationDeadlines::<T>::insert(&machine_id, BlockNumberFor::<T>::from(1000000u32));
+		Nodes::<T>::mutate(&controller, |maybe_node_info| {
+			if let Some(node_info) = maybe_node_info {
+				node_info.machine_id = Some(machine_id.clone());
+				node_info.reported_at = 5u32.into();
+			}
+		});
+		System::<T>::set_block_number(15u32.into());
+		Session::<T>::put(SessionState {
+			current: 1,
+			prev_begin_at: 0u32.into(),
+			begin_at: 10u32.into(),
+			end_at: 20u32.into(),
+		});
+		let sig = sign::p256_sign_heartbeat(&machine_id, &priv_k, &pub_k, 1);
+	}: _(SystemOrigin::Signed(controller.clone()), 1, sig)
+	verify {
+		assert_last_event::<T>(Event::<T>::NodeHeartbeat {
+			controller,
+			machine_id,
+			session: 1,
+			mine_reward: Zero::zero(),
+			share_store_reward: Zero::zero(),
+		}.into());
+	}
+
+	set_commission {
+		let controller = stash_node::<T>();
+		let commission = Perbill::from_percent(10);
+	}: _(SystemOrigin::Signed(controller.clone()), commission)
+	verify {
+		assert_last_event::<T>(Event::<T>::CommissionSet { controller, commission }.into());
+	}
+
+	delegate {
+		let controller = stash_node::<T>();
+		let delegator = create_funded_user::<T>("delegator", 20000);
+		let controller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(controller.clone());
+		let amount = T::MinDelegation::get();
+		assert_ok!(FileStorage::<T>::delegate(SystemOrigin::Signed(delegator.clone()).into(), controller_lookup.clone(), amount));
+	}: _(SystemOrigin::Signed(delegator.clone()), controller_lookup, amount)
+	verify {
+		assert_last_event::<T>(Event::<T>::Delegated { delegator, controller, amount }.into());
+	}
+
+	undelegate {
+		let controller = stash_node::<T>();
+		let delegator = create_funded_user::<T>("delegator", 20000);
+		let controller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(controller.clone());
+		let amount = T::MinDelegation::get();
+		assert_ok!(FileStorage::<T>::delegate(SystemOrigin::Signed(delegator.clone()).into(), controller_lookup.clone(), amount.saturating_mul(3u32.into())));
+	}: _(SystemOrigin::Signed(delegator.clone()), controller_lookup, amount)
+	verify {
+		assert_eq!(Unbondings::<T>::get(&delegator).len(), 1);
+	}
+
+	withdraw_unbonded {
+		let controller = stash_node::<T>();
+		let delegator = create_funded_user::<T>("delegator", 20000);
+		let controller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(controller.clone());
+		let amount = T::MinDelegation::get();
+		assert_ok!(FileStorage::<T>::delegate(SystemOrigin::Signed(delegator.clone()).into(), controller_lookup.clone(), amount));
+		assert_ok!(FileStorage::<T>::undelegate(SystemOrigin::Signed(delegator.clone()).into(), controller_lookup, amount));
+		System::<T>::set_block_number(T::UnbondingDuration::get().saturating_add(One::one()));
+	}: _(SystemOrigin::Signed(delegator.clone()))
+	verify {
+		assert_last_event::<T>(Event::<T>::WithdrawnUnbonded { delegator, amount }.into());
+	}
+
+	claim_delegation_rewards {
+		let controller = stash_node::<T>();
+		let delegator = create_funded_user::<T>("delegator", 20000);
+		let controller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(controller.clone());
+		let amount = T::MinDelegation::get();
+		assert_ok!(FileStorage::<T>::delegate(SystemOrigin::Signed(delegator.clone()).into(), controller_lookup.clone(), amount));
+		fund_storage_pot::<T>(20000u32.into());
+		Delegations::<T>::mutate(&controller, &delegator, |maybe_delegation| {
+			if let Some(delegation) = maybe_delegation {
+				delegation.unclaimed = amount;
+			}
+		});
+	}: _(SystemOrigin::Signed(delegator.clone()), controller_lookup)
+	verify {
+		assert_last_event::<T>(Event::<T>::DelegationRewardClaimed { delegator, controller, amount }.into());
+	}
+
+	set_payee {
+		let controller = stash_node::<T>();
+		let payee = RewardDestination::Controller;
+	}: _(SystemOrigin::Signed(controller.clone()), payee.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::PayeeSet { controller, payee }.into());
+	}
+
+	claim_rewards {
+		let controller = stash_node::<T>();
+		let payee: T::AccountId = account("payee", 0, SEED);
+		fund_storage_pot::<T>(20000u32.into());
+		let amount = T::Currency::minimum_balance().saturating_mul(100u32.saturated_into());
+		assert_ok!(FileStorage::<T>::set_payee(SystemOrigin::Signed(controller.clone()).into(), RewardDestination::Account(payee.clone())));
+		PendingRewards::<T>::insert(&controller, RewardLedger {
+			free: amount,
+			locked: vec![UnbondingChunk { amount, unlock_at: Zero::zero() }],
+		});
+		let amount = amount.saturating_mul(2u32.saturated_into());
+	}: _(SystemOrigin::Signed(controller.clone()))
+	verify {
+		assert_last_event::<T>(Event::<T>::RewardsClaimed { controller, payee, amount }.into());
+	}
+
+	deposit_pool {
+		let stash = create_funded_user::<T>("stasher", 20000);
+		let amount = T::Currency::minimum_balance().saturating_mul(1000u32.saturated_into());
+	}: _(SystemOrigin::Signed(stash.clone()), amount)
+	verify {
+		assert_last_event::<T>(Event::<T>::PoolDeposited { stash, amount }.into());
+	}
+
+	withdraw_pool {
+		let stash = create_funded_user::<T>("stasher", 20000);
+		let amount = T::Currency::minimum_balance().saturating_mul(1000u32.saturated_into());
+		assert_ok!(FileStorage::<T>::deposit_pool(SystemOrigin::Signed(stash.clone()).into(), amount));
+	}: _(SystemOrigin::Signed(stash.clone()), amount)
+	verify {
+		assert_last_event::<T>(Event::<T>::PoolWithdrawn { stash, amount }.into());
+	}
+
+	claim_stash_rewards {
+		let c in 1 .. T::MaxStashControllers::get();
+		let stash = create_funded_user::<T>("stasher", 20000);
+		fund_storage_pot::<T>(20000u32.into());
+		let reward = T::Currency::minimum_balance();
+		let mut controllers = vec![];
+		for i in 0 .. c {
+			let controller: T::AccountId = account("controller", i, SEED);
+			PendingRewards::<T>::insert(&controller, RewardLedger {
+				free: reward,
+				locked: vec![UnbondingChunk { amount: reward, unlock_at: Zero::zero() }],
+			});
+			controllers.push(controller);
+		}
+		StashPools::<T>::insert(&stash, StashPool { controllers, deposit: Zero::zero() });
+		let amount = reward.saturating_mul((2 * c).saturated_into());
+	}: _(SystemOrigin::Signed(stash.clone()))
+	verify {
+		assert_last_event::<T>(Event::<T>::StashRewardsClaimed { stash, amount }.into());
+	}
+
+	challenge_replica {
+		let cid = str2bytes("QmS9ErDVxHXRNMJRJ5i3bp1zxCZzKP8QXXNH1yeeeeeeeA");
+		let controller = stash_node::<T>();
+		create_file::<T>(&cid, false, &[controller.clone()], 100u32.into());
+		let challenger = create_funded_user::<T>("challenger", 20000);
+		let controller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(controller.clone());
+	}: _(SystemOrigin::Signed(challenger.clone()), cid.clone(), controller_lookup)
+	verify {
+		assert!(Challenges::<T>::contains_key(&controller, &cid));
+	}
+
+	answer_challenge {
+		let cid = str2bytes("QmS9ErDVxHXRNMJRJ5i3bp1zxCZzKP8QXXNH1yeeeeeeeA");
+		let controller = stash_node::<T>();
+		let machine_id: Vec<u8> = hex!("2663554671a5f2c3050e1cec37f31e55").into();
+		let priv_k: Vec<u8> = hex!("e394cf1de366242a772f44904ba475f5317ce8baedac5485ccd812db2ccf28ab").into();
+		let pub_k: Vec<u8> = hex!("87f66db5fe0888c65ddab6940020492fd2fe615413f13d8d9131c478c68c6c80dfa47365bf9fefac29003cf8f169a07662b3c5907511e99e439cde69f396ff82").into();
+		Registers::<T>::insert(&machine_id, RegisterInfo { key: pub_k.clone(), enclave: get_enclave(), ..Default::default() });
+		Nodes::<T>::mutate(&controller, |maybe_node_info| {
+			if let Some(node_info) = maybe_node_info {
+				node_info.machine_id = Some(machine_id.clone());
+			}
+		});
+		create_file::<T>(&cid, false, &[controller.clone()], 100u32.into());
+		let challenger = create_funded_user::<T>("challenger", 20000);
+		let controller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(controller.clone());
+		assert_ok!(FileStorage::<T>::challenge_replica(SystemOrigin::Signed(challenger.clone()).into(), cid.clone(), controller_lookup));
+		let challenged_at: u64 = System::<T>::block_number().saturated_into();
+		let sig = sign::p256_sign_challenge(&machine_id, &priv_k, &pub_k, &cid, challenged_at);
+		let bond = T::ChallengeBond::get();
+	}: _(SystemOrigin::Signed(controller.clone()), cid.clone(), sig)
+	verify {
+		assert_last_event::<T>(Event::<T>::ChallengeAnswered { controller, cid, bond }.into());
+	}
+
+	settle_challenge {
+		let cid = str2bytes("QmS9ErDVxHXRNMJRJ5i3bp1zxCZzKP8QXXNH1yeeeeeeeA");
+		let controller = stash_node::<T>();
+		Nodes::<T>::mutate(&controller, |maybe_node_info| {
+			if let Some(node_info) = maybe_node_info {
+				node_info.used = 1_000_000;
+			}
+		});
+		create_file::<T>(&cid, false, &[controller.clone()], 100u32.into());
+		let challenger = create_funded_user::<T>("challenger", 20000);
+		let controller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(controller.clone());
+		assert_ok!(FileStorage::<T>::challenge_replica(SystemOrigin::Signed(challenger.clone()).into(), cid.clone(), controller_lookup.clone()));
+		let deadline = System::<T>::block_number().saturating_add(T::ChallengeDuration::get());
+		System::<T>::set_block_number(deadline.saturating_add(One::one()));
+	}: _(SystemOrigin::Signed(challenger.clone()), controller_lookup, cid.clone())
+	verify {
+		assert!(!Challenges::<T>::contains_key(&controller, &cid));
+	}
+
 	session_end {
 		Summarys::<T>::insert(0, SummaryInfo { power: 100 * MB2, used: 10 * MB2, ..Default::default() });
 		FileStorage::<T>::session_end();
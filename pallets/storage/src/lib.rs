@@ -26,7 +26,11 @@ pub mod migrations;
 
 use codec::{Decode, Encode};
 use frame_support::{
-	traits::{Currency, ExistenceRequirement, Get, ReservableCurrency, UnixTime},
+	ensure,
+	traits::{
+		BalanceStatus, Currency, ExistenceRequirement, Get, IsSubType, ReservableCurrency, UnixTime,
+	},
+	transactional,
 	weights::Weight,
 	PalletId,
 };
@@ -35,18 +39,30 @@ use p256::ecdsa::{
 	signature::{Signature, Verifier},
 	VerifyingKey,
 };
-use runtime_api::NodeDepositInfo;
+pub use runtime_api::StorageClass;
+use runtime_api::{
+	AccountUsageInfo, FileDiversityInfo, NodeDepositInfo, SponsorshipBudgetInfo, StashDepositInfo,
+};
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AccountIdConversion, One, Saturating, StaticLookup, Zero},
-	Perbill, RuntimeDebug, SaturatedConversion,
+	helpers_128bit::multiply_by_rational,
+	traits::{
+		AccountIdConversion, DispatchInfoOf, One, Saturating, SignedExtension, StaticLookup, Zero,
+	},
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError,
+		ValidTransaction,
+	},
+	DispatchError, DispatchResult, PerThing, Perbill, RuntimeDebug, SaturatedConversion,
 };
-use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, prelude::*};
 
 pub type FileId = Vec<u8>;
 pub type EnclaveId = Vec<u8>;
 pub type PubKey = Vec<u8>;
 pub type MachineId = Vec<u8>;
+pub type MrSigner = Vec<u8>;
+pub type Spki = Vec<u8>;
 pub type SessionIndex = u32;
 pub type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as SystemConfig>::AccountId>>::Balance;
@@ -56,6 +72,21 @@ pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
 pub type NodeInfoOf<T> = NodeInfo<<T as SystemConfig>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
 pub type FileInfoOf<T> = FileInfo<<T as SystemConfig>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
 pub type SessionStateOf<T> = SessionState<BlockNumberFor<T>>;
+pub type NodeBackingOf<T> = NodeBacking<BalanceOf<T>>;
+pub type DelegationOf<T> = Delegation<BalanceOf<T>>;
+pub type UnbondingChunkOf<T> = UnbondingChunk<BalanceOf<T>, BlockNumberFor<T>>;
+pub type RewardLedgerOf<T> = RewardLedger<BalanceOf<T>, BlockNumberFor<T>>;
+pub type RewardDestinationOf<T> = RewardDestination<<T as frame_system::Config>::AccountId>;
+pub type StashPoolOf<T> = StashPool<<T as SystemConfig>::AccountId, BalanceOf<T>>;
+pub type AttestationPolicyOf<T> = AttestationPolicy<BlockNumberFor<T>>;
+pub type ChallengeInfoOf<T> =
+	ChallengeInfo<<T as SystemConfig>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
+pub type SponsorshipOf<T> = Sponsorship<BalanceOf<T>, BlockNumberFor<T>>;
+pub type AccountUsageOf<T> = AccountUsage<BalanceOf<T>>;
+pub type StoreOptionsOf<T> = StoreOptions<<<T as SystemConfig>::Lookup as StaticLookup>::Source>;
+
+/// Precision of accumulated reward per delegation share
+pub const REWARD_PER_SHARE_UNIT: u128 = 1_000_000_000_000;
 
 pub use pallet::*;
 pub use weights::WeightInfo;
@@ -73,6 +104,8 @@ pub struct NodeInfo<AccountId, Balance, BlockNumber> {
 	pub rid: u64,
 	/// Effective storage space
 	pub used: u64,
+	/// Part of effective storage space used by cold files
+	pub cold_used: u64,
 	/// Mine power of node, use this to distribute mining rewards
 	pub power: u64,
 	/// Slash Effective storage space
@@ -96,6 +129,7 @@ impl<AccountId: sp_std::fmt::Debug, Balance: Zero, BlockNumber: Zero>
 			machine_id: None,
 			rid: 0,
 			used: 0,
+			cold_used: 0,
 			power: 0,
 			slash_used: 0,
 			reward: Zero::zero(),
@@ -129,10 +163,15 @@ pub struct SummaryInfo<Balance> {
 	/// Eeffictive storage size
 	#[codec(compact)]
 	pub used: u128,
+	/// Part of effective storage size used by cold files
+	#[codec(compact)]
+	pub cold_used: u128,
 	/// Reward for node power
 	pub mine_reward: Balance,
 	/// Reward for node store file
 	pub store_reward: Balance,
+	/// Part of store reward from cold files, shared by nodes' cold storage size
+	pub cold_store_reward: Balance,
 	/// How many mine reward that already assigned to the node
 	pub paid_mine_reward: Balance,
 	/// How many store reward that already assigned to the node
@@ -156,15 +195,180 @@ pub struct FileInfo<AccountId, Balance, BlockNumber> {
 	pub liquidate_at: BlockNumber,
 	/// Nodes store the file
 	pub replicas: Vec<AccountId>,
+	/// Storage class of file
+	pub class: StorageClass,
+}
+
+/// Where and by whom a storage node is run
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
+pub struct NodeTag {
+	/// Region node located in
+	pub region: Vec<u8>,
+	/// Operator who runs the node
+	pub operator: Vec<u8>,
+	/// Whether the tag is attested by ForceOrigin rather than self-declared
+	pub attested: bool,
 }
 
 /// Node register info
-#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
 pub struct RegisterInfo {
 	/// PUb key to verify signed message
 	pub key: PubKey,
 	/// Tee enclave id
 	pub enclave: EnclaveId,
+	/// Tee enclave signer
+	pub mr_signer: MrSigner,
+	/// Product id of enclave
+	pub isv_prod_id: u16,
+	/// Security version of enclave
+	pub isv_svn: u16,
+}
+
+/// Accept enclaves signed by a signer for a product
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct AttestationPolicy<BlockNumber> {
+	/// The minimum security version of enclave
+	pub min_isv_svn: u16,
+	/// Policy expire at
+	pub expire_at: BlockNumber,
+}
+
+/// Root certificate trusted to sign IAS report, keyed by its DER SubjectPublicKeyInfo
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
+pub struct TrustAnchorInfo {
+	/// DER encoded subject of certificate
+	pub subject: Vec<u8>,
+	/// Anchor is valid from this unix time in seconds
+	pub not_before: u64,
+	/// Anchor is valid until this unix time in seconds
+	pub not_after: u64,
+}
+
+/// Challenge that a node does not hold a replica of file
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ChallengeInfo<AccountId, Balance, BlockNumber> {
+	/// Who raised the challenge
+	pub challenger: AccountId,
+	/// Funds reserved from challenger
+	pub bond: Balance,
+	/// When the challenge raised, node should sign it in the proof
+	pub challenged_at: BlockNumber,
+	/// Node should answer the challenge before this block
+	pub deadline: BlockNumber,
+}
+
+/// Funds delegated to a node
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
+pub struct NodeBacking<Balance> {
+	/// Operator commission of rewards shared with delegators
+	pub commission: Perbill,
+	/// Total delegated funds, decreased when node is slashed
+	pub total: Balance,
+	/// Total shares of delegators
+	pub shares: Balance,
+	/// Accumulated reward per share, scaled by REWARD_PER_SHARE_UNIT
+	pub reward_per_share: u128,
+}
+
+/// Delegation of account to a node
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
+pub struct Delegation<Balance> {
+	/// Shares of node backing
+	pub shares: Balance,
+	/// Rewards of shares that already settled, scaled by REWARD_PER_SHARE_UNIT
+	pub reward_debt: u128,
+	/// Rewards not claimed yet
+	pub unclaimed: Balance,
+}
+
+/// Undelegated funds waiting to be withdrawn
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct UnbondingChunk<Balance, BlockNumber> {
+	/// Amount of funds
+	pub amount: Balance,
+	/// When the funds can be withdrawn
+	pub unlock_at: BlockNumber,
+}
+
+/// Deposit pool shared by nodes of one stash
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
+pub struct StashPool<AccountId, Balance> {
+	/// Nodes funded by the stash
+	pub controllers: Vec<AccountId>,
+	/// Funds not assigned to any node, used to cover node's required deposit
+	pub deposit: Balance,
+}
+
+/// Options of storing file, `store` uses the default options
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct StoreOptions<AccountSource> {
+	/// Storage class of file
+	pub class: StorageClass,
+	/// Account pays the fee instead of caller
+	pub sponsor: Option<AccountSource>,
+	/// Number of chunks if a dag-pb file exceeds MaxFileSize, each chunk is limited by MaxFileSize
+	pub chunks: Option<u32>,
+}
+
+impl<AccountSource> Default for StoreOptions<AccountSource> {
+	fn default() -> Self {
+		StoreOptions { class: StorageClass::Hot, sponsor: None, chunks: None }
+	}
+}
+
+/// Budget of sponsor to pay store fees for whitelisted accounts
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
+pub struct Sponsorship<Balance, BlockNumber> {
+	/// Funds left to pay store fees
+	pub budget: Balance,
+	/// Max fee paid for one store, zero means no per file cap
+	pub max_per_file: Balance,
+	/// Max fees paid in one period
+	pub max_per_period: Balance,
+	/// Length of period in blocks
+	pub period: BlockNumber,
+	/// Current period begin at
+	pub period_start: BlockNumber,
+	/// Fees paid in current period
+	pub period_spent: Balance,
+}
+
+/// Files stored by account and fees it paid for them
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
+pub struct AccountUsage<Balance> {
+	/// Number of files account pays for
+	pub files: u32,
+	/// Total size of files account pays for
+	pub bytes: u64,
+	/// Total store fees paid by or sponsored for account
+	pub paid: Balance,
+}
+
+/// Rewards earned by node but not claimed yet
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
+pub struct RewardLedger<Balance, BlockNumber> {
+	/// Rewards can be claimed
+	pub free: Balance,
+	/// Mine rewards locked until a block number
+	pub locked: Vec<UnbondingChunk<Balance, BlockNumber>>,
+}
+
+/// Where node's rewards are paid to
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum RewardDestination<AccountId> {
+	/// Pay to the stash account
+	Stash,
+	/// Pay to the controller account
+	Controller,
+	/// Pay to another account
+	Account(AccountId),
+}
+
+impl<AccountId> Default for RewardDestination<AccountId> {
+	fn default() -> Self {
+		RewardDestination::Stash
+	}
 }
 
 // A value placed in storage that represents the current version of the Scheduler storage.
@@ -174,6 +378,11 @@ pub struct RegisterInfo {
 pub enum Releases {
 	V0,
 	V1,
+	V2,
+	V3,
+	V4,
+	V5,
+	V6,
 }
 
 impl Default for Releases {
@@ -223,6 +432,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type LiquidateDuration: Get<BlockNumberFor<Self>>;
 
+		/// Number of blocks to liquidate a cold file
+		#[pallet::constant]
+		type ColdLiquidateDuration: Get<BlockNumberFor<Self>>;
+
 		/// The maximum number of replicas order included
 		#[pallet::constant]
 		type MaxFileReplicas: Get<u32>;
@@ -231,14 +444,43 @@ pub mod pallet {
 		#[pallet::constant]
 		type EffectiveFileReplicas: Get<u32>;
 
+		/// The number of reporters of distinct stashes that must agree on file size to store the
+		/// file
+		#[pallet::constant]
+		type FileSizeQuorum: Get<u32>;
+
+		/// The maximum number of replicas of a file in one region
+		#[pallet::constant]
+		type MaxReplicasPerRegion: Get<u32>;
+
+		/// The maximum number of replicas of a file run by one operator
+		#[pallet::constant]
+		type MaxReplicasPerOperator: Get<u32>;
+
+		/// The maximum length of node's region and operator tag
+		#[pallet::constant]
+		type MaxNodeTagLength: Get<u32>;
+
 		/// The maximum file size the network accepts
 		#[pallet::constant]
 		type MaxFileSize: Get<u64>;
 
+		/// The maximum number of chunks in a dag-pb file, each chunk is limited by MaxFileSize
+		#[pallet::constant]
+		type MaxFileChunks: Get<u32>;
+
+		/// The maximum number of accounts paying for one file
+		#[pallet::constant]
+		type MaxFileOwners: Get<u32>;
+
 		/// The maximum power of node
 		#[pallet::constant]
 		type MaxPower: Get<u64>;
 
+		/// Priority of valid reports and heartbeats in transaction pool
+		#[pallet::constant]
+		type ReportPriority: Get<TransactionPriority>;
+
 		/// The maximum number of files in each report
 		#[pallet::constant]
 		type MaxReportFiles: Get<u32>;
@@ -251,6 +493,11 @@ pub mod pallet {
 		#[pallet::constant]
 		type FileSizePrice: Get<BalanceOf<Self>>;
 
+		/// The funds that must be spent for the number of bytes of the cold file, used instead of
+		/// FileSizePrice
+		#[pallet::constant]
+		type ColdFileSizePrice: Get<BalanceOf<Self>>;
+
 		/// The ratio for divide direct store reward and share store reward
 		#[pallet::constant]
 		type StoreRewardRatio: Get<Perbill>;
@@ -267,6 +514,56 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxMineReward: Get<BalanceOf<Self>>;
 
+		/// The minimum amount of funds to delegate to a node
+		#[pallet::constant]
+		type MinDelegation: Get<BalanceOf<Self>>;
+
+		/// Number of blocks that undelegated funds keep locked
+		#[pallet::constant]
+		type UnbondingDuration: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of unbonding chunks of an account
+		#[pallet::constant]
+		type MaxUnbondingChunks: Get<u32>;
+
+		/// Number of blocks that mine rewards keep locked, zero means no lock
+		#[pallet::constant]
+		type RewardLockDuration: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of locked reward chunks of a node, the earliest two chunks merge
+		/// once reached
+		#[pallet::constant]
+		type MaxRewardChunks: Get<u32>;
+
+		/// The maximum number of nodes funded by one stash
+		#[pallet::constant]
+		type MaxStashControllers: Get<u32>;
+
+		/// The maximum age in seconds of IAS report used to register
+		#[pallet::constant]
+		type MaxReportAge: Get<u64>;
+
+		/// Number of blocks that an attestation keeps valid, node should register again before it
+		/// lapses
+		#[pallet::constant]
+		type AttestationValidity: Get<BlockNumberFor<Self>>;
+
+		/// Number of sessions before attestation lapses that node's report will be warned
+		#[pallet::constant]
+		type AttestationWarningSessions: Get<u32>;
+
+		/// The amount of funds challenger should bond to challenge a replica
+		#[pallet::constant]
+		type ChallengeBond: Get<BalanceOf<Self>>;
+
+		/// Number of blocks that node should answer a challenge in
+		#[pallet::constant]
+		type ChallengeDuration: Get<BlockNumberFor<Self>>;
+
+		/// The ratio of slash paid to challenger when node failed to answer
+		#[pallet::constant]
+		type ChallengerRewardRatio: Get<Perbill>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -275,6 +572,23 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type Enclaves<T: Config> = StorageMap<_, Twox64Concat, EnclaveId, BlockNumberFor<T>>;
 
+	/// Attestation policies, keyed by enclave signer and product id
+	#[pallet::storage]
+	pub type AttestationPolicies<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, MrSigner, Twox64Concat, u16, AttestationPolicyOf<T>>;
+
+	/// Root certificates trusted to sign IAS report
+	#[pallet::storage]
+	pub type TrustAnchors<T: Config> = StorageMap<_, Twox64Concat, Spki, TrustAnchorInfo>;
+
+	/// Acceptable IAS quote status besides `OK`
+	#[pallet::storage]
+	pub type AllowedQuoteStatuses<T: Config> = StorageValue<_, Vec<Vec<u8>>, ValueQuery>;
+
+	/// Acceptable advisory ids of IAS report whose quote status is not `OK`
+	#[pallet::storage]
+	pub type AllowedAdvisoryIds<T: Config> = StorageValue<_, Vec<Vec<u8>>, ValueQuery>;
+
 	/// Number of balance reserved to storage pot
 	#[pallet::storage]
 	pub type StoragePotReserved<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
@@ -287,6 +601,31 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type Registers<T: Config> = StorageMap<_, Twox64Concat, MachineId, RegisterInfo>;
 
+	/// Block number before which machine should attest again
+	#[pallet::storage]
+	pub type AttestationDeadlines<T: Config> =
+		StorageMap<_, Twox64Concat, MachineId, BlockNumberFor<T>>;
+
+	#[pallet::type_value]
+	pub fn DefaultNodeClasses() -> Vec<StorageClass> {
+		vec![StorageClass::Hot]
+	}
+
+	/// Storage classes node serves
+	#[pallet::storage]
+	pub type NodeClasses<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Vec<StorageClass>,
+		ValueQuery,
+		DefaultNodeClasses,
+	>;
+
+	/// Region and operator tags of nodes
+	#[pallet::storage]
+	pub type NodeTags<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, NodeTag>;
+
 	/// Current session state
 	#[pallet::storage]
 	pub type Session<T: Config> = StorageValue<_, SessionStateOf<T>, ValueQuery>;
@@ -300,6 +639,89 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type Files<T: Config> = StorageMap<_, Blake2_128Concat, FileId, FileInfoOf<T>>;
 
+	/// Number of chunks declared for dag-pb files exceeding MaxFileSize
+	#[pallet::storage]
+	pub type FileChunks<T: Config> = StorageMap<_, Blake2_128Concat, FileId, u32>;
+
+	/// File sizes reported by nodes, waiting for size quorum
+	#[pallet::storage]
+	pub type FileSizeVotes<T: Config> =
+		StorageMap<_, Blake2_128Concat, FileId, Vec<(T::AccountId, u64)>, ValueQuery>;
+
+	/// Funds delegated to node
+	#[pallet::storage]
+	pub type Backings<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, NodeBackingOf<T>>;
+
+	/// Delegations of account, keyed by node controller and delegator
+	#[pallet::storage]
+	pub type Delegations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		DelegationOf<T>,
+	>;
+
+	/// Undelegated funds of account
+	#[pallet::storage]
+	pub type Unbondings<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Vec<UnbondingChunkOf<T>>, ValueQuery>;
+
+	/// Rewards of node not claimed yet
+	#[pallet::storage]
+	pub type PendingRewards<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, RewardLedgerOf<T>, ValueQuery>;
+
+	/// Deposit pool of stash
+	#[pallet::storage]
+	pub type StashPools<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, StashPoolOf<T>>;
+
+	/// Sponsorship budgets, keyed by sponsor
+	#[pallet::storage]
+	pub type Sponsorships<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, SponsorshipOf<T>>;
+
+	/// Accounts whitelisted by sponsor, keyed by sponsor and beneficiary
+	#[pallet::storage]
+	pub type SponsoredAccounts<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, ()>;
+
+	/// Storage usage of account
+	#[pallet::storage]
+	pub type AccountUsages<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, AccountUsageOf<T>, ValueQuery>;
+
+	/// Max bytes account can store, no limit if absent
+	#[pallet::storage]
+	pub type AccountQuotas<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u64>;
+
+	/// Fees paid for file before size quorum confirms it, at most MaxFileOwners payers
+	#[pallet::storage]
+	pub type FilePayments<T: Config> =
+		StorageMap<_, Blake2_128Concat, FileId, Vec<(T::AccountId, BalanceOf<T>)>, ValueQuery>;
+
+	/// Accounts paying for file and bytes counted to their usage, at most MaxFileOwners
+	#[pallet::storage]
+	pub type FileOwners<T: Config> =
+		StorageMap<_, Blake2_128Concat, FileId, Vec<(T::AccountId, u64)>, ValueQuery>;
+
+	/// Pending challenges, keyed by node controller and file
+	#[pallet::storage]
+	pub type Challenges<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		FileId,
+		ChallengeInfoOf<T>,
+	>;
+
+	/// Where node's rewards are paid to
+	#[pallet::storage]
+	pub type Payees<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, RewardDestinationOf<T>, ValueQuery>;
+
 	/// Storage version of the pallet.
 	///
 	/// New networks start with last version.
@@ -311,12 +733,28 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		/// Add or change enclave.
 		SetEnclave { enclave_id: EnclaveId, expire_at: BlockNumberFor<T> },
+		/// Add, change or remove attestation policy.
+		SetAttestationPolicy {
+			mr_signer: MrSigner,
+			isv_prod_id: u16,
+			policy: Option<AttestationPolicyOf<T>>,
+		},
+		/// Add, change or remove trust anchor.
+		SetTrustAnchor { spki: Spki, anchor: Option<TrustAnchorInfo> },
+		/// Change acceptable quote statuses and advisory ids.
+		SetQuotePolicy { statuses: Vec<Vec<u8>>, advisory_ids: Vec<Vec<u8>> },
 		/// A account have been stashed.
 		Stashed { controller: T::AccountId, amount: BalanceOf<T> },
 		/// A account have withdrawn some founds.
 		Withdrawn { controller: T::AccountId, stash: T::AccountId, amount: BalanceOf<T> },
 		/// A node was registerd.
 		NodeRegistered { controller: T::AccountId, machine_id: MachineId },
+		/// Attestation of a node will lapse soon, node should register again.
+		AttestationExpiring {
+			controller: T::AccountId,
+			machine_id: MachineId,
+			deadline: BlockNumberFor<T>,
+		},
 		/// A node reported its work.
 		NodeReported {
 			controller: T::AccountId,
@@ -326,16 +764,99 @@ pub mod pallet {
 			direct_store_reward: BalanceOf<T>,
 			slash: BalanceOf<T>,
 		},
+		/// A node proved it is alive and was paid its rewards of previous session.
+		NodeHeartbeat {
+			controller: T::AccountId,
+			machine_id: MachineId,
+			session: SessionIndex,
+			mine_reward: BalanceOf<T>,
+			share_store_reward: BalanceOf<T>,
+		},
 		/// A request to store file.
 		FileAdded { cid: FileId, caller: T::AccountId, fee: BalanceOf<T>, first: bool },
+		/// A node changed storage classes it serves.
+		NodeClassesSet { controller: T::AccountId, classes: Vec<StorageClass> },
+		/// Tag of a node was set or removed.
+		NodeTagSet { controller: T::AccountId, tag: Option<NodeTag> },
 		/// A file have been removed.
 		FileDeleted { cid: FileId },
 		/// A node have stored file
 		FileStored { cid: FileId },
+		/// A node reported file size which disagrees with the confirmed size.
+		FileSizeDisputed { cid: FileId, reporter: T::AccountId, reported: u64, confirmed: u64 },
 		/// A file was deleted by admin.
 		FileForceDeleted { cid: FileId },
+		/// Fees of a file never confirmed by size quorum were refunded.
+		FileRefunded { cid: FileId, amount: BalanceOf<T> },
 		/// A session end
 		NewSession { index: SessionIndex, mine: BalanceOf<T> },
+		/// A node changed commission.
+		CommissionSet { controller: T::AccountId, commission: Perbill },
+		/// A account delegated funds to node.
+		Delegated { delegator: T::AccountId, controller: T::AccountId, amount: BalanceOf<T> },
+		/// A account undelegated funds from node.
+		Undelegated {
+			delegator: T::AccountId,
+			controller: T::AccountId,
+			amount: BalanceOf<T>,
+			unlock_at: BlockNumberFor<T>,
+		},
+		/// A account have withdrawn unbonded funds.
+		WithdrawnUnbonded { delegator: T::AccountId, amount: BalanceOf<T> },
+		/// A account claimed delegation rewards.
+		DelegationRewardClaimed {
+			delegator: T::AccountId,
+			controller: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A node changed reward destination.
+		PayeeSet { controller: T::AccountId, payee: RewardDestinationOf<T> },
+		/// A node claimed rewards.
+		RewardsClaimed { controller: T::AccountId, payee: T::AccountId, amount: BalanceOf<T> },
+		/// A stash added funds to its deposit pool.
+		PoolDeposited { stash: T::AccountId, amount: BalanceOf<T> },
+		/// A stash withdrew funds from its deposit pool.
+		PoolWithdrawn { stash: T::AccountId, amount: BalanceOf<T> },
+		/// A stash claimed rewards of its nodes.
+		StashRewardsClaimed { stash: T::AccountId, amount: BalanceOf<T> },
+		/// A sponsor added funds to its budget.
+		SponsorshipFunded { sponsor: T::AccountId, amount: BalanceOf<T> },
+		/// A sponsor withdrew funds from its budget.
+		SponsorshipWithdrawn { sponsor: T::AccountId, amount: BalanceOf<T> },
+		/// A sponsor changed caps of its budget.
+		SponsorshipCapsSet {
+			sponsor: T::AccountId,
+			max_per_file: BalanceOf<T>,
+			max_per_period: BalanceOf<T>,
+			period: BlockNumberFor<T>,
+		},
+		/// A sponsor added or removed an account from its whitelist.
+		BeneficiarySet { sponsor: T::AccountId, beneficiary: T::AccountId, allowed: bool },
+		/// A sponsor paid store fee for an account.
+		FileSponsored {
+			cid: FileId,
+			sponsor: T::AccountId,
+			beneficiary: T::AccountId,
+			fee: BalanceOf<T>,
+		},
+		/// Storage quota of account was set or removed.
+		AccountQuotaSet { who: T::AccountId, quota: Option<u64> },
+		/// A replica of node was challenged.
+		ReplicaChallenged {
+			challenger: T::AccountId,
+			controller: T::AccountId,
+			cid: FileId,
+			deadline: BlockNumberFor<T>,
+		},
+		/// A node answered challenge with proof, challenger's bond goes to node.
+		ChallengeAnswered { controller: T::AccountId, cid: FileId, bond: BalanceOf<T> },
+		/// A node failed to answer challenge, the replica was dropped.
+		ChallengeFailed {
+			controller: T::AccountId,
+			cid: FileId,
+			slash: BalanceOf<T>,
+			reward: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -372,12 +893,70 @@ pub mod pallet {
 		NotEnoughFee,
 		/// File size incorrenct
 		InvalidFileSize,
+		/// Declared chunks of file are invalid
+		InvalidFileChunks,
 		/// Unable to delete file
 		UnableToDeleteFile,
+		/// File does not exist or was confirmed by size quorum
+		UnableToRefundFile,
 		/// Insufficient stash
 		InsufficientDeposit,
 		/// Invalid cid
 		InvalidCid,
+		/// Delegated funds is below T::MinDelegation
+		DelegationTooSmall,
+		/// Account have not delegated to node
+		NoDelegation,
+		/// Too many unbonding chunks
+		TooManyUnbondings,
+		/// No unbonded funds to withdraw
+		NoUnbonded,
+		/// No rewards to claim
+		NoReward,
+		/// Stash funds too many nodes
+		TooManyControllers,
+		/// Node does not store a replica of the file
+		ReplicaNotFound,
+		/// The replica is challenged already
+		ChallengeExists,
+		/// Challenge does not exist
+		ChallengeNotFound,
+		/// Challenge can not be answered after deadline
+		ChallengeExpired,
+		/// Challenge can only be settled after deadline
+		ChallengeNotExpired,
+		/// IAS quote status is not acceptable
+		QuoteStatusNotAllowed,
+		/// IAS report contains unacceptable advisory
+		AdvisoryNotAllowed,
+		/// IAS report is too old
+		ReportTooOld,
+		/// No trust anchor is valid now
+		NoValidTrustAnchor,
+		/// Attestation of node lapsed, node should register again
+		AttestationExpired,
+		/// File already stored in another storage class
+		StorageClassMismatch,
+		/// Node should serve at least one storage class
+		NoStorageClass,
+		/// Region or operator tag is too long
+		TagTooLong,
+		/// Attested tag can only be changed by ForceOrigin
+		TagAttested,
+		/// Session is not the current session
+		InvalidSession,
+		/// Node missed previous session and should report its work
+		ReportRequired,
+		/// Account is not whitelisted by sponsor
+		NotSponsored,
+		/// Store fee exceeds sponsor's per file or per period cap
+		SponsorshipCapExceeded,
+		/// Sponsor's budget is not enough
+		InsufficientSponsorship,
+		/// Account would exceed its storage quota
+		QuotaExceeded,
+		/// File is paid by too many accounts
+		TooManyFileOwners,
 	}
 
 	#[pallet::hooks]
@@ -392,43 +971,67 @@ pub mod pallet {
 		}
 
 		fn on_runtime_upgrade() -> Weight {
+			let mut weight = T::DbWeight::get().reads(1);
 			if StorageVersion::<T>::get() == Releases::V0 {
-				migrations::v1::migrate::<T>()
-			} else {
-				T::DbWeight::get().reads(1)
+				weight = weight.saturating_add(migrations::v1::migrate::<T>());
+			}
+			if StorageVersion::<T>::get() == Releases::V1 {
+				weight = weight.saturating_add(migrations::v2::migrate::<T>());
+			}
+			if StorageVersion::<T>::get() == Releases::V2 {
+				weight = weight.saturating_add(migrations::v3::migrate::<T>());
 			}
+			if StorageVersion::<T>::get() == Releases::V3 {
+				weight = weight.saturating_add(migrations::v4::migrate::<T>());
+			}
+			if StorageVersion::<T>::get() == Releases::V4 {
+				weight = weight.saturating_add(migrations::v5::migrate::<T>());
+			}
+			if StorageVersion::<T>::get() == Releases::V5 {
+				weight = weight.saturating_add(migrations::v6::migrate::<T>());
+			}
+			weight
 		}
 
 		#[cfg(feature = "try-runtime")]
 		fn pre_upgrade() -> Result<(), &'static str> {
-			if StorageVersion::<T>::get() == Releases::V0 {
-				migrations::v1::pre_migrate::<T>()
-			} else {
-				Ok(())
+			match StorageVersion::<T>::get() {
+				Releases::V0 => migrations::v1::pre_migrate::<T>(),
+				Releases::V1 => migrations::v2::pre_migrate::<T>(),
+				_ => Ok(()),
 			}
 		}
 
 		#[cfg(feature = "try-runtime")]
 		fn post_upgrade() -> Result<(), &'static str> {
-			migrations::v1::post_migrate::<T>()
+			migrations::v6::post_migrate::<T>()
 		}
 	}
 
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub enclaves: Vec<(EnclaveId, BlockNumberFor<T>)>,
+		pub quote_statuses: Vec<Vec<u8>>,
+		pub advisory_ids: Vec<Vec<u8>>,
+		/// Trust anchors in (subject, spki, not_before, not_after)
+		pub trust_anchors: Vec<(Vec<u8>, Spki, u64, u64)>,
 	}
 
 	#[cfg(feature = "std")]
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			GenesisConfig { enclaves: Default::default() }
+			GenesisConfig {
+				enclaves: Default::default(),
+				quote_statuses: Default::default(),
+				advisory_ids: Default::default(),
+				trust_anchors: Default::default(),
+			}
 		}
 	}
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
-			StorageVersion::<T>::put(Releases::V1);
+			StorageVersion::<T>::put(Releases::V6);
 			Session::<T>::mutate(|session| {
 				session.begin_at = One::one();
 				session.end_at = T::SessionDuration::get();
@@ -441,6 +1044,18 @@ pub mod pallet {
 			for (code, bn) in &self.enclaves {
 				Enclaves::<T>::insert(code.clone(), bn);
 			}
+			AllowedQuoteStatuses::<T>::put(self.quote_statuses.clone());
+			AllowedAdvisoryIds::<T>::put(self.advisory_ids.clone());
+			for (subject, spki, not_before, not_after) in &self.trust_anchors {
+				TrustAnchors::<T>::insert(
+					spki,
+					TrustAnchorInfo {
+						subject: subject.clone(),
+						not_before: *not_before,
+						not_after: *not_after,
+					},
+				);
+			}
 		}
 	}
 
@@ -463,6 +1078,60 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Add, change or remove policy accepting enclaves by signer and product id
+		#[pallet::weight((T::WeightInfo::set_attestation_policy(), DispatchClass::Operational))]
+		pub fn set_attestation_policy(
+			origin: OriginFor<T>,
+			mr_signer: MrSigner,
+			isv_prod_id: u16,
+			policy: Option<AttestationPolicyOf<T>>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			match &policy {
+				Some(policy) => AttestationPolicies::<T>::insert(&mr_signer, isv_prod_id, policy),
+				None => AttestationPolicies::<T>::remove(&mr_signer, isv_prod_id),
+			}
+			Self::deposit_event(Event::<T>::SetAttestationPolicy {
+				mr_signer,
+				isv_prod_id,
+				policy,
+			});
+			Ok(())
+		}
+
+		/// Add, change or remove root certificate trusted to sign IAS report
+		#[pallet::weight((T::WeightInfo::set_trust_anchor(), DispatchClass::Operational))]
+		pub fn set_trust_anchor(
+			origin: OriginFor<T>,
+			spki: Spki,
+			anchor: Option<TrustAnchorInfo>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			match &anchor {
+				Some(anchor) => TrustAnchors::<T>::insert(&spki, anchor),
+				None => TrustAnchors::<T>::remove(&spki),
+			}
+			Self::deposit_event(Event::<T>::SetTrustAnchor { spki, anchor });
+			Ok(())
+		}
+
+		/// Change acceptable quote statuses and advisory ids of IAS report
+		#[pallet::weight((
+			T::WeightInfo::set_quote_policy(statuses.len() as u32, advisory_ids.len() as u32),
+			DispatchClass::Operational
+		))]
+		pub fn set_quote_policy(
+			origin: OriginFor<T>,
+			statuses: Vec<Vec<u8>>,
+			advisory_ids: Vec<Vec<u8>>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			AllowedQuoteStatuses::<T>::put(statuses.clone());
+			AllowedAdvisoryIds::<T>::put(advisory_ids.clone());
+			Self::deposit_event(Event::<T>::SetQuotePolicy { statuses, advisory_ids });
+			Ok(())
+		}
+
 		/// Stash a account so it can be used for a storage node, the amount of funds to stash is
 		/// T::StashBalance
 		#[pallet::weight(1_000_000)]
@@ -475,27 +1144,24 @@ pub mod pallet {
 			let stash_balance = T::StashBalance::get();
 			if let Some(mut node_info) = Nodes::<T>::get(&controller) {
 				ensure!(&node_info.stash == &stash, Error::<T>::NotPair);
-				let new_deposit =
-					Self::deposit_for_used(node_info.used).saturating_add(stash_balance);
+				let new_deposit = Self::required_deposit(&controller, node_info.used);
 				let amount = new_deposit.saturating_sub(node_info.deposit);
 				if !amount.is_zero() {
-					T::Currency::transfer(
-						&stash,
-						&Self::account_id(),
-						amount,
-						ExistenceRequirement::KeepAlive,
-					)?;
+					Self::transfer_from_pool(&stash, amount)?;
 					node_info.deposit = new_deposit;
 					Nodes::<T>::insert(controller.clone(), node_info);
 					Self::deposit_event(Event::<T>::Stashed { controller, amount });
 				}
 			} else {
-				T::Currency::transfer(
-					&stash,
-					&Self::account_id(),
-					stash_balance,
-					ExistenceRequirement::KeepAlive,
-				)?;
+				let mut pool = StashPools::<T>::get(&stash).unwrap_or_default();
+				ensure!(
+					(pool.controllers.len() as u32) < T::MaxStashControllers::get(),
+					Error::<T>::TooManyControllers
+				);
+				Self::transfer_from_pool(&stash, stash_balance)?;
+				pool = StashPools::<T>::get(&stash).unwrap_or(pool);
+				pool.controllers.push(controller.clone());
+				StashPools::<T>::insert(&stash, pool);
 				Nodes::<T>::insert(
 					&controller,
 					NodeInfo {
@@ -504,6 +1170,7 @@ pub mod pallet {
 						machine_id: None,
 						rid: 0,
 						used: 0,
+						cold_used: 0,
 						power: 0,
 						slash_used: 0,
 						reward: Zero::zero(),
@@ -516,13 +1183,12 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Withdraw the mine reward, node's despoist should not below T::StashBalance
+		/// Withdraw the deposit exceeding what node is required to keep
 		#[pallet::weight(T::WeightInfo::withdraw())]
 		pub fn withdraw(origin: OriginFor<T>) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 			let mut node_info = Nodes::<T>::get(&controller).ok_or(Error::<T>::NodeNotStashed)?;
-			let stash_balance = T::StashBalance::get();
-			let new_deposit = Self::deposit_for_used(node_info.used).saturating_add(stash_balance);
+			let new_deposit = Self::required_deposit(&controller, node_info.used);
 			let amount = node_info.deposit.saturating_sub(new_deposit);
 			ensure!(!amount.is_zero(), Error::<T>::NoEnoughToWithdraw);
 			node_info.deposit = new_deposit;
@@ -567,10 +1233,22 @@ pub mod pallet {
 			#[cfg(feature = "runtime-benchmarks")]
 			let now: u64 = 1627833600;
 			let time_now = webpki::Time::from_seconds_since_unix_epoch(now);
+			let valid_anchors: Vec<(Spki, TrustAnchorInfo)> = TrustAnchors::<T>::iter()
+				.filter(|(_, anchor)| anchor.not_before <= now && now <= anchor.not_after)
+				.collect();
+			ensure!(!valid_anchors.is_empty(), Error::<T>::NoValidTrustAnchor);
+			let anchors: Vec<webpki::TrustAnchor> = valid_anchors
+				.iter()
+				.map(|(spki, anchor)| webpki::TrustAnchor {
+					subject: &anchor.subject,
+					spki,
+					name_constraints: None,
+				})
+				.collect();
 			sig_cert
 				.verify_is_valid_tls_server_cert(
 					SUPPORTED_SIG_ALGS,
-					&IAS_SERVER_ROOTS,
+					&webpki::TlsServerTrustAnchors(&anchors),
 					&chain,
 					time_now,
 				)
@@ -581,47 +1259,89 @@ pub mod pallet {
 				.map_err(|_| Error::<T>::InvalidIASSigningCert)?;
 			let json_body: serde_json::Value =
 				serde_json::from_slice(&ias_body).map_err(|_| Error::<T>::InvalidIASBody)?;
+			#[cfg_attr(feature = "runtime-benchmarks", allow(unused_variables))]
+			let report_at = json_body
+				.get("timestamp")
+				.and_then(|v| v.as_str())
+				.and_then(parse_ias_timestamp)
+				.ok_or(Error::<T>::InvalidIASBody)?;
+			// IAS report used by benchmarks is older than MaxReportAge
+			#[cfg(not(feature = "runtime-benchmarks"))]
+			ensure!(
+				now.saturating_sub(report_at) <= T::MaxReportAge::get(),
+				Error::<T>::ReportTooOld
+			);
+			let quote_status = json_body
+				.get("isvEnclaveQuoteStatus")
+				.and_then(|v| v.as_str())
+				.ok_or(Error::<T>::InvalidIASBody)?;
+			if quote_status != "OK" {
+				ensure!(
+					AllowedQuoteStatuses::<T>::get().iter().any(|v| v == quote_status.as_bytes()),
+					Error::<T>::QuoteStatusNotAllowed
+				);
+				let allowed_advisory_ids = AllowedAdvisoryIds::<T>::get();
+				let advisory_ids = json_body
+					.get("advisoryIDs")
+					.and_then(|v| v.as_array())
+					.cloned()
+					.unwrap_or_default();
+				for advisory_id in advisory_ids.iter() {
+					let advisory_id = advisory_id.as_str().ok_or(Error::<T>::InvalidIASBody)?;
+					ensure!(
+						allowed_advisory_ids.iter().any(|v| v == advisory_id.as_bytes()),
+						Error::<T>::AdvisoryNotAllowed
+					);
+				}
+			}
 			let isv_quote_body = json_body
 				.get("isvEnclaveQuoteBody")
 				.and_then(|v| v.as_str())
 				.ok_or(Error::<T>::InvalidIASBody)?;
 			let isv_quote_body =
 				base64::decode(isv_quote_body).map_err(|_| Error::<T>::InvalidIASBody)?;
+			ensure!(isv_quote_body.len() > 368, Error::<T>::InvalidIASBody);
 			let now_at = Self::now_at();
-			let enclave = &isv_quote_body[112..144].to_vec();
+			let key = isv_quote_body[368..].to_vec();
+			let new_register = RegisterInfo {
+				key: key.clone(),
+				enclave: isv_quote_body[112..144].to_vec(),
+				mr_signer: isv_quote_body[176..208].to_vec(),
+				isv_prod_id: u16::from_le_bytes([isv_quote_body[304], isv_quote_body[305]]),
+				isv_svn: u16::from_le_bytes([isv_quote_body[306], isv_quote_body[307]]),
+			};
 			ensure!(
-				Enclaves::<T>::get(enclave).unwrap_or_default() > now_at,
+				Self::attestation_expire_at(&new_register).unwrap_or_default() > now_at,
 				Error::<T>::InvalidEnclave
 			);
-			let key = &isv_quote_body[368..].to_vec();
 			let data: Vec<u8> =
 				[&ias_cert[..], &ias_sig[..], &ias_body[..], &machine_id[..]].concat();
 			ensure!(verify_p256_sig(&key, &data, &sig), Error::<T>::InvalidVerifyP256Sig);
 
-			match Registers::<T>::get(&machine_id) {
-				Some(mut register) => {
-					register.key = key.clone();
-					register.enclave = enclave.clone();
-					Registers::<T>::insert(&machine_id, register);
-				},
-				None => {
-					Registers::<T>::insert(
-						&machine_id,
-						RegisterInfo { key: key.clone(), enclave: enclave.clone() },
-					);
-					node_info.machine_id = Some(machine_id.clone());
-					Nodes::<T>::insert(&controller, node_info);
-				},
+			if maybe_register_info.is_none() {
+				node_info.machine_id = Some(machine_id.clone());
+				Nodes::<T>::insert(&controller, node_info);
 			}
+			Registers::<T>::insert(&machine_id, new_register);
+			AttestationDeadlines::<T>::insert(
+				&machine_id,
+				now_at.saturating_add(T::AttestationValidity::get()),
+			);
 
 			Self::deposit_event(Event::<T>::NodeRegistered { controller, machine_id });
 			Ok(())
 		}
 
 		/// Report storage work.
+		///
+		/// Report is feeless, `CheckStorageReport` rejects invalid reports before inclusion.
 		#[pallet::weight((
-			T::WeightInfo::report(add_files.len() as u32, del_files.len() as u32),
-			DispatchClass::Operational
+			T::WeightInfo::report(add_files.len() as u32, del_files.len() as u32)
+				.saturating_add(Pallet::<T>::file_owners_weight(
+					(add_files.len() + liquidate_fils.len()) as u32
+				)),
+			DispatchClass::Operational,
+			Pays::No
 		))]
 		pub fn report(
 			origin: OriginFor<T>,
@@ -633,37 +1353,19 @@ pub mod pallet {
 			liquidate_fils: Vec<FileId>,
 		) -> DispatchResult {
 			let reporter = ensure_signed(origin)?;
-			ensure!(
-				add_files.len() <= T::MaxReportFiles::get() as usize ||
-					liquidate_fils.len() <= T::MaxReportFiles::get() as usize,
-				Error::<T>::ReportExceedLimit
-			);
-			let mut node_info = Nodes::<T>::get(&reporter).ok_or(Error::<T>::NodeNotStashed)?;
-			let machine_id =
-				node_info.machine_id.as_ref().ok_or(Error::<T>::UnregisterNode)?.clone();
-
-			ensure!(node_info.deposit >= T::SlashBalance::get(), Error::<T>::InsufficientDeposit);
-
-			let register = Registers::<T>::get(&machine_id).ok_or(Error::<T>::UnregisterNode)?;
+			let (mut node_info, machine_id, attestation_deadline) = Self::check_report(
+				&reporter,
+				rid,
+				power,
+				&sig,
+				&add_files,
+				&del_files,
+				&liquidate_fils,
+			)?;
 			let now_at = Self::now_at();
-			let enclave_bn =
-				Enclaves::<T>::get(&register.enclave).ok_or(Error::<T>::InvalidEnclave)?;
-			ensure!(now_at <= enclave_bn, Error::<T>::InvalidEnclave);
 			let session = Session::<T>::get();
 			let current = session.current;
 			let prev = current.saturating_sub(One::one());
-			ensure!(!Self::is_reported(&node_info, &session), Error::<T>::DuplicateReport);
-			let data: Vec<u8> = [
-				&machine_id[..],
-				&register.key[..],
-				&encode_u64(node_info.rid)[..],
-				&encode_u64(rid)[..],
-				&encode_u64(power)[..],
-				&encode_add_files(&add_files)[..],
-				&encode_del_files(&del_files)[..],
-			]
-			.concat();
-			ensure!(verify_p256_sig(&register.key, &data, &sig), Error::<T>::InvalidVerifyP256Sig);
 
 			let mut ctx: ReportContextOf<T> = ReportContext {
 				now_at,
@@ -672,7 +1374,10 @@ pub mod pallet {
 				storage_pot_add: Zero::zero(),
 				node_changes: BTreeMap::new(),
 				node_infos: BTreeMap::new(),
+				classes: NodeClasses::<T>::get(&reporter),
+				node_tags: BTreeMap::new(),
 				session_store_reward: Zero::zero(),
+				session_cold_store_reward: Zero::zero(),
 			};
 			let mut slash: BalanceOf<T> = Zero::zero();
 
@@ -681,7 +1386,7 @@ pub mod pallet {
 			}
 
 			for (cid, file_size, ..) in add_files.iter() {
-				if file_size > &T::MaxFileSize::get() {
+				if *file_size > Self::max_file_size(cid) {
 					continue
 				}
 				Self::report_add_file(&mut ctx, cid, *file_size);
@@ -690,19 +1395,9 @@ pub mod pallet {
 				Self::report_delete_file(&mut ctx, cid);
 			}
 
-			let mut mine_reward: BalanceOf<T> = Zero::zero();
-			let mut share_store_reward: BalanceOf<T> = Zero::zero();
-			if Self::is_prev_reported(&node_info, &session) {
-				Summarys::<T>::mutate(prev, |mut summary| {
-					let used_ratio = Perbill::from_rational(node_info.used as u128, summary.used);
-					let power_ratio =
-						Perbill::from_rational(node_info.power as u128, summary.power);
-					share_store_reward = used_ratio * summary.store_reward;
-					mine_reward = power_ratio * summary.mine_reward;
-					summary.paid_store_reward =
-						summary.paid_store_reward.saturating_add(share_store_reward);
-					summary.paid_mine_reward = summary.paid_mine_reward.saturating_add(mine_reward);
-				});
+			let (mine_reward, share_store_reward) = if Self::is_prev_reported(&node_info, &session)
+			{
+				Self::settle_session_rewards(&node_info, prev)
 			} else {
 				if !node_info.reported_at.is_zero() {
 					let slash_balance = T::SlashBalance::get();
@@ -712,7 +1407,8 @@ pub mod pallet {
 					ctx.storage_pot_add = ctx.storage_pot_add.saturating_add(node_info.reward);
 					node_info.reward = Zero::zero();
 				}
-			}
+				(Zero::zero(), Zero::zero())
+			};
 
 			let reporter_change = ctx.node_changes.entry(reporter.clone()).or_default();
 			node_info.used = node_info
@@ -720,33 +1416,69 @@ pub mod pallet {
 				.saturating_add(reporter_change.used_inc)
 				.saturating_sub(reporter_change.slash_used_dec)
 				.saturating_sub(reporter_change.used_dec);
+			node_info.cold_used = node_info
+				.cold_used
+				.saturating_add(reporter_change.cold_used_inc)
+				.saturating_sub(reporter_change.cold_used_dec);
 			let reporter_total_used_dec =
 				node_info.slash_used.saturating_add(reporter_change.slash_used_dec);
 			if !reporter_total_used_dec.is_zero() {
 				slash = slash.saturating_add(Self::deposit_for_used(reporter_total_used_dec));
 			}
 			let direct_store_reward = node_info.reward.saturating_add(reporter_change.reward);
-
-			let reporter_new_deposit = node_info
-				.deposit
-				.saturating_add(mine_reward)
+			let total_reward = mine_reward
 				.saturating_add(share_store_reward)
 				.saturating_add(direct_store_reward);
-			let (reporter_new_deposit, storage_pot_add_slash) = if reporter_new_deposit >= slash {
-				(reporter_new_deposit.saturating_sub(slash), slash)
+			let (delegator_reward, delegator_slash) =
+				Self::share_with_delegators(&reporter, node_info.deposit, total_reward, slash);
+			ctx.storage_pot_add = ctx.storage_pot_add.saturating_add(delegator_slash);
+			let node_slash = slash.saturating_sub(delegator_slash);
+
+			let node_reward = total_reward.saturating_sub(delegator_reward);
+			let node_locked_reward = if total_reward.is_zero() {
+				Zero::zero()
+			} else {
+				Perbill::from_rational(mine_reward, total_reward) * node_reward
+			};
+
+			let (reporter_new_deposit, storage_pot_add_slash) = if node_info.deposit >= node_slash {
+				(node_info.deposit.saturating_sub(node_slash), node_slash)
 			} else {
-				(Zero::zero(), reporter_new_deposit)
+				(Zero::zero(), node_info.deposit)
 			};
-			ctx.storage_pot_add = ctx.storage_pot_add.saturating_add(storage_pot_add_slash);
+			let reward_slash = Self::credit_rewards(
+				&reporter,
+				node_reward,
+				node_locked_reward,
+				node_slash.saturating_sub(storage_pot_add_slash),
+			);
+			ctx.storage_pot_add = ctx
+				.storage_pot_add
+				.saturating_add(storage_pot_add_slash)
+				.saturating_add(reward_slash);
 			node_info.deposit = reporter_new_deposit;
+			let required_deposit = Self::required_deposit(&reporter, node_info.used);
+			if node_info.deposit < required_deposit {
+				let covered = Self::cover_from_pool(
+					&node_info.stash,
+					required_deposit.saturating_sub(node_info.deposit),
+				);
+				node_info.deposit = node_info.deposit.saturating_add(covered);
+			}
 			node_info.slash_used = 0;
 			node_info.reward = Zero::zero();
 
 			let mut storage_pot_add_rewards: BalanceOf<T> = Zero::zero();
 			for (account, node_change) in ctx.node_changes.iter() {
 				if account != &reporter {
-					let ReportNodeChange { slash_used_dec, used_dec, used_inc, reward } =
-						node_change;
+					let ReportNodeChange {
+						slash_used_dec,
+						used_dec,
+						used_inc,
+						cold_used_dec,
+						cold_used_inc,
+						reward,
+					} = node_change;
 					Nodes::<T>::mutate(account, |maybe_node| {
 						if let Some(other_node) = maybe_node {
 							other_node.slash_used =
@@ -756,6 +1488,10 @@ pub mod pallet {
 								.saturating_add(*used_inc)
 								.saturating_sub(*slash_used_dec)
 								.saturating_sub(*used_dec);
+							other_node.cold_used = other_node
+								.cold_used
+								.saturating_add(*cold_used_inc)
+								.saturating_sub(*cold_used_dec);
 							other_node.reward = other_node.reward.saturating_add(*reward);
 						} else {
 							storage_pot_add_rewards =
@@ -777,81 +1513,122 @@ pub mod pallet {
 			Summarys::<T>::mutate(current, |summary| {
 				summary.count += 1;
 				summary.used = summary.used.saturating_add(node_info.used.saturated_into());
+				summary.cold_used =
+					summary.cold_used.saturating_add(node_info.cold_used.saturated_into());
 				summary.power = summary.power.saturating_add(node_info.power.saturated_into());
 				summary.store_reward =
 					summary.store_reward.saturating_add(ctx.session_store_reward);
+				summary.cold_store_reward =
+					summary.cold_store_reward.saturating_add(ctx.session_cold_store_reward);
 			});
 			Nodes::<T>::insert(reporter.clone(), node_info);
 
 			Self::deposit_event(Event::<T>::NodeReported {
-				controller: reporter,
-				machine_id,
+				controller: reporter.clone(),
+				machine_id: machine_id.clone(),
 				mine_reward,
 				share_store_reward,
 				direct_store_reward,
 				slash,
 			});
 
+			let warning_period = T::SessionDuration::get()
+				.saturating_mul(T::AttestationWarningSessions::get().into());
+			if now_at.saturating_add(warning_period) >= attestation_deadline {
+				Self::deposit_event(Event::<T>::AttestationExpiring {
+					controller: reporter,
+					machine_id,
+					deadline: attestation_deadline,
+				});
+			}
+
 			Ok(())
 		}
 
-		/// Add file to storage
-		#[pallet::weight(T::WeightInfo::store())]
-		pub fn store(
+		/// Prove node is alive in current session without reporting files.
+		///
+		/// Node either reports or heartbeats in a session, heartbeat is feeless for well funded
+		/// nodes, `CheckStorageReport` rejects invalid heartbeats before inclusion.
+		#[pallet::weight((T::WeightInfo::heartbeat(), DispatchClass::Operational, Pays::No))]
+		pub fn heartbeat(
 			origin: OriginFor<T>,
-			cid: FileId,
-			file_size: u64,
-			fee: BalanceOf<T>,
+			session: SessionIndex,
+			sig: Vec<u8>,
 		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-			ensure!(
-				file_size > 0 && file_size <= T::MaxFileSize::get(),
-				Error::<T>::InvalidFileSize
+			let controller = ensure_signed(origin)?;
+			let (mut node_info, machine_id) = Self::check_heartbeat(&controller, session, &sig)?;
+
+			// Settle previous session here too, otherwise the next report settles the heartbeat
+			// session and the rewards of the session before are never paid
+			let (mine_reward, share_store_reward) =
+				Self::settle_session_rewards(&node_info, session.saturating_sub(1));
+			let total_reward = mine_reward.saturating_add(share_store_reward);
+			let (delegator_reward, _) = Self::share_with_delegators(
+				&controller,
+				node_info.deposit,
+				total_reward,
+				Zero::zero(),
 			);
-			ensure!(is_cid(&cid), Error::<T>::InvalidCid);
-
-			if let Some(mut file) = Files::<T>::get(&cid) {
-				let new_reserved = fee.saturating_add(file.reserved);
-				let min_fee = Self::store_file_bytes_fee(file.file_size);
-				ensure!(new_reserved >= min_fee, Error::<T>::NotEnoughFee);
-				T::Currency::transfer(
-					&who,
-					&Self::account_id(),
-					fee,
-					ExistenceRequirement::KeepAlive,
-				)?;
-				file.reserved = new_reserved;
-				Files::<T>::insert(cid.clone(), file);
-				Self::deposit_event(Event::<T>::FileAdded { cid, caller: who, fee, first: false });
+			let node_reward = total_reward.saturating_sub(delegator_reward);
+			let node_locked_reward = if total_reward.is_zero() {
+				Zero::zero()
 			} else {
-				let min_fee = Self::store_file_fee(file_size);
-				ensure!(fee >= min_fee, Error::<T>::NotEnoughFee);
-				T::Currency::transfer(
-					&who,
-					&Self::account_id(),
-					fee,
-					ExistenceRequirement::KeepAlive,
-				)?;
-				let base_fee = T::FileBaseFee::get();
-				Files::<T>::insert(
-					cid.clone(),
-					FileInfo {
-						reserved: fee.saturating_sub(base_fee),
-						base_fee,
-						file_size,
-						add_at: Self::now_at(),
-						fee: Zero::zero(),
-						liquidate_at: Zero::zero(),
-						replicas: vec![],
-					},
-				);
-				Self::deposit_event(Event::<T>::FileAdded { cid, caller: who, fee, first: true });
-			}
+				Perbill::from_rational(mine_reward, total_reward) * node_reward
+			};
+			Self::credit_rewards(&controller, node_reward, node_locked_reward, Zero::zero());
+
+			node_info.prev_reported_at = node_info.reported_at;
+			node_info.reported_at = Self::now_at();
+			Summarys::<T>::mutate(session, |summary| {
+				summary.count += 1;
+				summary.used = summary.used.saturating_add(node_info.used.saturated_into());
+				summary.cold_used =
+					summary.cold_used.saturating_add(node_info.cold_used.saturated_into());
+				summary.power = summary.power.saturating_add(node_info.power.saturated_into());
+			});
+			Nodes::<T>::insert(&controller, node_info);
+
+			Self::deposit_event(Event::<T>::NodeHeartbeat {
+				controller,
+				machine_id,
+				session,
+				mine_reward,
+				share_store_reward,
+			});
 			Ok(())
 		}
 
+		/// Add hot file to storage, fee is paid by caller
+		#[pallet::weight(T::WeightInfo::store())]
+		pub fn store(
+			origin: OriginFor<T>,
+			cid: FileId,
+			file_size: u64,
+			fee: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_store(who, cid, file_size, fee, StorageClass::Hot, None, None)
+		}
+
+		/// Add file to storage with storage class, fee is paid by sponsor if given, a dag-pb file
+		/// exceeding MaxFileSize must declare its number of chunks
+		#[pallet::weight(T::WeightInfo::store())]
+		pub fn store_with_options(
+			origin: OriginFor<T>,
+			cid: FileId,
+			file_size: u64,
+			fee: BalanceOf<T>,
+			options: StoreOptionsOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let sponsor = options.sponsor.map(T::Lookup::lookup).transpose()?;
+			Self::do_store(who, cid, file_size, fee, options.class, sponsor, options.chunks)
+		}
+
 		/// Force delete unsoloved file
-		#[pallet::weight(T::WeightInfo::force_delete())]
+		#[pallet::weight(
+			T::WeightInfo::force_delete().saturating_add(Pallet::<T>::file_owners_weight(1))
+		)]
 		pub fn force_delete(origin: OriginFor<T>, cid: FileId) -> DispatchResult {
 			T::ForceOrigin::ensure_origin(origin)?;
 
@@ -859,17 +1636,644 @@ pub mod pallet {
 				let now = Self::now_at();
 				ensure!(
 					!file.base_fee.is_zero() &&
-						now > file.add_at.saturating_add(T::LiquidateDuration::get()),
+						now > file.add_at.saturating_add(Self::liquidate_duration(file.class)),
 					Error::<T>::UnableToDeleteFile
 				);
 				StoragePotReserved::<T>::mutate(|v| {
 					*v = v.saturating_add(file.base_fee).saturating_add(file.reserved)
 				});
-				Files::<T>::remove(&cid);
+				Self::remove_file(&cid);
 				Self::deposit_event(Event::<T>::FileForceDeleted { cid });
 			}
 			Ok(())
 		}
+
+		/// Refund fees of file which failed to reach size quorum before its first liquidation,
+		/// fees paid by sponsor are refunded to sponsor
+		#[pallet::weight(
+			T::WeightInfo::refund_file(T::MaxFileOwners::get())
+				.saturating_add(Pallet::<T>::file_owners_weight(1))
+		)]
+		pub fn refund_file(origin: OriginFor<T>, cid: FileId) -> DispatchResult {
+			ensure_signed(origin)?;
+			let file = Files::<T>::get(&cid).ok_or(Error::<T>::UnableToRefundFile)?;
+			ensure!(
+				file.liquidate_at.is_zero() &&
+					Self::now_at() >
+						file.add_at.saturating_add(Self::liquidate_duration(file.class)),
+				Error::<T>::UnableToRefundFile
+			);
+			let mut amount: BalanceOf<T> = Zero::zero();
+			for (payer, paid) in FilePayments::<T>::take(&cid) {
+				T::Currency::transfer(
+					&Self::account_id(),
+					&payer,
+					paid,
+					ExistenceRequirement::KeepAlive,
+				)?;
+				amount = amount.saturating_add(paid);
+			}
+			// fees paid before payments were recorded go to storage pot
+			let unrecorded = file.base_fee.saturating_add(file.reserved).saturating_sub(amount);
+			if !unrecorded.is_zero() {
+				StoragePotReserved::<T>::mutate(|v| *v = v.saturating_add(unrecorded));
+			}
+			Self::remove_file(&cid);
+			Self::deposit_event(Event::<T>::FileRefunded { cid, amount });
+			Ok(())
+		}
+
+		/// Set storage classes node serves, only files of these classes will be added to node
+		#[pallet::weight(T::WeightInfo::set_node_classes())]
+		pub fn set_node_classes(
+			origin: OriginFor<T>,
+			mut classes: Vec<StorageClass>,
+		) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			ensure!(Nodes::<T>::contains_key(&controller), Error::<T>::NodeNotStashed);
+			classes.sort();
+			classes.dedup();
+			ensure!(!classes.is_empty(), Error::<T>::NoStorageClass);
+			NodeClasses::<T>::insert(&controller, classes.clone());
+			Self::deposit_event(Event::<T>::NodeClassesSet { controller, classes });
+			Ok(())
+		}
+
+		/// Declare region and operator of node for information, only tags attested by ForceOrigin
+		/// are used to keep replicas of a file diverse
+		#[pallet::weight(T::WeightInfo::set_node_tag())]
+		pub fn set_node_tag(
+			origin: OriginFor<T>,
+			region: Vec<u8>,
+			operator: Vec<u8>,
+		) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			ensure!(Nodes::<T>::contains_key(&controller), Error::<T>::NodeNotStashed);
+			if let Some(tag) = NodeTags::<T>::get(&controller) {
+				ensure!(!tag.attested, Error::<T>::TagAttested);
+			}
+			Self::do_set_node_tag(controller, Some(NodeTag { region, operator, attested: false }))
+		}
+
+		/// Set attested region and operator of node, or remove the tag of node
+		#[pallet::weight(T::WeightInfo::force_set_node_tag())]
+		pub fn force_set_node_tag(
+			origin: OriginFor<T>,
+			controller: <T::Lookup as StaticLookup>::Source,
+			maybe_tag: Option<(Vec<u8>, Vec<u8>)>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let controller = T::Lookup::lookup(controller)?;
+			let tag =
+				maybe_tag.map(|(region, operator)| NodeTag { region, operator, attested: true });
+			Self::do_set_node_tag(controller, tag)
+		}
+
+		/// Set the commission node takes from rewards shared with delegators
+		#[pallet::weight(T::WeightInfo::set_commission())]
+		pub fn set_commission(origin: OriginFor<T>, commission: Perbill) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			ensure!(Nodes::<T>::contains_key(&controller), Error::<T>::NodeNotStashed);
+			Backings::<T>::mutate(&controller, |maybe_backing| {
+				maybe_backing.get_or_insert_with(Default::default).commission = commission;
+			});
+			Self::deposit_event(Event::<T>::CommissionSet { controller, commission });
+			Ok(())
+		}
+
+		/// Delegate funds to a node, the funds count toward node's deposit
+		#[pallet::weight(T::WeightInfo::delegate())]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			controller: <T::Lookup as StaticLookup>::Source,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let delegator = ensure_signed(origin)?;
+			let controller = T::Lookup::lookup(controller)?;
+			ensure!(Nodes::<T>::contains_key(&controller), Error::<T>::NodeNotStashed);
+			let mut backing = Backings::<T>::get(&controller).unwrap_or_default();
+			let mut delegation = Delegations::<T>::get(&controller, &delegator).unwrap_or_default();
+			let delegated = Self::mul_div(delegation.shares, backing.total, backing.shares);
+			ensure!(
+				delegated.saturating_add(amount) >= T::MinDelegation::get(),
+				Error::<T>::DelegationTooSmall
+			);
+			let shares = if backing.shares.is_zero() {
+				amount
+			} else {
+				Self::mul_div(amount, backing.shares, backing.total)
+			};
+			ensure!(!shares.is_zero(), Error::<T>::DelegationTooSmall);
+			T::Currency::transfer(
+				&delegator,
+				&Self::account_id(),
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			Self::settle_delegation(&backing, &mut delegation);
+			backing.total = backing.total.saturating_add(amount);
+			backing.shares = backing.shares.saturating_add(shares);
+			delegation.shares = delegation.shares.saturating_add(shares);
+			delegation.reward_debt =
+				reward_of_shares(delegation.shares.saturated_into(), backing.reward_per_share);
+			Backings::<T>::insert(&controller, backing);
+			Delegations::<T>::insert(&controller, &delegator, delegation);
+			Self::deposit_event(Event::<T>::Delegated { delegator, controller, amount });
+			Ok(())
+		}
+
+		/// Undelegate funds from a node, the funds can be withdrawn after T::UnbondingDuration
+		#[pallet::weight(T::WeightInfo::undelegate())]
+		pub fn undelegate(
+			origin: OriginFor<T>,
+			controller: <T::Lookup as StaticLookup>::Source,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let delegator = ensure_signed(origin)?;
+			let controller = T::Lookup::lookup(controller)?;
+			let mut delegation =
+				Delegations::<T>::get(&controller, &delegator).ok_or(Error::<T>::NoDelegation)?;
+			let mut backing = Backings::<T>::get(&controller).unwrap_or_default();
+			let delegated = Self::mul_div(delegation.shares, backing.total, backing.shares);
+			ensure!(!delegated.is_zero(), Error::<T>::NoDelegation);
+
+			let (amount, shares) = if delegated.saturating_sub(amount) < T::MinDelegation::get() {
+				// undelegate all when the rest is too small
+				(delegated, delegation.shares)
+			} else {
+				// round up shares to burn so the rest of delegators never lose funds
+				let mut shares = Self::mul_div(amount, backing.shares, backing.total);
+				if Self::mul_div(shares, backing.total, backing.shares) < amount {
+					shares = shares.saturating_add(One::one());
+				}
+				(amount, shares.min(delegation.shares))
+			};
+
+			// node's own deposit must still cover the required deposit once backing drops
+			let maybe_node_info = Nodes::<T>::get(&controller);
+			let mut lacking_deposit: BalanceOf<T> = Zero::zero();
+			if let Some(node_info) = maybe_node_info.as_ref() {
+				let required_deposit = Self::required_deposit_with_backing(
+					node_info.used,
+					backing.total.saturating_sub(amount),
+				);
+				lacking_deposit = required_deposit.saturating_sub(node_info.deposit);
+				if !lacking_deposit.is_zero() {
+					let pool_deposit = StashPools::<T>::get(&node_info.stash)
+						.map(|v| v.deposit)
+						.unwrap_or_default();
+					ensure!(pool_deposit >= lacking_deposit, Error::<T>::InsufficientDeposit);
+				}
+			}
+
+			let unlock_at = Self::now_at().saturating_add(T::UnbondingDuration::get());
+			Unbondings::<T>::try_mutate(&delegator, |chunks| -> DispatchResult {
+				ensure!(
+					(chunks.len() as u32) < T::MaxUnbondingChunks::get(),
+					Error::<T>::TooManyUnbondings
+				);
+				chunks.push(UnbondingChunk { amount, unlock_at });
+				Ok(())
+			})?;
+
+			if let Some(mut node_info) = maybe_node_info {
+				if !lacking_deposit.is_zero() {
+					let covered = Self::cover_from_pool(&node_info.stash, lacking_deposit);
+					node_info.deposit = node_info.deposit.saturating_add(covered);
+					Nodes::<T>::insert(&controller, node_info);
+				}
+			}
+			Self::settle_delegation(&backing, &mut delegation);
+			backing.total = backing.total.saturating_sub(amount);
+			backing.shares = backing.shares.saturating_sub(shares);
+			delegation.shares = delegation.shares.saturating_sub(shares);
+			delegation.reward_debt =
+				reward_of_shares(delegation.shares.saturated_into(), backing.reward_per_share);
+			if delegation.shares.is_zero() && delegation.unclaimed.is_zero() {
+				Delegations::<T>::remove(&controller, &delegator);
+			} else {
+				Delegations::<T>::insert(&controller, &delegator, delegation);
+			}
+			Backings::<T>::insert(&controller, backing);
+			Self::deposit_event(Event::<T>::Undelegated {
+				delegator,
+				controller,
+				amount,
+				unlock_at,
+			});
+			Ok(())
+		}
+
+		/// Withdraw undelegated funds which have been unlocked
+		#[pallet::weight(T::WeightInfo::withdraw_unbonded())]
+		pub fn withdraw_unbonded(origin: OriginFor<T>) -> DispatchResult {
+			let delegator = ensure_signed(origin)?;
+			let now_at = Self::now_at();
+			let mut chunks = Unbondings::<T>::get(&delegator);
+			let mut amount: BalanceOf<T> = Zero::zero();
+			chunks.retain(|chunk| {
+				if chunk.unlock_at <= now_at {
+					amount = amount.saturating_add(chunk.amount);
+					false
+				} else {
+					true
+				}
+			});
+			ensure!(!amount.is_zero(), Error::<T>::NoUnbonded);
+			T::Currency::transfer(
+				&Self::account_id(),
+				&delegator,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			if chunks.is_empty() {
+				Unbondings::<T>::remove(&delegator);
+			} else {
+				Unbondings::<T>::insert(&delegator, chunks);
+			}
+			Self::deposit_event(Event::<T>::WithdrawnUnbonded { delegator, amount });
+			Ok(())
+		}
+
+		/// Claim rewards of delegation
+		#[pallet::weight(T::WeightInfo::claim_delegation_rewards())]
+		pub fn claim_delegation_rewards(
+			origin: OriginFor<T>,
+			controller: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let delegator = ensure_signed(origin)?;
+			let controller = T::Lookup::lookup(controller)?;
+			let mut delegation =
+				Delegations::<T>::get(&controller, &delegator).ok_or(Error::<T>::NoDelegation)?;
+			let backing = Backings::<T>::get(&controller).unwrap_or_default();
+			Self::settle_delegation(&backing, &mut delegation);
+			let amount = delegation.unclaimed;
+			ensure!(!amount.is_zero(), Error::<T>::NoReward);
+			T::Currency::transfer(
+				&Self::account_id(),
+				&delegator,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			delegation.unclaimed = Zero::zero();
+			if delegation.shares.is_zero() {
+				Delegations::<T>::remove(&controller, &delegator);
+			} else {
+				Delegations::<T>::insert(&controller, &delegator, delegation);
+			}
+			Self::deposit_event(Event::<T>::DelegationRewardClaimed {
+				delegator,
+				controller,
+				amount,
+			});
+			Ok(())
+		}
+
+		/// Set where node's rewards are paid to
+		#[pallet::weight(T::WeightInfo::set_payee())]
+		pub fn set_payee(origin: OriginFor<T>, payee: RewardDestinationOf<T>) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			ensure!(Nodes::<T>::contains_key(&controller), Error::<T>::NodeNotStashed);
+			Payees::<T>::insert(&controller, payee.clone());
+			Self::deposit_event(Event::<T>::PayeeSet { controller, payee });
+			Ok(())
+		}
+
+		/// Claim node's unlocked rewards to its payee, rewards are paid to the controller if
+		/// payee is stash but node is gone
+		#[pallet::weight(T::WeightInfo::claim_rewards())]
+		pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			let mut ledger = PendingRewards::<T>::get(&controller);
+			Self::unlock_rewards(&mut ledger);
+			let amount = ledger.free;
+			ensure!(!amount.is_zero(), Error::<T>::NoReward);
+			let payee = match Payees::<T>::get(&controller) {
+				RewardDestination::Stash => Nodes::<T>::get(&controller)
+					.map(|node_info| node_info.stash)
+					.unwrap_or_else(|| controller.clone()),
+				RewardDestination::Controller => controller.clone(),
+				RewardDestination::Account(account) => account,
+			};
+			T::Currency::transfer(
+				&Self::account_id(),
+				&payee,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			ledger.free = Zero::zero();
+			if ledger.locked.is_empty() {
+				PendingRewards::<T>::remove(&controller);
+			} else {
+				PendingRewards::<T>::insert(&controller, ledger);
+			}
+			Self::deposit_event(Event::<T>::RewardsClaimed { controller, payee, amount });
+			Ok(())
+		}
+
+		/// Add funds to stash's deposit pool
+		#[pallet::weight(T::WeightInfo::deposit_pool())]
+		pub fn deposit_pool(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let stash = ensure_signed(origin)?;
+			T::Currency::transfer(
+				&stash,
+				&Self::account_id(),
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			StashPools::<T>::mutate(&stash, |maybe_pool| {
+				let pool = maybe_pool.get_or_insert_with(Default::default);
+				pool.deposit = pool.deposit.saturating_add(amount);
+			});
+			Self::deposit_event(Event::<T>::PoolDeposited { stash, amount });
+			Ok(())
+		}
+
+		/// Withdraw funds from stash's deposit pool
+		#[pallet::weight(T::WeightInfo::withdraw_pool())]
+		pub fn withdraw_pool(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let stash = ensure_signed(origin)?;
+			let mut pool = StashPools::<T>::get(&stash).ok_or(Error::<T>::NoEnoughToWithdraw)?;
+			ensure!(!amount.is_zero() && pool.deposit >= amount, Error::<T>::NoEnoughToWithdraw);
+			T::Currency::transfer(
+				&Self::account_id(),
+				&stash,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			pool.deposit = pool.deposit.saturating_sub(amount);
+			StashPools::<T>::insert(&stash, pool);
+			Self::deposit_event(Event::<T>::PoolWithdrawn { stash, amount });
+			Ok(())
+		}
+
+		/// Add funds to sponsor's budget
+		#[pallet::weight(T::WeightInfo::fund_sponsorship())]
+		pub fn fund_sponsorship(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			T::Currency::transfer(
+				&sponsor,
+				&Self::account_id(),
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			Sponsorships::<T>::mutate(&sponsor, |maybe_sponsorship| {
+				let sponsorship = maybe_sponsorship.get_or_insert_with(Default::default);
+				sponsorship.budget = sponsorship.budget.saturating_add(amount);
+			});
+			Self::deposit_event(Event::<T>::SponsorshipFunded { sponsor, amount });
+			Ok(())
+		}
+
+		/// Withdraw funds from sponsor's budget
+		#[pallet::weight(T::WeightInfo::withdraw_sponsorship())]
+		pub fn withdraw_sponsorship(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			let mut sponsorship =
+				Sponsorships::<T>::get(&sponsor).ok_or(Error::<T>::NoEnoughToWithdraw)?;
+			ensure!(
+				!amount.is_zero() && sponsorship.budget >= amount,
+				Error::<T>::NoEnoughToWithdraw
+			);
+			T::Currency::transfer(
+				&Self::account_id(),
+				&sponsor,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			sponsorship.budget = sponsorship.budget.saturating_sub(amount);
+			Sponsorships::<T>::insert(&sponsor, sponsorship);
+			Self::deposit_event(Event::<T>::SponsorshipWithdrawn { sponsor, amount });
+			Ok(())
+		}
+
+		/// Set caps of sponsor's budget, a zero max per file or a period of zero means no such cap
+		#[pallet::weight(T::WeightInfo::set_sponsorship_caps())]
+		pub fn set_sponsorship_caps(
+			origin: OriginFor<T>,
+			max_per_file: BalanceOf<T>,
+			max_per_period: BalanceOf<T>,
+			period: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			Sponsorships::<T>::mutate(&sponsor, |maybe_sponsorship| {
+				let sponsorship = maybe_sponsorship.get_or_insert_with(Default::default);
+				sponsorship.max_per_file = max_per_file;
+				sponsorship.max_per_period = max_per_period;
+				sponsorship.period = period;
+				sponsorship.period_start = Self::now_at();
+				sponsorship.period_spent = Zero::zero();
+			});
+			Self::deposit_event(Event::<T>::SponsorshipCapsSet {
+				sponsor,
+				max_per_file,
+				max_per_period,
+				period,
+			});
+			Ok(())
+		}
+
+		/// Add or remove an account from sponsor's whitelist
+		#[pallet::weight(T::WeightInfo::set_beneficiary())]
+		pub fn set_beneficiary(
+			origin: OriginFor<T>,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			allowed: bool,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			if allowed {
+				SponsoredAccounts::<T>::insert(&sponsor, &beneficiary, ());
+			} else {
+				SponsoredAccounts::<T>::remove(&sponsor, &beneficiary);
+			}
+			Self::deposit_event(Event::<T>::BeneficiarySet { sponsor, beneficiary, allowed });
+			Ok(())
+		}
+
+		/// Set or remove max bytes an account can store
+		#[pallet::weight(T::WeightInfo::set_account_quota())]
+		pub fn set_account_quota(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			quota: Option<u64>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			match quota {
+				Some(quota) => AccountQuotas::<T>::insert(&who, quota),
+				None => AccountQuotas::<T>::remove(&who),
+			}
+			Self::deposit_event(Event::<T>::AccountQuotaSet { who, quota });
+			Ok(())
+		}
+
+		/// Claim rewards of all stash's nodes paying to the stash
+		#[pallet::weight(T::WeightInfo::claim_stash_rewards(T::MaxStashControllers::get()))]
+		pub fn claim_stash_rewards(origin: OriginFor<T>) -> DispatchResult {
+			let stash = ensure_signed(origin)?;
+			let pool = StashPools::<T>::get(&stash).unwrap_or_default();
+			let mut amount: BalanceOf<T> = Zero::zero();
+			let mut ledgers = vec![];
+			for controller in pool.controllers.iter() {
+				if Payees::<T>::get(controller) != RewardDestination::Stash {
+					continue
+				}
+				let mut ledger = PendingRewards::<T>::get(controller);
+				Self::unlock_rewards(&mut ledger);
+				if ledger.free.is_zero() {
+					continue
+				}
+				amount = amount.saturating_add(ledger.free);
+				ledger.free = Zero::zero();
+				ledgers.push((controller, ledger));
+			}
+			ensure!(!amount.is_zero(), Error::<T>::NoReward);
+			T::Currency::transfer(
+				&Self::account_id(),
+				&stash,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			for (controller, ledger) in ledgers {
+				if ledger.locked.is_empty() {
+					PendingRewards::<T>::remove(controller);
+				} else {
+					PendingRewards::<T>::insert(controller, ledger);
+				}
+			}
+			Self::deposit_event(Event::<T>::StashRewardsClaimed { stash, amount });
+			Ok(())
+		}
+
+		/// Challenge that node does not hold a replica of file, T::ChallengeBond is reserved
+		#[pallet::weight(T::WeightInfo::challenge_replica())]
+		pub fn challenge_replica(
+			origin: OriginFor<T>,
+			cid: FileId,
+			controller: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let challenger = ensure_signed(origin)?;
+			let controller = T::Lookup::lookup(controller)?;
+			let file = Files::<T>::get(&cid).ok_or(Error::<T>::ReplicaNotFound)?;
+			ensure!(file.replicas.contains(&controller), Error::<T>::ReplicaNotFound);
+			ensure!(!Challenges::<T>::contains_key(&controller, &cid), Error::<T>::ChallengeExists);
+			let bond = T::ChallengeBond::get();
+			T::Currency::reserve(&challenger, bond)?;
+			let now_at = Self::now_at();
+			let deadline = now_at.saturating_add(T::ChallengeDuration::get());
+			Challenges::<T>::insert(
+				&controller,
+				&cid,
+				ChallengeInfo {
+					challenger: challenger.clone(),
+					bond,
+					challenged_at: now_at,
+					deadline,
+				},
+			);
+			Self::deposit_event(Event::<T>::ReplicaChallenged {
+				challenger,
+				controller,
+				cid,
+				deadline,
+			});
+			Ok(())
+		}
+
+		/// Answer a challenge with proof of possession signed by node's TEE
+		#[pallet::weight((T::WeightInfo::answer_challenge(), DispatchClass::Operational))]
+		pub fn answer_challenge(origin: OriginFor<T>, cid: FileId, sig: Vec<u8>) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			let challenge =
+				Challenges::<T>::get(&controller, &cid).ok_or(Error::<T>::ChallengeNotFound)?;
+			ensure!(Self::now_at() <= challenge.deadline, Error::<T>::ChallengeExpired);
+			let node_info = Nodes::<T>::get(&controller).ok_or(Error::<T>::NodeNotStashed)?;
+			let machine_id = node_info.machine_id.ok_or(Error::<T>::UnregisterNode)?;
+			let register = Registers::<T>::get(&machine_id).ok_or(Error::<T>::UnregisterNode)?;
+			let data: Vec<u8> = [
+				&machine_id[..],
+				&register.key[..],
+				&cid[..],
+				&encode_u64(challenge.challenged_at.saturated_into())[..],
+			]
+			.concat();
+			ensure!(verify_p256_sig(&register.key, &data, &sig), Error::<T>::InvalidVerifyP256Sig);
+
+			// challenger may have lost part of reserved bond by slashing elsewhere
+			let unmoved = T::Currency::repatriate_reserved(
+				&challenge.challenger,
+				&Self::account_id(),
+				challenge.bond,
+				BalanceStatus::Free,
+			)?;
+			let bond = challenge.bond.saturating_sub(unmoved);
+			PendingRewards::<T>::mutate(&controller, |ledger| {
+				ledger.free = ledger.free.saturating_add(bond);
+			});
+			Challenges::<T>::remove(&controller, &cid);
+			Self::deposit_event(Event::<T>::ChallengeAnswered { controller, cid, bond });
+			Ok(())
+		}
+
+		/// Settle a challenge which node failed to answer before deadline
+		#[pallet::weight(T::WeightInfo::settle_challenge())]
+		#[transactional]
+		pub fn settle_challenge(
+			origin: OriginFor<T>,
+			controller: <T::Lookup as StaticLookup>::Source,
+			cid: FileId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let controller = T::Lookup::lookup(controller)?;
+			let challenge =
+				Challenges::<T>::get(&controller, &cid).ok_or(Error::<T>::ChallengeNotFound)?;
+			ensure!(Self::now_at() > challenge.deadline, Error::<T>::ChallengeNotExpired);
+			Challenges::<T>::remove(&controller, &cid);
+			T::Currency::unreserve(&challenge.challenger, challenge.bond);
+
+			let mut slash: BalanceOf<T> = Zero::zero();
+			let mut reward: BalanceOf<T> = Zero::zero();
+			if let Some(mut file) = Files::<T>::get(&cid) {
+				if let Some(index) = file.replicas.iter().position(|v| v == &controller) {
+					file.replicas.remove(index);
+					let file_size = file.file_size;
+					let class = file.class;
+					Files::<T>::insert(&cid, file);
+					if let Some(mut node_info) = Nodes::<T>::get(&controller) {
+						node_info.used = node_info.used.saturating_sub(file_size);
+						if class == StorageClass::Cold {
+							node_info.cold_used = node_info.cold_used.saturating_sub(file_size);
+						}
+						if (index as u32) < T::MaxFileReplicas::get() {
+							// slashed right away, so challenger is paid from what the node lost
+							// instead of advancing it from storage pot
+							slash = Self::slash_node(
+								&controller,
+								&mut node_info,
+								Self::deposit_for_used(file_size),
+							);
+							reward = T::ChallengerRewardRatio::get() * slash;
+						}
+						Nodes::<T>::insert(&controller, node_info);
+					}
+				}
+			}
+			if !reward.is_zero() {
+				T::Currency::transfer(
+					&Self::account_id(),
+					&challenge.challenger,
+					reward,
+					ExistenceRequirement::KeepAlive,
+				)?;
+			}
+			StoragePotReserved::<T>::mutate(|v| {
+				*v = v.saturating_add(slash.saturating_sub(reward))
+			});
+			Self::deposit_event(Event::<T>::ChallengeFailed { controller, cid, slash, reward });
+			Ok(())
+		}
 	}
 }
 
@@ -887,7 +2291,21 @@ struct ReportContext<AccountId, Balance, BlockNumber> {
 	storage_pot_add: Balance,
 	node_changes: BTreeMap<AccountId, ReportNodeChange<Balance>>,
 	node_infos: BTreeMap<AccountId, NodeInfo<AccountId, Balance, BlockNumber>>,
+	classes: Vec<StorageClass>,
+	node_tags: BTreeMap<AccountId, (Option<Vec<u8>>, Vec<u8>)>,
 	session_store_reward: Balance,
+	session_cold_store_reward: Balance,
+}
+
+impl<AccountId, Balance: Saturating + Copy, BlockNumber>
+	ReportContext<AccountId, Balance, BlockNumber>
+{
+	fn add_store_reward(&mut self, class: StorageClass, reward: Balance) {
+		self.session_store_reward = self.session_store_reward.saturating_add(reward);
+		if class == StorageClass::Cold {
+			self.session_cold_store_reward = self.session_cold_store_reward.saturating_add(reward);
+		}
+	}
 }
 
 #[derive(RuntimeDebug, Default)]
@@ -895,22 +2313,45 @@ struct ReportNodeChange<Balance> {
 	slash_used_dec: u64,
 	used_dec: u64,
 	used_inc: u64,
+	cold_used_dec: u64,
+	cold_used_inc: u64,
 	reward: Balance,
 }
 
+impl<Balance> ReportNodeChange<Balance> {
+	fn inc_used(&mut self, file_size: u64, class: StorageClass) {
+		self.used_inc = self.used_inc.saturating_add(file_size);
+		if class == StorageClass::Cold {
+			self.cold_used_inc = self.cold_used_inc.saturating_add(file_size);
+		}
+	}
+
+	/// Decrease used of node, node will be slashed for the space if `slash` is true
+	fn dec_used(&mut self, file_size: u64, class: StorageClass, slash: bool) {
+		if slash {
+			self.slash_used_dec = self.slash_used_dec.saturating_add(file_size);
+		} else {
+			self.used_dec = self.used_dec.saturating_add(file_size);
+		}
+		if class == StorageClass::Cold {
+			self.cold_used_dec = self.cold_used_dec.saturating_add(file_size);
+		}
+	}
+}
+
 impl<T: Config> Pallet<T> {
 	pub fn account_id() -> T::AccountId {
 		T::PalletId::get().into_account_truncating()
 	}
 
-	pub fn store_fee(file_size: u64, time: BlockNumberFor<T>) -> BalanceOf<T> {
-		let duration = T::LiquidateDuration::get();
+	pub fn store_fee(file_size: u64, time: BlockNumberFor<T>, class: StorageClass) -> BalanceOf<T> {
+		let duration = Self::liquidate_duration(class);
 		let mut sessions: u64 = (time / duration).saturated_into();
 		let rem = time % duration;
 		if !rem.is_zero() {
 			sessions += 1;
 		}
-		Self::store_file_bytes_fee(file_size)
+		Self::store_file_bytes_fee(file_size, class)
 			.saturating_mul(sessions.saturated_into())
 			.saturating_add(T::FileBaseFee::get())
 	}
@@ -919,16 +2360,103 @@ impl<T: Config> Pallet<T> {
 		let stash_balance = T::StashBalance::get();
 		if let Some(node_info) = Nodes::<T>::get(&controller) {
 			let used_deposit = Self::deposit_for_used(node_info.used);
+			let backing_deposit =
+				Backings::<T>::get(&controller).map(|v| v.total).unwrap_or_default();
 			NodeDepositInfo {
 				current_deposit: node_info.deposit,
 				slash_deposit: stash_balance,
 				used_deposit,
+				backing_deposit,
 			}
 		} else {
 			NodeDepositInfo { slash_deposit: stash_balance, ..Default::default() }
 		}
 	}
 
+	/// Diversity of file's replicas, counts distinct attested regions and operators
+	pub fn file_diversity(cid: &FileId) -> Option<FileDiversityInfo> {
+		let file = Files::<T>::get(cid)?;
+		let mut regions: Vec<Vec<u8>> = vec![];
+		let mut operators: Vec<Vec<u8>> = vec![];
+		for replica in file.replicas.iter() {
+			match NodeTags::<T>::get(replica) {
+				Some(tag) if tag.attested => {
+					regions.push(tag.region);
+					operators.push(tag.operator);
+				},
+				_ =>
+					if let Some(node_info) = Nodes::<T>::get(replica) {
+						operators.push(node_info.stash.encode());
+					},
+			}
+		}
+		regions.sort();
+		regions.dedup();
+		operators.sort();
+		operators.dedup();
+		Some(FileDiversityInfo {
+			replicas: file.replicas.len() as u32,
+			regions: regions.len() as u32,
+			operators: operators.len() as u32,
+		})
+	}
+
+	/// Storage usage and quota of account
+	pub fn account_usage(who: &T::AccountId) -> AccountUsageInfo<BalanceOf<T>> {
+		let usage = AccountUsages::<T>::get(who);
+		AccountUsageInfo {
+			files: usage.files,
+			bytes: usage.bytes,
+			paid: usage.paid,
+			quota: AccountQuotas::<T>::get(who),
+		}
+	}
+
+	/// Remaining budget of sponsor, in total and in current period
+	pub fn sponsorship_budget(sponsor: &T::AccountId) -> SponsorshipBudgetInfo<BalanceOf<T>> {
+		match Sponsorships::<T>::get(sponsor) {
+			Some(mut sponsorship) => {
+				Self::refresh_sponsorship_period(&mut sponsorship);
+				let period_remaining = if sponsorship.period.is_zero() {
+					sponsorship.budget
+				} else {
+					sponsorship
+						.max_per_period
+						.saturating_sub(sponsorship.period_spent)
+						.min(sponsorship.budget)
+				};
+				SponsorshipBudgetInfo {
+					budget: sponsorship.budget,
+					period_remaining,
+					max_per_file: sponsorship.max_per_file,
+				}
+			},
+			None => Default::default(),
+		}
+	}
+
+	pub fn stash_info(stash: &T::AccountId) -> StashDepositInfo<T::AccountId, BalanceOf<T>> {
+		let pool = StashPools::<T>::get(stash).unwrap_or_default();
+		let mut info = StashDepositInfo { pool_deposit: pool.deposit, ..Default::default() };
+		for controller in pool.controllers.iter() {
+			if let Some(node_info) = Nodes::<T>::get(controller) {
+				info.nodes_deposit = info.nodes_deposit.saturating_add(node_info.deposit);
+				info.required_deposit = info
+					.required_deposit
+					.saturating_add(Self::required_deposit(controller, node_info.used));
+			}
+			let ledger = PendingRewards::<T>::get(controller);
+			info.pending_rewards = ledger
+				.locked
+				.iter()
+				.fold(info.pending_rewards.saturating_add(ledger.free), |acc, chunk| {
+					acc.saturating_add(chunk.amount)
+				});
+		}
+		info.controllers = pool.controllers;
+		info
+	}
+
 	pub(crate) fn session_end() -> Weight {
 		let session = Session::<T>::get();
 		let current = session.current;
@@ -983,6 +2511,10 @@ impl<T: Config> Pallet<T> {
 
 	fn report_add_file(ctx: &mut ReportContextOf<T>, cid: &FileId, file_size: u64) {
 		if let Some(mut file) = Files::<T>::get(cid) {
+			if !ctx.classes.contains(&file.class) {
+				return
+			}
+			let class = file.class;
 			if !file.liquidate_at.is_zero() {
 				let mut new_nodes = vec![];
 				let mut is_included = false;
@@ -997,51 +2529,168 @@ impl<T: Config> Pallet<T> {
 					} else {
 						let node_change =
 							ctx.node_changes.entry(replica_account.clone()).or_default();
-						if (index as u32) < T::MaxFileReplicas::get() {
-							node_change.slash_used_dec =
-								node_change.slash_used_dec.saturating_add(file_size);
-						} else {
-							node_change.used_dec = node_change.used_dec.saturating_add(file_size);
-						}
+						node_change.dec_used(
+							file_size,
+							class,
+							(index as u32) < T::MaxFileReplicas::get(),
+						);
 					}
 					if replica_account == &ctx.reporter {
 						is_included = true;
 					}
 				}
-				if !is_included && (new_nodes.len() as u32) < T::MaxFileReplicas::get() {
-					new_nodes.push(ctx.reporter.clone());
-					let node_change = ctx.node_changes.entry(ctx.reporter.clone()).or_default();
-					node_change.used_inc = node_change.used_inc.saturating_add(file_size);
+				let reporter = ctx.reporter.clone();
+				if !is_included &&
+					(new_nodes.len() as u32) < T::MaxFileReplicas::get() &&
+					Self::is_diverse(ctx, &reporter, &new_nodes)
+				{
+					new_nodes.push(reporter.clone());
+					let node_change = ctx.node_changes.entry(reporter).or_default();
+					node_change.inc_used(file_size, class);
 				}
 				file.replicas = new_nodes;
 				Files::<T>::insert(cid, file);
-			} else {
-				let is_file_exist = Self::liquidate_file(
-					ctx,
-					cid,
-					&mut file,
-					vec![ctx.reporter.clone()],
-					Some(file_size),
-				);
+			} else if let Some(nodes) = Self::vote_file_size(ctx, cid, file_size) {
+				let is_file_exist =
+					Self::liquidate_file(ctx, cid, &mut file, nodes.clone(), Some(file_size));
 				if is_file_exist {
-					let node_change = ctx.node_changes.entry(ctx.reporter.clone()).or_default();
-					node_change.used_inc = node_change.used_inc.saturating_add(file_size);
+					for node in nodes {
+						let node_change = ctx.node_changes.entry(node).or_default();
+						node_change.inc_used(file_size, class);
+					}
 				}
 			}
 		}
 	}
 
+	/// Region and operator of node, operator defaults to node's stash if node has no attested tag
+	fn node_diversity_tag(
+		ctx: &mut ReportContextOf<T>,
+		account: &T::AccountId,
+	) -> (Option<Vec<u8>>, Vec<u8>) {
+		if let Some(tag) = ctx.node_tags.get(account) {
+			return tag.clone()
+		}
+		let tag = match NodeTags::<T>::get(account) {
+			Some(tag) if tag.attested => (Some(tag.region), tag.operator),
+			_ => (None, Self::node_stash(ctx, account).encode()),
+		};
+		ctx.node_tags.insert(account.clone(), tag.clone());
+		tag
+	}
+
+	fn node_stash(ctx: &mut ReportContextOf<T>, account: &T::AccountId) -> T::AccountId {
+		let node_info = ctx.node_infos.entry(account.clone()).or_insert_with(|| {
+			Nodes::<T>::get(account).unwrap_or_else(|| NodeInfo::new(account.clone()))
+		});
+		node_info.stash.clone()
+	}
+
+	/// Record reporter's vote on file size, returns nodes agreeing on the size once quorum reached
+	fn vote_file_size(
+		ctx: &mut ReportContextOf<T>,
+		cid: &FileId,
+		file_size: u64,
+	) -> Option<Vec<T::AccountId>> {
+		let mut votes = FileSizeVotes::<T>::get(cid);
+		votes.retain(|(node, _)| node != &ctx.reporter);
+		if votes.len() >= T::MaxFileReplicas::get().saturating_mul(2) as usize {
+			votes.remove(0);
+		}
+		votes.push((ctx.reporter.clone(), file_size));
+		let mut stashes = vec![];
+		let mut nodes = vec![];
+		for (node, size) in votes.iter() {
+			if *size != file_size {
+				continue
+			}
+			let stash = Self::node_stash(ctx, node);
+			if !stashes.contains(&stash) {
+				stashes.push(stash);
+			}
+			if (nodes.len() as u32) < T::MaxFileReplicas::get() &&
+				Self::is_diverse(ctx, node, &nodes)
+			{
+				nodes.push(node.clone());
+			}
+		}
+		if (stashes.len() as u32) < T::FileSizeQuorum::get() {
+			FileSizeVotes::<T>::insert(cid, votes);
+			return None
+		}
+		FileSizeVotes::<T>::remove(cid);
+		for (reporter, reported) in votes {
+			if reported != file_size {
+				Self::deposit_event(Event::<T>::FileSizeDisputed {
+					cid: cid.clone(),
+					reporter,
+					reported,
+					confirmed: file_size,
+				});
+			}
+		}
+		Some(nodes)
+	}
+
+	/// Whether adding node to replicas keeps replicas diverse in region and operator
+	fn is_diverse(
+		ctx: &mut ReportContextOf<T>,
+		node: &T::AccountId,
+		replicas: &[T::AccountId],
+	) -> bool {
+		let (region, operator) = Self::node_diversity_tag(ctx, node);
+		let mut same_region = 0u32;
+		let mut same_operator = 0u32;
+		for replica in replicas.iter() {
+			let (replica_region, replica_operator) = Self::node_diversity_tag(ctx, replica);
+			if region.is_some() && replica_region == region {
+				same_region += 1;
+			}
+			if replica_operator == operator {
+				same_operator += 1;
+			}
+		}
+		same_region < T::MaxReplicasPerRegion::get() &&
+			same_operator < T::MaxReplicasPerOperator::get()
+	}
+
+	fn do_set_node_tag(controller: T::AccountId, tag: Option<NodeTag>) -> DispatchResult {
+		match &tag {
+			Some(tag) => {
+				let max_len = T::MaxNodeTagLength::get() as usize;
+				ensure!(
+					tag.region.len() <= max_len && tag.operator.len() <= max_len,
+					Error::<T>::TagTooLong
+				);
+				NodeTags::<T>::insert(&controller, tag);
+			},
+			None => NodeTags::<T>::remove(&controller),
+		}
+		Self::deposit_event(Event::<T>::NodeTagSet { controller, tag });
+		Ok(())
+	}
+
 	fn report_delete_file(ctx: &mut ReportContextOf<T>, cid: &FileId) {
 		if let Some(mut file) = Files::<T>::get(cid) {
+			if file.liquidate_at.is_zero() {
+				FileSizeVotes::<T>::mutate_exists(cid, |maybe_votes| {
+					if let Some(votes) = maybe_votes {
+						votes.retain(|(node, _)| node != &ctx.reporter);
+						if votes.is_empty() {
+							*maybe_votes = None;
+						}
+					}
+				});
+				return
+			}
 			if let Ok(index) = file.replicas.binary_search(&ctx.reporter) {
 				file.replicas.remove(index);
 				let node_change = ctx.node_changes.entry(ctx.reporter.clone()).or_default();
-				if (index as u32) < T::MaxFileReplicas::get() {
-					node_change.slash_used_dec =
-						node_change.slash_used_dec.saturating_add(file.file_size);
-				} else {
-					node_change.used_dec = node_change.used_dec.saturating_add(file.file_size);
-				}
+				node_change.dec_used(
+					file.file_size,
+					file.class,
+					(index as u32) < T::MaxFileReplicas::get(),
+				);
 				Files::<T>::insert(cid, file);
 			}
 		}
@@ -1074,24 +2723,23 @@ impl<T: Config> Pallet<T> {
 					replicas.push(replica_account.clone());
 				} else {
 					let node_change = ctx.node_changes.entry(replica_account.clone()).or_default();
-					if (index as u32) < T::MaxFileReplicas::get() {
-						node_change.slash_used_dec =
-							node_change.slash_used_dec.saturating_add(file.file_size);
-					} else {
-						node_change.used_dec = node_change.used_dec.saturating_add(file.file_size);
-					}
+					node_change.dec_used(
+						file.file_size,
+						file.class,
+						(index as u32) < T::MaxFileReplicas::get(),
+					);
 				}
 			}
 			let is_file_exist = Self::liquidate_file(ctx, cid, &mut file, replicas.clone(), None);
 			if !is_file_exist {
 				for node in replicas.iter() {
 					let node_change = ctx.node_changes.entry(node.clone()).or_default();
-					node_change.used_dec = node_change.used_dec.saturating_add(file.file_size);
+					node_change.dec_used(file.file_size, file.class, false);
 				}
 			}
 			let unpaid_reward = file_fee.saturating_sub(total_order_reward);
 			if !unpaid_reward.is_zero() {
-				ctx.session_store_reward = ctx.session_store_reward.saturating_add(unpaid_reward);
+				ctx.add_store_reward(file.class, unpaid_reward);
 			}
 		}
 	}
@@ -1105,22 +2753,34 @@ impl<T: Config> Pallet<T> {
 	) -> bool {
 		let first = file.liquidate_at.is_zero();
 		let expect_order_fee =
-			Self::store_file_bytes_fee(maybe_file_size.unwrap_or(file.file_size));
+			Self::store_file_bytes_fee(maybe_file_size.unwrap_or(file.file_size), file.class);
 		if let Some(file_size) = maybe_file_size {
 			if first {
 				ctx.storage_pot_add = ctx.storage_pot_add.saturating_add(file.base_fee);
 				// user underreported the file size
 				if file.file_size < file_size && file.reserved < expect_order_fee {
-					let to_reporter_reward = Self::share_ratio() * file.reserved;
-					let node_change = ctx.node_changes.entry(ctx.reporter.clone()).or_default();
-					node_change.reward = node_change.reward.saturating_add(to_reporter_reward);
-					ctx.session_store_reward = ctx
-						.session_store_reward
-						.saturating_add(file.reserved.saturating_sub(to_reporter_reward));
+					let each_reporter_reward = Perbill::from_rational(1, nodes.len().max(1) as u32) *
+						(Self::share_ratio() * file.reserved);
+					let mut to_reporters_reward: BalanceOf<T> = Zero::zero();
+					for node in nodes.iter() {
+						let node_change = ctx.node_changes.entry(node.clone()).or_default();
+						node_change.reward =
+							node_change.reward.saturating_add(each_reporter_reward);
+						to_reporters_reward =
+							to_reporters_reward.saturating_add(each_reporter_reward);
+					}
+					ctx.add_store_reward(
+						file.class,
+						file.reserved.saturating_sub(to_reporters_reward),
+					);
 					Self::delete_file(cid);
 					return false
 				}
 				file.base_fee = Zero::zero();
+				FilePayments::<T>::remove(cid);
+				if file.file_size != file_size {
+					Self::resize_file_owners(cid, file.file_size, file_size);
+				}
 				file.file_size = file_size;
 			}
 		}
@@ -1134,7 +2794,7 @@ impl<T: Config> Pallet<T> {
 			return false
 		}
 		let now_at = Self::now_at();
-		let mut duration = T::LiquidateDuration::get();
+		let mut duration = Self::liquidate_duration(file.class);
 		if order_fee < expect_order_fee {
 			duration = Perbill::from_rational(order_fee, expect_order_fee) * duration;
 		}
@@ -1151,6 +2811,150 @@ impl<T: Config> Pallet<T> {
 		return true
 	}
 
+	/// Check report is acceptable, returns reporter's node info, machine id and attestation
+	/// deadline
+	pub(crate) fn check_report(
+		reporter: &T::AccountId,
+		rid: u64,
+		power: u64,
+		sig: &Vec<u8>,
+		add_files: &Vec<(FileId, u64)>,
+		del_files: &Vec<FileId>,
+		liquidate_files: &Vec<FileId>,
+	) -> Result<(NodeInfoOf<T>, MachineId, BlockNumberFor<T>), DispatchError> {
+		let (node_info, machine_id, register, attestation_deadline) =
+			Self::check_report_state(reporter, add_files, del_files, liquidate_files)?;
+		let data: Vec<u8> = [
+			&machine_id[..],
+			&register.key[..],
+			&encode_u64(node_info.rid)[..],
+			&encode_u64(rid)[..],
+			&encode_u64(power)[..],
+			&encode_add_files(add_files)[..],
+			&encode_del_files(del_files)[..],
+		]
+		.concat();
+		ensure!(verify_p256_sig(&register.key, &data, sig), Error::<T>::InvalidVerifyP256Sig);
+		Ok((node_info, machine_id, attestation_deadline))
+	}
+
+	/// Check report is acceptable without verifying its signature
+	pub(crate) fn check_report_state(
+		reporter: &T::AccountId,
+		add_files: &Vec<(FileId, u64)>,
+		del_files: &Vec<FileId>,
+		liquidate_files: &Vec<FileId>,
+	) -> Result<(NodeInfoOf<T>, MachineId, RegisterInfo, BlockNumberFor<T>), DispatchError> {
+		let max_files = T::MaxReportFiles::get() as usize;
+		ensure!(
+			add_files.len() <= max_files &&
+				del_files.len() <= max_files &&
+				liquidate_files.len() <= max_files,
+			Error::<T>::ReportExceedLimit
+		);
+		let node_info = Nodes::<T>::get(reporter).ok_or(Error::<T>::NodeNotStashed)?;
+		let machine_id = node_info.machine_id.as_ref().ok_or(Error::<T>::UnregisterNode)?.clone();
+
+		ensure!(node_info.deposit >= T::SlashBalance::get(), Error::<T>::InsufficientDeposit);
+
+		let (register, attestation_deadline) = Self::check_attested(&machine_id)?;
+		ensure!(!Self::is_reported(&node_info, &Session::<T>::get()), Error::<T>::DuplicateReport);
+		Ok((node_info, machine_id, register, attestation_deadline))
+	}
+
+	/// Check heartbeat is acceptable, returns node info and machine id of controller
+	pub(crate) fn check_heartbeat(
+		controller: &T::AccountId,
+		session: SessionIndex,
+		sig: &Vec<u8>,
+	) -> Result<(NodeInfoOf<T>, MachineId), DispatchError> {
+		let (node_info, machine_id, register) = Self::check_heartbeat_state(controller, session)?;
+		let data: Vec<u8> =
+			[&machine_id[..], &register.key[..], &encode_u64(session as u64)[..]].concat();
+		ensure!(verify_p256_sig(&register.key, &data, sig), Error::<T>::InvalidVerifyP256Sig);
+		Ok((node_info, machine_id))
+	}
+
+	/// Check heartbeat is acceptable without verifying its signature
+	pub(crate) fn check_heartbeat_state(
+		controller: &T::AccountId,
+		session: SessionIndex,
+	) -> Result<(NodeInfoOf<T>, MachineId, RegisterInfo), DispatchError> {
+		let node_info = Nodes::<T>::get(controller).ok_or(Error::<T>::NodeNotStashed)?;
+		let machine_id = node_info.machine_id.as_ref().ok_or(Error::<T>::UnregisterNode)?.clone();
+		ensure!(
+			node_info.deposit >= Self::required_deposit(controller, node_info.used),
+			Error::<T>::InsufficientDeposit
+		);
+		let (register, _) = Self::check_attested(&machine_id)?;
+		let state = Session::<T>::get();
+		ensure!(session == state.current, Error::<T>::InvalidSession);
+		ensure!(!Self::is_reported(&node_info, &state), Error::<T>::DuplicateReport);
+		ensure!(
+			!node_info.reported_at.is_zero() && Self::is_prev_reported(&node_info, &state),
+			Error::<T>::ReportRequired
+		);
+		Ok((node_info, machine_id, register))
+	}
+
+	/// Check machine's enclave and attestation are valid, returns register info and deadline
+	fn check_attested(
+		machine_id: &MachineId,
+	) -> Result<(RegisterInfo, BlockNumberFor<T>), DispatchError> {
+		let register = Registers::<T>::get(machine_id).ok_or(Error::<T>::UnregisterNode)?;
+		let now_at = Self::now_at();
+		let enclave_bn =
+			Self::attestation_expire_at(&register).ok_or(Error::<T>::InvalidEnclave)?;
+		ensure!(now_at <= enclave_bn, Error::<T>::InvalidEnclave);
+		let attestation_deadline =
+			AttestationDeadlines::<T>::get(machine_id).ok_or(Error::<T>::AttestationExpired)?;
+		ensure!(now_at < attestation_deadline, Error::<T>::AttestationExpired);
+		Ok((register, attestation_deadline))
+	}
+
+	/// Transaction validity of a checked report or heartbeat, one per node in each session
+	pub(crate) fn report_validity(who: &T::AccountId) -> TransactionValidity {
+		let session = Session::<T>::get();
+		let longevity = session
+			.end_at
+			.saturating_sub(Self::now_at())
+			.saturating_add(One::one())
+			.saturated_into::<u64>();
+		ValidTransaction::with_tag_prefix("FileStorageReport")
+			.priority(T::ReportPriority::get())
+			.and_provides((who, session.current))
+			.longevity(longevity)
+			.build()
+	}
+
+	/// Pay node's share of session rewards in summary, returns mine reward and store reward
+	fn settle_session_rewards(
+		node_info: &NodeInfoOf<T>,
+		session: SessionIndex,
+	) -> (BalanceOf<T>, BalanceOf<T>) {
+		Summarys::<T>::mutate(session, |summary| {
+			let hot_used = node_info.used.saturating_sub(node_info.cold_used);
+			let used_ratio = Perbill::from_rational(
+				hot_used as u128,
+				summary.used.saturating_sub(summary.cold_used),
+			);
+			let power_ratio = Perbill::from_rational(node_info.power as u128, summary.power);
+			let mut share_store_reward =
+				used_ratio * summary.store_reward.saturating_sub(summary.cold_store_reward);
+			if node_info.cold_used > 0 {
+				let cold_used_ratio =
+					Perbill::from_rational(node_info.cold_used as u128, summary.cold_used);
+				share_store_reward =
+					share_store_reward.saturating_add(cold_used_ratio * summary.cold_store_reward);
+			}
+			let mine_reward = power_ratio * summary.mine_reward;
+			summary.paid_store_reward =
+				summary.paid_store_reward.saturating_add(share_store_reward);
+			summary.paid_mine_reward = summary.paid_mine_reward.saturating_add(mine_reward);
+			(mine_reward, share_store_reward)
+		})
+	}
+
 	fn is_reported(node_info: &NodeInfoOf<T>, session: &SessionStateOf<T>) -> bool {
 		!node_info.reported_at.is_zero() && node_info.reported_at >= session.begin_at
 	}
@@ -1166,18 +2970,427 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Deposit node's stash should keep, delegated funds count toward it
+	fn required_deposit(controller: &T::AccountId, used: u64) -> BalanceOf<T> {
+		let backing = Backings::<T>::get(controller).map(|v| v.total).unwrap_or_default();
+		Self::required_deposit_with_backing(used, backing)
+	}
+
+	fn required_deposit_with_backing(used: u64, backing: BalanceOf<T>) -> BalanceOf<T> {
+		Self::deposit_for_used(used)
+			.saturating_add(T::StashBalance::get())
+			.saturating_sub(backing)
+			.max(T::SlashBalance::get())
+	}
+
+	/// Share rewards and slash of node with its delegators, returns delegators' part
+	fn share_with_delegators(
+		controller: &T::AccountId,
+		deposit: BalanceOf<T>,
+		reward: BalanceOf<T>,
+		slash: BalanceOf<T>,
+	) -> (BalanceOf<T>, BalanceOf<T>) {
+		Backings::<T>::mutate(controller, |maybe_backing| {
+			let backing = match maybe_backing {
+				Some(backing) if !backing.total.is_zero() && !backing.shares.is_zero() => backing,
+				_ => return (Zero::zero(), Zero::zero()),
+			};
+			let ratio =
+				Perbill::from_rational(backing.total, backing.total.saturating_add(deposit));
+			let delegator_reward = backing.commission.left_from_one() * (ratio * reward);
+			let delegator_slash = (ratio * slash).min(backing.total);
+			if !delegator_reward.is_zero() {
+				let reward_per_share = multiply_by_rational(
+					delegator_reward.saturated_into(),
+					REWARD_PER_SHARE_UNIT,
+					backing.shares.saturated_into(),
+				)
+				.unwrap_or(0);
+				backing.reward_per_share =
+					backing.reward_per_share.saturating_add(reward_per_share);
+			}
+			backing.total = backing.total.saturating_sub(delegator_slash);
+			(delegator_reward, delegator_slash)
+		})
+	}
+
+	/// When the attestation of registered enclave expires, by enclave id or by signer policy
+	fn attestation_expire_at(register: &RegisterInfo) -> Option<BlockNumberFor<T>> {
+		let by_enclave = Enclaves::<T>::get(&register.enclave);
+		let by_policy = AttestationPolicies::<T>::get(&register.mr_signer, register.isv_prod_id)
+			.filter(|policy| register.isv_svn >= policy.min_isv_svn)
+			.map(|policy| policy.expire_at);
+		by_enclave.max(by_policy)
+	}
+
+	/// Pay amount for stash, use funds in deposit pool first
+	fn transfer_from_pool(stash: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		let covered = Self::cover_from_pool(stash, amount);
+		let rest = amount.saturating_sub(covered);
+		if !rest.is_zero() {
+			if let Err(e) = T::Currency::transfer(
+				stash,
+				&Self::account_id(),
+				rest,
+				ExistenceRequirement::KeepAlive,
+			) {
+				Self::refund_pool(stash, covered);
+				return Err(e)
+			}
+		}
+		Ok(())
+	}
+
+	/// Slash node's deposit now, shared with delegators and topped up by free rewards,
+	/// returns slashed funds
+	fn slash_node(
+		controller: &T::AccountId,
+		node_info: &mut NodeInfoOf<T>,
+		slash: BalanceOf<T>,
+	) -> BalanceOf<T> {
+		let (_, delegator_slash) =
+			Self::share_with_delegators(controller, node_info.deposit, Zero::zero(), slash);
+		let node_slash = slash.saturating_sub(delegator_slash);
+		let deposit_slash = node_slash.min(node_info.deposit);
+		node_info.deposit = node_info.deposit.saturating_sub(deposit_slash);
+		let reward_slash = Self::credit_rewards(
+			controller,
+			Zero::zero(),
+			Zero::zero(),
+			node_slash.saturating_sub(deposit_slash),
+		);
+		delegator_slash.saturating_add(deposit_slash).saturating_add(reward_slash)
+	}
+
+	/// Take at most amount from stash's deposit pool, returns taken funds
+	fn cover_from_pool(stash: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+		StashPools::<T>::mutate(stash, |maybe_pool| match maybe_pool {
+			Some(pool) => {
+				let covered = amount.min(pool.deposit);
+				pool.deposit = pool.deposit.saturating_sub(covered);
+				covered
+			},
+			None => Zero::zero(),
+		})
+	}
+
+	fn refund_pool(stash: &T::AccountId, amount: BalanceOf<T>) {
+		if amount.is_zero() {
+			return
+		}
+		StashPools::<T>::mutate(stash, |maybe_pool| {
+			if let Some(pool) = maybe_pool {
+				pool.deposit = pool.deposit.saturating_add(amount);
+			}
+		});
+	}
+
+	/// Add node's rewards to pending ledger, then take the rest of slash from free rewards
+	fn credit_rewards(
+		controller: &T::AccountId,
+		reward: BalanceOf<T>,
+		locked_reward: BalanceOf<T>,
+		slash: BalanceOf<T>,
+	) -> BalanceOf<T> {
+		if reward.is_zero() && slash.is_zero() {
+			return Zero::zero()
+		}
+		PendingRewards::<T>::mutate(controller, |ledger| {
+			Self::unlock_rewards(ledger);
+			let lock_duration = T::RewardLockDuration::get();
+			let locked_reward = if lock_duration.is_zero() { Zero::zero() } else { locked_reward };
+			ledger.free = ledger.free.saturating_add(reward.saturating_sub(locked_reward));
+			if !locked_reward.is_zero() {
+				let unlock_at = Self::now_at().saturating_add(lock_duration);
+				let len = ledger.locked.len();
+				let is_full = len >= T::MaxRewardChunks::get() as usize;
+				match ledger.locked.last_mut() {
+					Some(chunk) if chunk.unlock_at == unlock_at => {
+						chunk.amount = chunk.amount.saturating_add(locked_reward);
+					},
+					Some(chunk) if is_full && len < 2 => {
+						chunk.amount = chunk.amount.saturating_add(locked_reward);
+						chunk.unlock_at = unlock_at;
+					},
+					_ => {
+						if is_full && len >= 2 {
+							// The earliest chunk merges into the next one, so accrued rewards are
+							// locked no longer than the next chunk
+							let earliest = ledger.locked.remove(0);
+							ledger.locked[0].amount =
+								ledger.locked[0].amount.saturating_add(earliest.amount);
+						}
+						ledger.locked.push(UnbondingChunk { amount: locked_reward, unlock_at });
+					},
+				}
+			}
+			let slashed = slash.min(ledger.free);
+			ledger.free = ledger.free.saturating_sub(slashed);
+			slashed
+		})
+	}
+
+	/// Move matured locked rewards to free
+	fn unlock_rewards(ledger: &mut RewardLedgerOf<T>) {
+		let now_at = Self::now_at();
+		let mut unlocked: BalanceOf<T> = Zero::zero();
+		ledger.locked.retain(|chunk| {
+			if chunk.unlock_at <= now_at {
+				unlocked = unlocked.saturating_add(chunk.amount);
+				false
+			} else {
+				true
+			}
+		});
+		ledger.free = ledger.free.saturating_add(unlocked);
+	}
+
+	/// Move rewards accumulated since last settlement to unclaimed
+	fn settle_delegation(backing: &NodeBackingOf<T>, delegation: &mut DelegationOf<T>) {
+		let accrued =
+			reward_of_shares(delegation.shares.saturated_into(), backing.reward_per_share);
+		let pending = accrued.saturating_sub(delegation.reward_debt);
+		delegation.unclaimed = delegation.unclaimed.saturating_add(pending.saturated_into());
+		delegation.reward_debt = accrued;
+	}
+
+	/// Calculate `a * b / c` without overflow
+	fn mul_div(a: BalanceOf<T>, b: BalanceOf<T>, c: BalanceOf<T>) -> BalanceOf<T> {
+		if c.is_zero() {
+			return Zero::zero()
+		}
+		multiply_by_rational(a.saturated_into(), b.saturated_into(), c.saturated_into())
+			.unwrap_or(0)
+			.saturated_into()
+	}
+
+	/// Add file to storage by caller
+	fn do_store(
+		who: T::AccountId,
+		cid: FileId,
+		file_size: u64,
+		fee: BalanceOf<T>,
+		class: StorageClass,
+		sponsor: Option<T::AccountId>,
+		chunks: Option<u32>,
+	) -> DispatchResult {
+		ensure!(is_cid(&cid), Error::<T>::InvalidCid);
+		if let Some(chunks) = chunks {
+			Self::check_file_chunks(&cid, file_size, chunks)?;
+		}
+		ensure!(
+			file_size > 0 && (chunks.is_some() || file_size <= Self::max_file_size(&cid)),
+			Error::<T>::InvalidFileSize
+		);
+
+		if let Some(mut file) = Files::<T>::get(&cid) {
+			ensure!(file.class == class, Error::<T>::StorageClassMismatch);
+			let new_reserved = fee.saturating_add(file.reserved);
+			let min_fee = Self::store_file_bytes_fee(file.file_size, file.class);
+			ensure!(new_reserved >= min_fee, Error::<T>::NotEnoughFee);
+			Self::add_account_usage(&who, &cid, file.file_size, file.class, fee)?;
+			Self::pay_store_fee(&who, sponsor.clone(), &cid, fee)?;
+			if file.liquidate_at.is_zero() {
+				Self::add_file_payment(&cid, sponsor.unwrap_or_else(|| who.clone()), fee)?;
+			}
+			file.reserved = new_reserved;
+			Files::<T>::insert(cid.clone(), file);
+			Self::deposit_event(Event::<T>::FileAdded { cid, caller: who, fee, first: false });
+		} else {
+			let min_fee = Self::store_file_fee(file_size, class);
+			ensure!(fee >= min_fee, Error::<T>::NotEnoughFee);
+			Self::add_account_usage(&who, &cid, file_size, class, fee)?;
+			Self::pay_store_fee(&who, sponsor.clone(), &cid, fee)?;
+			Self::add_file_payment(&cid, sponsor.unwrap_or_else(|| who.clone()), fee)?;
+			let base_fee = T::FileBaseFee::get();
+			if let Some(chunks) = chunks {
+				FileChunks::<T>::insert(&cid, chunks);
+			}
+			Files::<T>::insert(
+				cid.clone(),
+				FileInfo {
+					reserved: fee.saturating_sub(base_fee),
+					base_fee,
+					file_size,
+					add_at: Self::now_at(),
+					fee: Zero::zero(),
+					liquidate_at: Zero::zero(),
+					replicas: vec![],
+					class,
+				},
+			);
+			Self::deposit_event(Event::<T>::FileAdded { cid, caller: who, fee, first: true });
+		}
+		Ok(())
+	}
+
+	/// Pay store fee from sponsor's budget if sponsor is given, otherwise from payer
+	fn pay_store_fee(
+		who: &T::AccountId,
+		sponsor: Option<T::AccountId>,
+		cid: &FileId,
+		fee: BalanceOf<T>,
+	) -> DispatchResult {
+		let sponsor = match sponsor {
+			Some(sponsor) => sponsor,
+			None =>
+				return T::Currency::transfer(
+					who,
+					&Self::account_id(),
+					fee,
+					ExistenceRequirement::KeepAlive,
+				),
+		};
+		ensure!(SponsoredAccounts::<T>::contains_key(&sponsor, who), Error::<T>::NotSponsored);
+		let mut sponsorship = Sponsorships::<T>::get(&sponsor).ok_or(Error::<T>::NotSponsored)?;
+		Self::refresh_sponsorship_period(&mut sponsorship);
+		ensure!(
+			sponsorship.max_per_file.is_zero() || fee <= sponsorship.max_per_file,
+			Error::<T>::SponsorshipCapExceeded
+		);
+		let period_spent = sponsorship.period_spent.saturating_add(fee);
+		ensure!(
+			sponsorship.period.is_zero() || period_spent <= sponsorship.max_per_period,
+			Error::<T>::SponsorshipCapExceeded
+		);
+		ensure!(sponsorship.budget >= fee, Error::<T>::InsufficientSponsorship);
+		sponsorship.budget = sponsorship.budget.saturating_sub(fee);
+		sponsorship.period_spent = period_spent;
+		Sponsorships::<T>::insert(&sponsor, sponsorship);
+		Self::deposit_event(Event::<T>::FileSponsored {
+			cid: cid.clone(),
+			sponsor,
+			beneficiary: who.clone(),
+			fee,
+		});
+		Ok(())
+	}
+
+	/// Start a new period of sponsorship if current period is over
+	fn refresh_sponsorship_period(sponsorship: &mut SponsorshipOf<T>) {
+		if sponsorship.period.is_zero() {
+			return
+		}
+		let now_at = Self::now_at();
+		if now_at >= sponsorship.period_start.saturating_add(sponsorship.period) {
+			sponsorship.period_start = now_at;
+			sponsorship.period_spent = Zero::zero();
+		}
+	}
+
 	fn delete_file(cid: &FileId) {
-		Files::<T>::remove(cid);
+		Self::remove_file(cid);
 		Self::deposit_event(Event::<T>::FileDeleted { cid: cid.clone() });
 	}
 
+	/// Remove file and release its bytes from owners' usage
+	fn remove_file(cid: &FileId) {
+		Files::<T>::remove(cid);
+		FileChunks::<T>::remove(cid);
+		FileSizeVotes::<T>::remove(cid);
+		FilePayments::<T>::remove(cid);
+		for (owner, bytes) in FileOwners::<T>::take(cid) {
+			AccountUsages::<T>::mutate(&owner, |usage| {
+				usage.files = usage.files.saturating_sub(1);
+				usage.bytes = usage.bytes.saturating_sub(bytes);
+			});
+		}
+	}
+
+	/// Count file and fee to account's usage, the file is counted once per account and its bytes
+	/// are counted in proportion to the fee paid for the whole file
+	fn add_account_usage(
+		who: &T::AccountId,
+		cid: &FileId,
+		file_size: u64,
+		class: StorageClass,
+		fee: BalanceOf<T>,
+	) -> DispatchResult {
+		let full_fee = Self::store_file_fee(file_size, class);
+		let paid_bytes: u64 = if fee >= full_fee {
+			file_size
+		} else {
+			Self::mul_div(file_size.saturated_into(), fee, full_fee).saturated_into()
+		};
+		let mut usage = AccountUsages::<T>::get(who);
+		let mut owners = FileOwners::<T>::get(cid);
+		let index = match owners.iter().position(|(owner, _)| owner == who) {
+			Some(index) => index,
+			None => {
+				ensure!(
+					(owners.len() as u32) < T::MaxFileOwners::get(),
+					Error::<T>::TooManyFileOwners
+				);
+				usage.files = usage.files.saturating_add(1);
+				owners.push((who.clone(), 0));
+				owners.len() - 1
+			},
+		};
+		let bytes = &mut owners[index].1;
+		let added_bytes = bytes.saturating_add(paid_bytes).min(file_size).saturating_sub(*bytes);
+		let total_bytes = usage.bytes.saturating_add(added_bytes);
+		if let Some(quota) = AccountQuotas::<T>::get(who) {
+			ensure!(total_bytes <= quota, Error::<T>::QuotaExceeded);
+		}
+		*bytes = bytes.saturating_add(added_bytes);
+		usage.bytes = total_bytes;
+		usage.paid = usage.paid.saturating_add(fee);
+		FileOwners::<T>::insert(cid, owners);
+		AccountUsages::<T>::insert(who, usage);
+		Ok(())
+	}
+
+	/// Rescale owners' usage with the file size agreed by reporters
+	fn resize_file_owners(cid: &FileId, old_size: u64, new_size: u64) {
+		let mut owners = FileOwners::<T>::get(cid);
+		for (owner, bytes) in owners.iter_mut() {
+			let new_bytes = if old_size.is_zero() {
+				new_size
+			} else {
+				multiply_by_rational(*bytes as u128, new_size as u128, old_size as u128)
+					.unwrap_or(0)
+					.saturated_into()
+			};
+			AccountUsages::<T>::mutate(&*owner, |usage| {
+				usage.bytes = usage.bytes.saturating_sub(*bytes).saturating_add(new_bytes);
+			});
+			*bytes = new_bytes;
+		}
+		FileOwners::<T>::insert(cid, owners);
+	}
+
+	/// Weight of releasing or rescaling owners' usage of files, each has at most MaxFileOwners
+	pub(crate) fn file_owners_weight(files: u32) -> Weight {
+		let owners = T::MaxFileOwners::get() as Weight;
+		T::DbWeight::get()
+			.reads_writes(owners.saturating_add(2), owners.saturating_add(2))
+			.saturating_mul(files as Weight)
+	}
+
+	/// Record fee paid for file not confirmed yet, so it can be refunded if quorum is never reached
+	fn add_file_payment(cid: &FileId, payer: T::AccountId, fee: BalanceOf<T>) -> DispatchResult {
+		FilePayments::<T>::try_mutate(cid, |payments| {
+			match payments.iter_mut().find(|(account, _)| account == &payer) {
+				Some((_, paid)) => *paid = paid.saturating_add(fee),
+				None => {
+					ensure!(
+						(payments.len() as u32) < T::MaxFileOwners::get(),
+						Error::<T>::TooManyFileOwners
+					);
+					payments.push((payer, fee));
+				},
+			}
+			Ok(())
+		})
+	}
+
 	/// Reserved deposit balance for node's used storage space
 	fn deposit_for_used(space: u64) -> BalanceOf<T> {
-		Self::share_ratio() * Self::store_file_bytes_fee(space)
+		Self::share_ratio() * Self::store_file_bytes_fee(space, StorageClass::Hot)
 	}
 
-	fn store_file_fee(file_size: u64) -> BalanceOf<T> {
-		T::FileBaseFee::get().saturating_add(Self::store_file_bytes_fee(file_size))
+	fn store_file_fee(file_size: u64, class: StorageClass) -> BalanceOf<T> {
+		T::FileBaseFee::get().saturating_add(Self::store_file_bytes_fee(file_size, class))
 	}
 
 	fn share_ratio() -> Perbill {
@@ -1185,12 +3398,41 @@ impl<T: Config> Pallet<T> {
 			T::StoreRewardRatio::get()
 	}
 
-	fn store_file_bytes_fee(file_size: u64) -> BalanceOf<T> {
+	/// The maximum size of file, only dag-pb file declared with chunks may exceed MaxFileSize
+	fn max_file_size(cid: &FileId) -> u64 {
+		let chunks = FileChunks::<T>::get(cid).unwrap_or(1);
+		T::MaxFileSize::get().saturating_mul(chunks as u64)
+	}
+
+	/// Check declared number of chunks covers file size. The chain can't see the DAG links, so
+	/// this only bounds the size, the file size itself is confirmed by size quorum of reporters
+	fn check_file_chunks(cid: &FileId, file_size: u64, chunks: u32) -> DispatchResult {
+		ensure!(is_dag_pb_cid(cid), Error::<T>::InvalidFileChunks);
+		ensure!(chunks > 0 && chunks <= T::MaxFileChunks::get(), Error::<T>::InvalidFileChunks);
+		ensure!(
+			file_size <= T::MaxFileSize::get().saturating_mul(chunks as u64),
+			Error::<T>::InvalidFileSize
+		);
+		Ok(())
+	}
+
+	fn store_file_bytes_fee(file_size: u64, class: StorageClass) -> BalanceOf<T> {
 		let mut file_size_in_mega = file_size / 1_048_576;
 		if file_size % 1_048_576 != 0 {
 			file_size_in_mega += 1;
 		}
-		T::FileSizePrice::get().saturating_mul(file_size_in_mega.saturated_into())
+		let price = match class {
+			StorageClass::Hot => T::FileSizePrice::get(),
+			StorageClass::Cold => T::ColdFileSizePrice::get(),
+		};
+		price.saturating_mul(file_size_in_mega.saturated_into())
+	}
+
+	fn liquidate_duration(class: StorageClass) -> BlockNumberFor<T> {
+		match class {
+			StorageClass::Hot => T::LiquidateDuration::get(),
+			StorageClass::Cold => T::ColdLiquidateDuration::get(),
+		}
 	}
 
 	fn now_at() -> BlockNumberFor<T> {
@@ -1198,7 +3440,122 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+/// Validate `report` and `heartbeat` before they enter transaction pool.
+///
+/// Both calls are feeless, so enclave signature and session uniqueness are checked here to
+/// keep invalid ones out of blocks. Valid ones get `Config::ReportPriority`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckStorageReport<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckStorageReport<T> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+
+	fn invalid(err: DispatchError) -> TransactionValidityError {
+		let invalid = if err == Error::<T>::DuplicateReport.into() {
+			InvalidTransaction::Stale
+		} else if err == Error::<T>::InvalidVerifyP256Sig.into() {
+			InvalidTransaction::BadProof
+		} else if err == Error::<T>::InsufficientDeposit.into() {
+			InvalidTransaction::Payment
+		} else {
+			InvalidTransaction::Call
+		};
+		invalid.into()
+	}
+}
+
+impl<T: Config + Send + Sync> Default for CheckStorageReport<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckStorageReport<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckStorageReport")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckStorageReport<T>
+where
+	<T as SystemConfig>::Call: IsSubType<Call<T>>,
+{
+	const IDENTIFIER: &'static str = "CheckStorageReport";
+	type AccountId = T::AccountId;
+	type Call = <T as SystemConfig>::Call;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		match call.is_sub_type() {
+			Some(Call::report { rid, power, sig, add_files, del_files, liquidate_fils }) => {
+				Pallet::<T>::check_report(
+					who,
+					*rid,
+					*power,
+					sig,
+					add_files,
+					del_files,
+					liquidate_fils,
+				)
+				.map_err(Self::invalid)?;
+				Pallet::<T>::report_validity(who)
+			},
+			Some(Call::heartbeat { session, sig }) => {
+				Pallet::<T>::check_heartbeat(who, *session, sig).map_err(Self::invalid)?;
+				Pallet::<T>::report_validity(who)
+			},
+			_ => Ok(Default::default()),
+		}
+	}
+
+	/// Signature was verified when entering the pool and is verified again in dispatch, which
+	/// the call weight covers, so only the cheap state checks run here.
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		match call.is_sub_type() {
+			Some(Call::report { add_files, del_files, liquidate_fils, .. }) => {
+				Pallet::<T>::check_report_state(who, add_files, del_files, liquidate_fils)
+					.map_err(Self::invalid)?;
+			},
+			Some(Call::heartbeat { session, .. }) => {
+				Pallet::<T>::check_heartbeat_state(who, *session).map_err(Self::invalid)?;
+			},
+			_ => {},
+		}
+		Ok(())
+	}
+}
+
 pub fn verify_p256_sig(pk: &Vec<u8>, data: &Vec<u8>, sig: &Vec<u8>) -> bool {
+	// Both key and signature are two 32 bytes scalars
+	if pk.len() != 64 || sig.len() != 64 {
+		return false
+	}
 	let mut pk = pk.clone();
 	let mut sig = sig.clone();
 
@@ -1217,6 +3574,35 @@ pub fn verify_p256_sig(pk: &Vec<u8>, data: &Vec<u8>, sig: &Vec<u8>) -> bool {
 	false
 }
 
+/// Parse IAS report timestamp like `2021-07-21T07:23:39.696594` (UTC) to unix seconds
+fn parse_ias_timestamp(value: &str) -> Option<u64> {
+	let bytes = value.as_bytes();
+	if bytes.len() < 19 || bytes[4] != b'-' || bytes[7] != b'-' || bytes[10] != b'T' {
+		return None
+	}
+	let num = |start: usize, end: usize| -> Option<u64> {
+		core::str::from_utf8(&bytes[start..end]).ok()?.parse::<u64>().ok()
+	};
+	let (year, month, day) = (num(0, 4)?, num(5, 7)?, num(8, 10)?);
+	let (hour, minute, second) = (num(11, 13)?, num(14, 16)?, num(17, 19)?);
+	if year < 1970 || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+		return None
+	}
+	// days from civil, see http://howardhinnant.github.io/date_algorithms.html
+	let y = if month <= 2 { year - 1 } else { year };
+	let era = y / 400;
+	let yoe = y - era * 400;
+	let mp = (month + 9) % 12;
+	let doy = (153 * mp + 2) / 5 + day - 1;
+	let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+	let days = (era * 146097 + doe).checked_sub(719468)?;
+	Some(days * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+fn reward_of_shares(shares: u128, reward_per_share: u128) -> u128 {
+	multiply_by_rational(shares, reward_per_share, REWARD_PER_SHARE_UNIT).unwrap_or(0)
+}
+
 fn encode_u64(number: u64) -> Vec<u8> {
 	let mut value = number;
 	let mut encoded_number: Vec<u8> = [].to_vec();
@@ -1257,7 +3643,7 @@ fn is_cid(cid: &[u8]) -> bool {
 				_ => false,
 			})
 	} else if len == 59 {
-		return &cid[0..7] == b"bafkrei" &&
+		return (&cid[0..7] == b"bafkrei" || &cid[0..7] == b"bafybei") &&
 			cid[7..].iter().all(|x| match *x {
 				49..=57 | 65..=90 | 97..=122 => true,
 				_ => false,
@@ -1265,3 +3651,9 @@ fn is_cid(cid: &[u8]) -> bool {
 	}
 	false
 }
+
+/// CIDv0 and CIDv1 with dag-pb codec, the codec of UnixFS files that may link chunks
+fn is_dag_pb_cid(cid: &[u8]) -> bool {
+	let len = cid.len();
+	(len == 46 && &cid[0..2] == b"Qm") || (len == 59 && &cid[0..7] == b"bafybei")
+}
@@ -117,6 +117,36 @@ pub mod v1 {
 		OldNodeInfo<BlockNumberFor<T>>,
 	>;
 
+	/// Nodes in layout of v1, translated to current layout by v5
+	#[allow(type_alias_bounds)]
+	pub type V1Nodes<T: Config> = StorageMap<
+		NodesInstance<T>,
+		Blake2_128Concat,
+		T::AccountId,
+		v5::OldNodeInfo<T::AccountId, BalanceOf<T>, BlockNumberFor<T>>,
+	>;
+
+	generate_storage_instance!(FileStorage, Summarys, SummarysInstance);
+	/// Summarys in layout of v1, translated to current layout by v5
+	#[allow(type_alias_bounds)]
+	pub type V1Summarys<T: Config> = StorageMap<
+		SummarysInstance<T>,
+		Twox64Concat,
+		SessionIndex,
+		v5::OldSummaryInfo<BalanceOf<T>>,
+		ValueQuery,
+	>;
+
+	generate_storage_instance!(FileStorage, Files, FilesInstance);
+	/// Files in layout of v1, translated to current layout by v5
+	#[allow(type_alias_bounds)]
+	pub type V1Files<T: Config> = StorageMap<
+		FilesInstance<T>,
+		Blake2_128Concat,
+		FileId,
+		v5::OldFileInfo<T::AccountId, BalanceOf<T>, BlockNumberFor<T>>,
+	>;
+
 	generate_storage_instance!(FileStorage, CurrentRound, CurrentRoundInstance);
 	#[allow(type_alias_bounds)]
 	pub type CurrentRound<T: Config> =
@@ -212,9 +242,9 @@ pub mod v1 {
 				if node_info.reported_at >= begin_at {
 					reported_node_count += 1;
 				}
-				Nodes::<T>::insert(
+				V1Nodes::<T>::insert(
 					controller,
-					NodeInfo {
+					v5::OldNodeInfo {
 						stash: stash_info.stasher,
 						deposit: stash_info.deposit,
 						machine_id: stash_info.machine_id,
@@ -229,9 +259,9 @@ pub mod v1 {
 				);
 				node_count += 1;
 			} else {
-				Nodes::<T>::insert(
+				V1Nodes::<T>::insert(
 					controller,
-					NodeInfo {
+					v5::OldNodeInfo {
 						stash: stash_info.stasher,
 						deposit: stash_info.deposit,
 						machine_id: stash_info.machine_id,
@@ -252,9 +282,9 @@ pub mod v1 {
 			let summary = RoundsSummary::<T>::take(round);
 			let reward = RoundsReward::<T>::take(round);
 			let count = if i == 0 { reported_node_count } else { 0 };
-			Summarys::<T>::insert(
+			V1Summarys::<T>::insert(
 				round,
-				SummaryInfo {
+				v5::OldSummaryInfo {
 					count,
 					power: summary.power,
 					used: summary.used,
@@ -268,9 +298,9 @@ pub mod v1 {
 
 		for (cid, store_file) in StoreFiles::<T>::drain() {
 			if let Some(file_order) = FileOrders::<T>::take(&cid) {
-				Files::<T>::insert(
+				V1Files::<T>::insert(
 					cid.clone(),
-					FileInfo {
+					v5::OldFileInfo {
 						reserved: store_file.reserved,
 						base_fee: store_file.base_fee,
 						file_size: file_order.file_size,
@@ -282,9 +312,9 @@ pub mod v1 {
 				);
 				file_order_count += 1;
 			} else {
-				Files::<T>::insert(
+				V1Files::<T>::insert(
 					cid.clone(),
-					FileInfo {
+					v5::OldFileInfo {
 						reserved: store_file.reserved,
 						base_fee: store_file.base_fee,
 						file_size: store_file.file_size,
@@ -321,3 +351,285 @@ pub mod v1 {
 		Ok(())
 	}
 }
+
+pub mod v2 {
+	use super::*;
+
+	use frame_support::pallet_prelude::*;
+
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+		assert!(PalletStorageVersion::<T>::get() == Releases::V1);
+		assert!(StashPools::<T>::iter().next().is_none());
+		let mut controllers: BTreeMap<T::AccountId, u32> = BTreeMap::new();
+		for (_, node_info) in v1::V1Nodes::<T>::iter() {
+			*controllers.entry(node_info.stash).or_default() += 1;
+		}
+		let max_controllers = T::MaxStashControllers::get();
+		let exceeded_count = controllers.values().filter(|v| **v > max_controllers).count();
+		log::debug!(
+			target: "runtime::file-storage",
+			"migration: file storage storage version v2 PRE migration checks succesful, {} stashes exceed MaxStashControllers",
+			exceeded_count,
+		);
+		Ok(())
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut node_count = 0u32;
+		let mut skipped_count = 0u32;
+		let max_controllers = T::MaxStashControllers::get() as usize;
+		let mut pools: BTreeMap<T::AccountId, StashPoolOf<T>> = BTreeMap::new();
+		// Nodes are still in layout of v1 until v5
+		for (controller, node_info) in v1::V1Nodes::<T>::iter() {
+			let pool = pools.entry(node_info.stash).or_default();
+			// Nodes beyond the cap keep their own deposit but are not listed in pool
+			if pool.controllers.len() < max_controllers {
+				pool.controllers.push(controller);
+			} else {
+				skipped_count += 1;
+			}
+			node_count += 1;
+		}
+		let pool_count = pools.len() as u32;
+		for (stash, pool) in pools {
+			StashPools::<T>::insert(stash, pool);
+		}
+		PalletStorageVersion::<T>::put(Releases::V2);
+
+		log::info!(
+			target: "runtime::file-storage",
+			"Migrate {} nodes into {} stash pools, {} nodes exceed MaxStashControllers",
+			node_count,
+			pool_count,
+			skipped_count,
+		);
+
+		T::DbWeight::get().reads_writes((node_count + 1) as Weight, (pool_count + 1) as Weight)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+		assert!(PalletStorageVersion::<T>::get() == Releases::V2);
+		Ok(())
+	}
+}
+
+pub mod v3 {
+	use super::*;
+
+	use frame_support::pallet_prelude::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		TrustAnchors::<T>::insert(
+			IAS_ROOT_CA_SPKI.to_vec(),
+			TrustAnchorInfo {
+				subject: IAS_ROOT_CA_SUBJECT.to_vec(),
+				not_before: IAS_ROOT_CA_NOT_BEFORE,
+				not_after: IAS_ROOT_CA_NOT_AFTER,
+			},
+		);
+		PalletStorageVersion::<T>::put(Releases::V3);
+
+		log::info!(
+			target: "runtime::file-storage",
+			"Migrate builtin IAS root certificate into trust anchors",
+		);
+
+		T::DbWeight::get().writes(2)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+		assert!(PalletStorageVersion::<T>::get() == Releases::V3);
+		assert!(TrustAnchors::<T>::contains_key(IAS_ROOT_CA_SPKI.to_vec()));
+		Ok(())
+	}
+}
+
+pub mod v4 {
+	use super::*;
+
+	use frame_support::pallet_prelude::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let deadline =
+			<frame_system::Pallet<T>>::block_number().saturating_add(T::AttestationValidity::get());
+		let mut register_count = 0u32;
+		for machine_id in Registers::<T>::iter_keys() {
+			AttestationDeadlines::<T>::insert(machine_id, deadline);
+			register_count += 1;
+		}
+		PalletStorageVersion::<T>::put(Releases::V4);
+
+		log::info!(
+			target: "runtime::file-storage",
+			"Migrate {} registers with attestation deadline {:?}",
+			register_count,
+			deadline,
+		);
+
+		T::DbWeight::get()
+			.reads_writes((register_count + 1) as Weight, (register_count + 1) as Weight)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+		assert!(PalletStorageVersion::<T>::get() == Releases::V4);
+		assert!(Registers::<T>::iter_keys().all(|k| AttestationDeadlines::<T>::contains_key(k)));
+		Ok(())
+	}
+}
+
+pub mod v5 {
+	use super::*;
+
+	use frame_support::pallet_prelude::*;
+
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct OldNodeInfo<AccountId, Balance, BlockNumber> {
+		pub stash: AccountId,
+		pub deposit: Balance,
+		pub machine_id: Option<MachineId>,
+		pub rid: u64,
+		pub used: u64,
+		pub power: u64,
+		pub slash_used: u64,
+		pub reward: Balance,
+		pub reported_at: BlockNumber,
+		pub prev_reported_at: BlockNumber,
+	}
+
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct OldSummaryInfo<Balance> {
+		pub count: u32,
+		#[codec(compact)]
+		pub power: u128,
+		#[codec(compact)]
+		pub used: u128,
+		pub mine_reward: Balance,
+		pub store_reward: Balance,
+		pub paid_mine_reward: Balance,
+		pub paid_store_reward: Balance,
+	}
+
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct OldFileInfo<AccountId, Balance, BlockNumber> {
+		pub reserved: Balance,
+		pub base_fee: Balance,
+		pub file_size: u64,
+		pub add_at: BlockNumber,
+		pub fee: Balance,
+		pub liquidate_at: BlockNumber,
+		pub replicas: Vec<AccountId>,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut node_count = 0u32;
+		Nodes::<T>::translate::<OldNodeInfo<T::AccountId, BalanceOf<T>, BlockNumberFor<T>>, _>(
+			|_, old| {
+				node_count += 1;
+				Some(NodeInfo {
+					stash: old.stash,
+					deposit: old.deposit,
+					machine_id: old.machine_id,
+					rid: old.rid,
+					used: old.used,
+					cold_used: 0,
+					power: old.power,
+					slash_used: old.slash_used,
+					reward: old.reward,
+					reported_at: old.reported_at,
+					prev_reported_at: old.prev_reported_at,
+				})
+			},
+		);
+
+		let mut summary_count = 0u32;
+		Summarys::<T>::translate::<OldSummaryInfo<BalanceOf<T>>, _>(|_, old| {
+			summary_count += 1;
+			Some(SummaryInfo {
+				count: old.count,
+				power: old.power,
+				used: old.used,
+				cold_used: 0,
+				mine_reward: old.mine_reward,
+				store_reward: old.store_reward,
+				cold_store_reward: Zero::zero(),
+				paid_mine_reward: old.paid_mine_reward,
+				paid_store_reward: old.paid_store_reward,
+			})
+		});
+
+		let mut file_count = 0u32;
+		Files::<T>::translate::<OldFileInfo<T::AccountId, BalanceOf<T>, BlockNumberFor<T>>, _>(
+			|_, old| {
+				file_count += 1;
+				Some(FileInfo {
+					reserved: old.reserved,
+					base_fee: old.base_fee,
+					file_size: old.file_size,
+					add_at: old.add_at,
+					fee: old.fee,
+					liquidate_at: old.liquidate_at,
+					replicas: old.replicas,
+					class: StorageClass::Hot,
+				})
+			},
+		);
+		PalletStorageVersion::<T>::put(Releases::V5);
+
+		log::info!(
+			target: "runtime::file-storage",
+			"Migrate {} nodes, {} summarys, {} files into hot storage class",
+			node_count,
+			summary_count,
+			file_count,
+		);
+
+		let count = (node_count + summary_count + file_count + 1) as Weight;
+		T::DbWeight::get().reads_writes(count, count)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+		assert!(PalletStorageVersion::<T>::get() == Releases::V5);
+		Ok(())
+	}
+}
+
+pub mod v6 {
+	use super::*;
+
+	use frame_support::pallet_prelude::*;
+
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub struct OldRegisterInfo {
+		pub key: PubKey,
+		pub enclave: EnclaveId,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut register_count = 0u32;
+		Registers::<T>::translate::<OldRegisterInfo, _>(|_, old| {
+			register_count += 1;
+			Some(RegisterInfo { key: old.key, enclave: old.enclave, ..Default::default() })
+		});
+		PalletStorageVersion::<T>::put(Releases::V6);
+
+		log::info!(
+			target: "runtime::file-storage",
+			"Migrate {} registers with enclave signer info",
+			register_count,
+		);
+
+		T::DbWeight::get()
+			.reads_writes((register_count + 1) as Weight, (register_count + 1) as Weight)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn post_migrate<T: Config>() -> Result<(), &'static str> {
+		assert!(PalletStorageVersion::<T>::get() == Releases::V6);
+		Ok(())
+	}
+}
@@ -24,6 +24,7 @@ pub type BlockNumber = u64;
 pub type Balance = u128;
 
 pub const MAX_FILE_SIZE: u64 = 64 * 1024 * 1024; // 64M
+pub const MAX_FILE_CHUNKS: u32 = 16;
 pub const MAX_POWER: u64 = 1024 * 1024 * 1024; // 1G
 pub const FILE_BASE_PRICE: Balance = 1000;
 
@@ -135,15 +136,36 @@ parameter_types! {
 	pub const SlashBalance: Balance = 100;
 	pub const SessionDuration: BlockNumber = 10;
 	pub const LiquidateDuration: u32 = 30;
+	pub const ColdLiquidateDuration: u32 = 60;
+	pub const ColdFileSizePrice: Balance = 40;
 	pub const MaxFileReplicas: u32 = 5;
 	pub const EffectiveFileReplicas: u32 = 4;
+	pub static FileSizeQuorum: u32 = 1;
 	pub const MaxFileSize: u64 = MAX_FILE_SIZE;
+	pub const MaxFileChunks: u32 = MAX_FILE_CHUNKS;
+	pub const MaxFileOwners: u32 = 2;
 	pub const MaxPower: u64 = MAX_POWER;
 	pub const MaxReportFiles: u32 = 10;
+	pub const ReportPriority: TransactionPriority = TransactionPriority::max_value() / 2;
 	pub const FileBaseFee: Balance = FILE_BASE_PRICE;
 	pub const StoreRewardRatio: Perbill = Perbill::from_percent(50);
 	pub static MineFactor: Perbill = Perbill::from_percent(0);
 	pub const MaxMineReward: Balance = 4 * 1048576;
+	pub const MinDelegation: Balance = 100;
+	pub const UnbondingDuration: BlockNumber = 20;
+	pub const MaxUnbondingChunks: u32 = 2;
+	pub const MaxRewardChunks: u32 = 2;
+	pub static RewardLockDuration: BlockNumber = 0;
+	pub const MaxStashControllers: u32 = 3;
+	pub const ChallengeBond: Balance = 500;
+	pub const ChallengeDuration: BlockNumber = 5;
+	pub const ChallengerRewardRatio: Perbill = Perbill::from_percent(50);
+	pub const MaxReportAge: u64 = 30 * 86400;
+	pub const AttestationValidity: BlockNumber = 200;
+	pub const AttestationWarningSessions: u32 = 2;
+	pub static MaxReplicasPerRegion: u32 = 5;
+	pub static MaxReplicasPerOperator: u32 = 5;
+	pub const MaxNodeTagLength: u32 = 16;
 }
 
 ord_parameter_types! {
@@ -161,17 +183,38 @@ impl Config for Test {
 	type SlashBalance = SlashBalance;
 	type SessionDuration = SessionDuration;
 	type LiquidateDuration = LiquidateDuration;
+	type ColdLiquidateDuration = ColdLiquidateDuration;
 	type MaxFileReplicas = MaxFileReplicas;
 	type EffectiveFileReplicas = EffectiveFileReplicas;
+	type FileSizeQuorum = FileSizeQuorum;
 	type MaxFileSize = MaxFileSize;
+	type MaxFileChunks = MaxFileChunks;
+	type MaxFileOwners = MaxFileOwners;
 	type MaxPower = MaxPower;
+	type ReportPriority = ReportPriority;
 	type MaxReportFiles = MaxReportFiles;
 	type FileBaseFee = FileBaseFee;
 	type FileSizePrice = FileSizePrice;
+	type ColdFileSizePrice = ColdFileSizePrice;
 	type StoreRewardRatio = StoreRewardRatio;
 	type StashBalance = StashBalance;
 	type MineFactor = MineFactor;
 	type MaxMineReward = MaxMineReward;
+	type MinDelegation = MinDelegation;
+	type UnbondingDuration = UnbondingDuration;
+	type MaxUnbondingChunks = MaxUnbondingChunks;
+	type RewardLockDuration = RewardLockDuration;
+	type MaxRewardChunks = MaxRewardChunks;
+	type MaxStashControllers = MaxStashControllers;
+	type ChallengeBond = ChallengeBond;
+	type ChallengeDuration = ChallengeDuration;
+	type ChallengerRewardRatio = ChallengerRewardRatio;
+	type MaxReportAge = MaxReportAge;
+	type AttestationValidity = AttestationValidity;
+	type AttestationWarningSessions = AttestationWarningSessions;
+	type MaxReplicasPerRegion = MaxReplicasPerRegion;
+	type MaxReplicasPerOperator = MaxReplicasPerOperator;
+	type MaxNodeTagLength = MaxNodeTagLength;
 	type WeightInfo = ();
 }
 
@@ -183,6 +226,10 @@ pub struct ExtBuilder {
 	reports: Vec<(AccountId, RegisterData, ReportData)>,
 	now: u64,
 	mine_factor: Perbill,
+	reward_lock_duration: BlockNumber,
+	max_replicas_per_region: u32,
+	max_replicas_per_operator: u32,
+	file_size_quorum: u32,
 }
 
 impl Default for ExtBuilder {
@@ -194,6 +241,10 @@ impl Default for ExtBuilder {
 			files: vec![],
 			reports: vec![],
 			mine_factor: Perbill::from_percent(0),
+			reward_lock_duration: 0,
+			max_replicas_per_region: 5,
+			max_replicas_per_operator: 5,
+			file_size_quorum: 1,
 			now: 1627833600000,
 		}
 	}
@@ -230,17 +281,43 @@ impl ExtBuilder {
 		self
 	}
 
+	pub fn max_replicas_per(mut self, region: u32, operator: u32) -> Self {
+		self.max_replicas_per_region = region;
+		self.max_replicas_per_operator = operator;
+		self
+	}
+
+	pub fn file_size_quorum(mut self, quorum: u32) -> Self {
+		self.file_size_quorum = quorum;
+		self
+	}
+
 	pub fn mine_factor(mut self, factor: Perbill) -> Self {
 		self.mine_factor = factor;
 		self
 	}
 
+	pub fn reward_lock_duration(mut self, duration: BlockNumber) -> Self {
+		self.reward_lock_duration = duration;
+		self
+	}
+
 	pub fn build(self) -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
-		pallet_storage::GenesisConfig::<Test> { enclaves: self.enclaves.clone() }
-			.assimilate_storage(&mut t)
-			.unwrap();
+		pallet_storage::GenesisConfig::<Test> {
+			enclaves: self.enclaves.clone(),
+			quote_statuses: vec![str2bytes("GROUP_OUT_OF_DATE")],
+			advisory_ids: MOCK_ADVISORY_IDS.iter().map(|v| str2bytes(v)).collect(),
+			trust_anchors: vec![(
+				IAS_ROOT_CA_SUBJECT.to_vec(),
+				IAS_ROOT_CA_SPKI.to_vec(),
+				IAS_ROOT_CA_NOT_BEFORE,
+				IAS_ROOT_CA_NOT_AFTER,
+			)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
 
 		pallet_balances::GenesisConfig::<Test> {
 			balances: vec![
@@ -255,6 +332,10 @@ impl ExtBuilder {
 		.unwrap();
 
 		MINE_FACTOR.with(|v| *v.borrow_mut() = self.mine_factor);
+		REWARD_LOCK_DURATION.with(|v| *v.borrow_mut() = self.reward_lock_duration);
+		MAX_REPLICAS_PER_REGION.with(|v| *v.borrow_mut() = self.max_replicas_per_region);
+		MAX_REPLICAS_PER_OPERATOR.with(|v| *v.borrow_mut() = self.max_replicas_per_operator);
+		FILE_SIZE_QUORUM.with(|v| *v.borrow_mut() = self.file_size_quorum);
 
 		let mut ext = sp_io::TestExternalities::new(t);
 		let ExtBuilder { registers, stashs, files, now, reports, .. } = self;
@@ -267,7 +348,21 @@ impl ExtBuilder {
 			let mut file_sizes = BTreeMap::new();
 			for (cid, file_size, fee) in files {
 				file_sizes.insert(cid.clone(), file_size);
-				FileStorage::store(Origin::signed(9999), cid.clone(), file_size, fee).unwrap();
+				if file_size > MAX_FILE_SIZE {
+					FileStorage::store_with_options(
+						Origin::signed(9999),
+						cid.clone(),
+						file_size,
+						fee,
+						StoreOptions {
+							chunks: Some(mock_file_chunks(file_size)),
+							..Default::default()
+						},
+					)
+					.unwrap();
+				} else {
+					FileStorage::store(Origin::signed(9999), cid.clone(), file_size, fee).unwrap();
+				}
 			}
 			for (controller, register_data) in registers {
 				register_data.call(controller).unwrap();
@@ -321,6 +416,28 @@ pub fn mock_file_id(suffix: char) -> FileId {
 	str2bytes(&format!("QmQywLBjvLgDabmv8QZmJxxj6AqmZdGNZxCvnKh644JfF{}", suffix))
 }
 
+pub fn mock_raw_file_id(suffix: char) -> FileId {
+	str2bytes(&format!("bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyk{}", suffix))
+}
+
+/// Number of chunks of MaxFileSize to split file into
+pub fn mock_file_chunks(file_size: u64) -> u32 {
+	((file_size + MAX_FILE_SIZE - 1) / MAX_FILE_SIZE) as u32
+}
+
+pub const MOCK_ADVISORY_IDS: [&'static str; 10] = [
+	"INTEL-SA-00161",
+	"INTEL-SA-00477",
+	"INTEL-SA-00381",
+	"INTEL-SA-00389",
+	"INTEL-SA-00320",
+	"INTEL-SA-00329",
+	"INTEL-SA-00220",
+	"INTEL-SA-00270",
+	"INTEL-SA-00293",
+	"INTEL-SA-00233",
+];
+
 #[derive(Debug, Clone, Default)]
 pub struct MachineInfo<'a> {
 	pub machine_id: &'a str,
@@ -387,11 +504,19 @@ impl<'a> MachineInfo<'a> {
 		}
 	}
 	pub fn register_info(&self) -> RegisterInfo {
+		let quote_body = self.quote_body();
 		RegisterInfo {
 			enclave: hex::decode(self.enclave).unwrap(),
 			key: hex::decode(self.pub_key).unwrap(),
+			mr_signer: quote_body[176..208].to_vec(),
+			isv_prod_id: u16::from_le_bytes([quote_body[304], quote_body[305]]),
+			isv_svn: u16::from_le_bytes([quote_body[306], quote_body[307]]),
 		}
 	}
+	pub fn quote_body(&self) -> Vec<u8> {
+		let json_body: serde_json::Value = serde_json::from_str(self.ias_body).unwrap();
+		base64::decode(json_body["isvEnclaveQuoteBody"].as_str().unwrap()).unwrap()
+	}
 	pub fn get_enclave(&self) -> Vec<u8> {
 		hex::decode(self.enclave).unwrap()
 	}
@@ -401,6 +526,23 @@ impl<'a> MachineInfo<'a> {
 	pub fn register(&self, node: AccountId) -> DispatchResult {
 		self.register_data().call(node)
 	}
+	pub fn challenge_sig(&self, cid: &FileId, challenged_at: u64) -> Vec<u8> {
+		sign::p256_sign_challenge(
+			&self.get_machine_id(),
+			&hex::decode(self.priv_key).unwrap(),
+			&hex::decode(self.pub_key).unwrap(),
+			cid,
+			challenged_at,
+		)
+	}
+	pub fn heartbeat_sig(&self, session: SessionIndex) -> Vec<u8> {
+		sign::p256_sign_heartbeat(
+			&self.get_machine_id(),
+			&hex::decode(self.priv_key).unwrap(),
+			&hex::decode(self.pub_key).unwrap(),
+			session,
+		)
+	}
 }
 
 #[derive(Debug, Clone)]
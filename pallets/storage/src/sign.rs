@@ -62,3 +62,38 @@ fn encode_del_files(list: &[Vec<u8>]) -> Vec<u8> {
 	}
 	output
 }
+
+pub fn p256_sign_challenge(
+	machine_id: &[u8],
+	priv_k: &[u8],
+	pub_k: &[u8],
+	cid: &[u8],
+	challenged_at: u64,
+) -> Vec<u8> {
+	let mut priv_k = priv_k.to_vec();
+	priv_k.reverse();
+	let sk = SigningKey::from_bytes(&priv_k).unwrap();
+	let data = [&machine_id[..], &pub_k[..], &cid[..], &encode_u64(challenged_at)[..]].concat();
+	let sigr = sk.sign(&data);
+	let mut sig = sigr.as_bytes().to_vec();
+	sig[0..32].reverse();
+	sig[32..].reverse();
+	sig
+}
+
+pub fn p256_sign_heartbeat(
+	machine_id: &[u8],
+	priv_k: &[u8],
+	pub_k: &[u8],
+	session: u32,
+) -> Vec<u8> {
+	let mut priv_k = priv_k.to_vec();
+	priv_k.reverse();
+	let sk = SigningKey::from_bytes(&priv_k).unwrap();
+	let data = [&machine_id[..], &pub_k[..], &encode_u64(session as u64)[..]].concat();
+	let sigr = sk.sign(&data);
+	let mut sig = sigr.as_bytes().to_vec();
+	sig[0..32].reverse();
+	sig[32..].reverse();
+	sig
+}
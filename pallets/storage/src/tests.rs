@@ -2,7 +2,15 @@ use super::*;
 
 use crate::mock::*;
 
-use frame_support::{assert_err, assert_ok, traits::Currency};
+use frame_support::{
+	assert_err, assert_ok,
+	traits::{Currency, Hooks, ReservableCurrency},
+	weights::{GetDispatchInfo, Pays},
+};
+use sp_runtime::{
+	traits::SignedExtension,
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
 
 use pallet::Event as PalletEvent;
 
@@ -279,7 +287,8 @@ fn file_order_removed_if_file_size_is_too_small() {
 				direct_store_reward: 10,
 				slash: 0,
 			});
-			assert_node!(2, deposit: default_stash_balance() + 10);
+			assert_node!(2, deposit: default_stash_balance());
+			assert_eq!(PendingRewards::<Test>::get(2).free, 10);
 			assert_summary!(0,
 				store_reward: 90,
 			);
@@ -384,7 +393,8 @@ fn report_liquidate_files() {
 				.liquidate_files(&['A'])
 				.report_data(0)
 				.call(2));
-			assert_node!(2, power: 9 * MB, used: 0, deposit: default_stash_balance() + 20);
+			assert_node!(2, power: 9 * MB, used: 0, deposit: default_stash_balance());
+			assert_eq!(PendingRewards::<Test>::get(2).free, 20);
 			assert_last_pallet_event!(PalletEvent::NodeReported {
 				controller: 2,
 				machine_id: get_machine_id(0),
@@ -445,7 +455,8 @@ fn report_liquidate_files_do_not_reward_unhealth_node() {
 				.report_data(3)
 				.call(3));
 			assert_file!(mock_file_id('A'), replicas: vec![3]);
-			assert_node!(3, deposit: default_stash_balance() + 20);
+			assert_node!(3, deposit: default_stash_balance());
+			assert_eq!(PendingRewards::<Test>::get(3).free, 20);
 			assert_node!(2, deposit: default_stash_balance(), rid: 4, 
             reported_at: 11, power: 10 * MB, used: 0, slash_used: MB);
 			assert_ok!(MockData::new(4, 5, 10 * MB, &[]).report_data(0).call(2));
@@ -498,10 +509,12 @@ fn report_do_store_reward() {
 				direct_store_reward: 20,
 				slash: 0,
 			});
-			assert_node!(2, deposit: default_stash_balance() + 20);
+			assert_node!(2, deposit: default_stash_balance());
+			assert_eq!(PendingRewards::<Test>::get(2).free, 20);
 			assert_node!(3, deposit: default_stash_balance(), reward: 10);
 			assert_ok!(MockData::new(3, 4, 10 * MB, &[]).report_data(3).call(3));
-			assert_node!(3, deposit: default_stash_balance() + 10, reward: 0);
+			assert_node!(3, deposit: default_stash_balance(), reward: 0);
+			assert_eq!(PendingRewards::<Test>::get(3).free, 10);
 			assert_session! { current: 1 };
 			assert_summary!(1,
 				mine_reward: 0,
@@ -657,13 +670,39 @@ fn report_failed_when_rid_is_not_continuous() {
 		})
 }
 
+#[test]
+fn report_requires_fresh_attestation() {
+	ExtBuilder::default().stash(1, 2).build().execute_with(|| {
+		assert_ok!(MACHINES[0].register(2));
+		assert_eq!(AttestationDeadlines::<Test>::get(&get_machine_id(0)), Some(201));
+
+		// Warned when attestation lapses in AttestationWarningSessions
+		run_to_block(185);
+		assert_ok!(MockData::new(0, 3, 10 * MB, &[]).report_data(0).call(2));
+		assert_last_pallet_event!(PalletEvent::AttestationExpiring {
+			controller: 2,
+			machine_id: get_machine_id(0),
+			deadline: 201,
+		});
+
+		// Failed when attestation lapsed
+		run_to_block(201);
+		let report_data = MockData::new(3, 4, 10 * MB, &[]).report_data(0);
+		assert_err!(report_data.call(2), Error::<Test>::AttestationExpired);
+
+		assert_ok!(MACHINES[0].register(2));
+		assert_eq!(AttestationDeadlines::<Test>::get(&get_machine_id(0)), Some(401));
+		assert_ok!(report_data.call(2));
+	})
+}
+
 #[test]
 fn store_works() {
 	ExtBuilder::default().build().execute_with(|| {
 		let pot = balance_of_storage_pot();
 		let u1000 = Balances::free_balance(&1000);
 		let file_fee = 1100;
-		assert_eq!(FileStorage::store_file_fee(2000), file_fee);
+		assert_eq!(FileStorage::store_file_fee(2000, StorageClass::Hot), file_fee);
 		assert_ok!(FileStorage::store(Origin::signed(1000), mock_file_id('A'), MB, file_fee));
 		let now_at = FileStorage::now_at();
 		assert_file!(mock_file_id('A'),
@@ -676,7 +715,7 @@ fn store_works() {
 		assert_eq!(balance_of_storage_pot(), pot.saturating_add(file_fee));
 
 		// Add more fee
-		assert_ok!(FileStorage::store(Origin::signed(1000), mock_file_id('A'), MB, 10,));
+		assert_ok!(FileStorage::store(Origin::signed(1000), mock_file_id('A'), MB, 10));
 		assert_file!(mock_file_id('A'),
 			reserved: file_fee.saturating_sub(FILE_BASE_PRICE).saturating_add(10),
 			base_fee: FILE_BASE_PRICE,
@@ -690,24 +729,112 @@ fn store_works() {
 				Origin::signed(100),
 				mock_file_id('B'),
 				MB,
-				file_fee.saturating_sub(1),
+				file_fee.saturating_sub(1)
 			),
 			Error::<Test>::NotEnoughFee
 		);
 
 		// Failed when fize size not in [0, T::MaxFileSize]
+		assert_err!(
+			FileStorage::store(
+				Origin::signed(1000),
+				mock_raw_file_id('X'),
+				MAX_FILE_SIZE + 1,
+				u128::max_value()
+			),
+			Error::<Test>::InvalidFileSize
+		);
+
+		// Failed when dag-pb file exceeds T::MaxFileSize without declaring chunks
 		assert_err!(
 			FileStorage::store(
 				Origin::signed(1000),
 				mock_file_id('X'),
 				MAX_FILE_SIZE + 1,
-				u128::max_value(),
+				u128::max_value()
 			),
 			Error::<Test>::InvalidFileSize
 		);
 	})
 }
 
+#[test]
+fn store_chunked_file_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let chunked_size = 2 * MAX_FILE_SIZE;
+		let file_fee = FileStorage::store_file_fee(chunked_size, StorageClass::Hot);
+		assert_eq!(file_fee, 13800);
+		let store_chunked = |cid: FileId, file_size: u64, chunks: u32| {
+			FileStorage::store_with_options(
+				Origin::signed(1000),
+				cid,
+				file_size,
+				file_fee,
+				StoreOptions { chunks: Some(chunks), ..Default::default() },
+			)
+		};
+		assert_ok!(store_chunked(mock_file_id('A'), chunked_size, 2));
+		assert_file!(mock_file_id('A'),
+			reserved: file_fee.saturating_sub(FILE_BASE_PRICE),
+			file_size: chunked_size,
+		);
+		assert_eq!(FileChunks::<Test>::get(mock_file_id('A')), Some(2));
+
+		// File without declared chunks is limited as a single object
+		assert_err!(
+			FileStorage::store(Origin::signed(1000), mock_file_id('B'), chunked_size, file_fee),
+			Error::<Test>::InvalidFileSize
+		);
+
+		// Failed when raw file declares chunks
+		assert_err!(
+			store_chunked(mock_raw_file_id('C'), chunked_size, 2),
+			Error::<Test>::InvalidFileChunks
+		);
+
+		// Failed when chunks can't hold file size
+		assert_err!(
+			store_chunked(mock_file_id('C'), chunked_size + 1, 2),
+			Error::<Test>::InvalidFileSize
+		);
+
+		// Failed when no or too many chunks
+		assert_err!(
+			store_chunked(mock_file_id('C'), chunked_size, 0),
+			Error::<Test>::InvalidFileChunks
+		);
+		assert_err!(
+			store_chunked(mock_file_id('C'), chunked_size, MAX_FILE_CHUNKS + 1),
+			Error::<Test>::InvalidFileChunks
+		);
+	})
+}
+
+#[test]
+fn report_chunked_file() {
+	let chunked_size = 2 * MAX_FILE_SIZE;
+	ExtBuilder::default()
+		.stash(1, 2)
+		.register(2, MACHINES[0].register_data())
+		.files(vec![(mock_file_id('A'), chunked_size, 13800)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(MockData::new(0, 3, 10 * MB, &[('A', chunked_size)]).report_data(0).call(2));
+			assert_file!(mock_file_id('A'),
+				base_fee: 0,
+				file_size: chunked_size,
+				reserved: 0,
+				fee: 12800,
+				replicas: vec![2],
+			);
+			assert_node!(2, used: chunked_size);
+			assert_eq!(
+				FileStorage::node_deposit(&2).used_deposit,
+				FileStorage::deposit_for_used(chunked_size)
+			);
+		})
+}
+
 #[test]
 fn force_delete() {
 	ExtBuilder::default()
@@ -847,7 +974,8 @@ fn node_deposit_works() {
 			NodeDepositInfo {
 				current_deposit: 0,
 				slash_deposit: default_stash_balance(),
-				used_deposit: 0
+				used_deposit: 0,
+				backing_deposit: 0
 			}
 		);
 		assert_ok!(FileStorage::stash(Origin::signed(1), 2));
@@ -856,7 +984,8 @@ fn node_deposit_works() {
 			NodeDepositInfo {
 				current_deposit: default_stash_balance(),
 				slash_deposit: default_stash_balance(),
-				used_deposit: 0
+				used_deposit: 0,
+				backing_deposit: 0
 			}
 		);
 		assert_ok!(MACHINES[0].register(2));
@@ -870,7 +999,8 @@ fn node_deposit_works() {
 			NodeDepositInfo {
 				current_deposit: default_stash_balance(),
 				slash_deposit: default_stash_balance(),
-				used_deposit: 10
+				used_deposit: 10,
+				backing_deposit: 0
 			}
 		);
 		assert_ok!(FileStorage::stash(Origin::signed(1), 2));
@@ -879,8 +1009,1313 @@ fn node_deposit_works() {
 			NodeDepositInfo {
 				current_deposit: default_stash_balance() + 10,
 				slash_deposit: default_stash_balance(),
-				used_deposit: 10
+				used_deposit: 10,
+				backing_deposit: 0
+			}
+		);
+	})
+}
+
+#[test]
+fn delegate_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_err!(
+			FileStorage::delegate(Origin::signed(11), 2, 1_000),
+			Error::<Test>::NodeNotStashed
+		);
+		assert_ok!(FileStorage::stash(Origin::signed(1), 2));
+		assert_err!(
+			FileStorage::delegate(Origin::signed(11), 2, 50),
+			Error::<Test>::DelegationTooSmall
+		);
+
+		let pot = balance_of_storage_pot();
+		let u11 = Balances::free_balance(11);
+		assert_ok!(FileStorage::delegate(Origin::signed(11), 2, 1_000));
+		assert_last_pallet_event!(PalletEvent::Delegated {
+			delegator: 11,
+			controller: 2,
+			amount: 1_000
+		});
+		assert_eq!(Balances::free_balance(11), u11 - 1_000);
+		assert_eq!(balance_of_storage_pot(), pot + 1_000);
+		let backing = Backings::<Test>::get(2).unwrap();
+		assert_eq!((backing.total, backing.shares), (1_000, 1_000));
+		assert_eq!(Delegations::<Test>::get(2, 11).unwrap().shares, 1_000);
+
+		// top up below T::MinDelegation is fine once delegated
+		assert_ok!(FileStorage::delegate(Origin::signed(11), 2, 50));
+		assert_eq!(Backings::<Test>::get(2).unwrap().total, 1_050);
+		assert_eq!(FileStorage::node_deposit(&2).backing_deposit, 1_050);
+	})
+}
+
+#[test]
+fn undelegate_and_withdraw_unbonded() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(FileStorage::stash(Origin::signed(1), 2));
+		assert_err!(
+			FileStorage::undelegate(Origin::signed(11), 2, 100),
+			Error::<Test>::NoDelegation
+		);
+		assert_ok!(FileStorage::delegate(Origin::signed(11), 2, 1_000));
+
+		assert_ok!(FileStorage::undelegate(Origin::signed(11), 2, 300));
+		assert_last_pallet_event!(PalletEvent::Undelegated {
+			delegator: 11,
+			controller: 2,
+			amount: 300,
+			unlock_at: 21,
+		});
+		assert_eq!(Backings::<Test>::get(2).unwrap().total, 700);
+		assert_ok!(FileStorage::undelegate(Origin::signed(11), 2, 100));
+		assert_err!(
+			FileStorage::undelegate(Origin::signed(11), 2, 100),
+			Error::<Test>::TooManyUnbondings
+		);
+		assert_err!(FileStorage::withdraw_unbonded(Origin::signed(11)), Error::<Test>::NoUnbonded);
+
+		run_to_block(21);
+		let u11 = Balances::free_balance(11);
+		assert_ok!(FileStorage::withdraw_unbonded(Origin::signed(11)));
+		assert_last_pallet_event!(PalletEvent::WithdrawnUnbonded { delegator: 11, amount: 400 });
+		assert_eq!(Balances::free_balance(11), u11 + 400);
+		assert!(!Unbondings::<Test>::contains_key(11));
+
+		// undelegate all if the rest is below T::MinDelegation
+		assert_ok!(FileStorage::undelegate(Origin::signed(11), 2, 550));
+		assert_eq!(Unbondings::<Test>::get(11)[0].amount, 600);
+		assert_eq!(Backings::<Test>::get(2).unwrap().total, 0);
+		assert!(!Delegations::<Test>::contains_key(2, 11));
+	})
+}
+
+#[test]
+fn withdraw_with_backing_deposit() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(FileStorage::stash(Origin::signed(1), 2));
+		assert_ok!(FileStorage::delegate(Origin::signed(11), 2, 5_000));
+		let u1 = Balances::free_balance(1);
+		assert_ok!(FileStorage::withdraw(Origin::signed(2)));
+		assert_node!(2, deposit: default_stash_balance() - 5_000);
+		assert_eq!(Balances::free_balance(1), u1 + 5_000);
+
+		// node's deposit must be covered by stash's pool when backing decreased
+		assert_err!(
+			FileStorage::undelegate(Origin::signed(11), 2, 5_000),
+			Error::<Test>::InsufficientDeposit
+		);
+		assert_ok!(FileStorage::deposit_pool(Origin::signed(1), 5_000));
+		assert_ok!(FileStorage::undelegate(Origin::signed(11), 2, 5_000));
+		assert_node!(2, deposit: default_stash_balance());
+		assert_eq!(StashPools::<Test>::get(1).unwrap().deposit, 0);
+	})
+}
+
+#[test]
+fn undelegate_rounds_up_shares() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(FileStorage::stash(Origin::signed(1), 2));
+		assert_ok!(FileStorage::delegate(Origin::signed(11), 2, 1_000));
+		Backings::<Test>::mutate(2, |maybe_backing| {
+			if let Some(backing) = maybe_backing {
+				backing.total = 1_500;
+			}
+		});
+		assert_ok!(FileStorage::undelegate(Origin::signed(11), 2, 100));
+		let backing = Backings::<Test>::get(2).unwrap();
+		assert_eq!((backing.total, backing.shares), (1_400, 933));
+		assert_eq!(Delegations::<Test>::get(2, 11).unwrap().shares, 933);
+	})
+}
+
+#[test]
+fn report_share_reward_with_delegators() {
+	let report_data = MockData::new(0, 1, 10 * MB, &[]).report_data(0);
+	ExtBuilder::default()
+		.reports(vec![(2, MACHINES[0].register_data(), report_data.clone())])
+		.build()
+		.execute_with(|| {
+			run_to_block(11);
+			assert_ok!(FileStorage::set_commission(Origin::signed(2), Perbill::from_percent(10)));
+			assert_ok!(FileStorage::delegate(Origin::signed(11), 2, default_stash_balance()));
+			Nodes::<Test>::mutate(2, |maybe_node_info| {
+				if let Some(node_info) = maybe_node_info {
+					node_info.reward = 100;
+				}
+			});
+			assert_ok!(MockData::new(1, 2, 10 * MB, &[]).report_data(0).call(2));
+			assert_node!(2, deposit: default_stash_balance());
+			assert_eq!(PendingRewards::<Test>::get(2).free, 55);
+
+			let u11 = Balances::free_balance(11);
+			assert_ok!(FileStorage::claim_delegation_rewards(Origin::signed(11), 2));
+			assert_last_pallet_event!(PalletEvent::DelegationRewardClaimed {
+				delegator: 11,
+				controller: 2,
+				amount: 45,
+			});
+			assert_eq!(Balances::free_balance(11), u11 + 45);
+			assert_err!(
+				FileStorage::claim_delegation_rewards(Origin::signed(11), 2),
+				Error::<Test>::NoReward
+			);
+		})
+}
+
+#[test]
+fn report_slash_delegators() {
+	let report_data = MockData::new(0, 3, 10 * MB, &[('A', MB)]).report_data(0);
+	ExtBuilder::default()
+		.files(vec![(mock_file_id('A'), MB, 1200)])
+		.reports(vec![(2, MACHINES[0].register_data(), report_data.clone())])
+		.build()
+		.execute_with(|| {
+			assert_ok!(FileStorage::delegate(Origin::signed(11), 2, default_stash_balance()));
+			run_to_block(11);
+			assert_ok!(MockData::new(3, 4, 10 * MB, &[]).report_data(0).call(2));
+			run_to_block(21);
+			let pot_reserved = StoragePotReserved::<Test>::get();
+			run_to_block(31);
+			assert_ok!(MockData::new(4, 5, 10 * MB, &[])
+				.liquidate_files(&['A'])
+				.report_data(0)
+				.call(2));
+
+			assert_node!(2, deposit: default_stash_balance() - 55);
+			assert_eq!(Backings::<Test>::get(2).unwrap().total, default_stash_balance() - 55);
+			assert_eq!(StoragePotReserved::<Test>::get(), pot_reserved.saturating_add(110));
+		})
+}
+
+#[test]
+fn claim_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(FileStorage::stash(Origin::signed(1), 2));
+		assert_err!(FileStorage::claim_rewards(Origin::signed(2)), Error::<Test>::NoReward);
+		PendingRewards::<Test>::insert(2, RewardLedger { free: 100, locked: vec![] });
+
+		let u1 = Balances::free_balance(1);
+		assert_ok!(FileStorage::claim_rewards(Origin::signed(2)));
+		assert_last_pallet_event!(PalletEvent::RewardsClaimed {
+			controller: 2,
+			payee: 1,
+			amount: 100
+		});
+		assert_eq!(Balances::free_balance(1), u1 + 100);
+		assert!(!PendingRewards::<Test>::contains_key(2));
+
+		assert_ok!(FileStorage::set_payee(Origin::signed(2), RewardDestination::Account(1000)));
+		assert_last_pallet_event!(PalletEvent::PayeeSet {
+			controller: 2,
+			payee: RewardDestination::Account(1000)
+		});
+		PendingRewards::<Test>::insert(2, RewardLedger { free: 100, locked: vec![] });
+		let u1000 = Balances::free_balance(1000);
+		assert_ok!(FileStorage::claim_rewards(Origin::signed(2)));
+		assert_eq!(Balances::free_balance(1000), u1000 + 100);
+		assert_node!(2, deposit: default_stash_balance());
+
+		// rewards left without node are claimed by the account itself
+		PendingRewards::<Test>::insert(3, RewardLedger { free: 100, locked: vec![] });
+		let u3 = Balances::free_balance(3);
+		assert_ok!(FileStorage::claim_rewards(Origin::signed(3)));
+		assert_last_pallet_event!(PalletEvent::RewardsClaimed {
+			controller: 3,
+			payee: 3,
+			amount: 100
+		});
+		assert_eq!(Balances::free_balance(3), u3 + 100);
+	})
+}
+
+#[test]
+fn locked_rewards_merge_chunks() {
+	ExtBuilder::default().reward_lock_duration(20).build().execute_with(|| {
+		FileStorage::credit_rewards(&2, 100, 100, 0);
+		FileStorage::credit_rewards(&2, 100, 100, 0);
+		assert_eq!(
+			PendingRewards::<Test>::get(2).locked,
+			vec![UnbondingChunk { amount: 200, unlock_at: 21 }]
+		);
+
+		run_to_block(2);
+		FileStorage::credit_rewards(&2, 100, 100, 0);
+		run_to_block(3);
+		FileStorage::credit_rewards(&2, 100, 100, 0);
+		// Earliest chunk merges into the next one when chunks are full
+		assert_eq!(
+			PendingRewards::<Test>::get(2).locked,
+			vec![
+				UnbondingChunk { amount: 300, unlock_at: 22 },
+				UnbondingChunk { amount: 100, unlock_at: 23 }
+			]
+		);
+
+		run_to_block(22);
+		FileStorage::credit_rewards(&2, 100, 100, 0);
+		let ledger = PendingRewards::<Test>::get(2);
+		assert_eq!(ledger.free, 300);
+		assert_eq!(
+			ledger.locked,
+			vec![
+				UnbondingChunk { amount: 100, unlock_at: 23 },
+				UnbondingChunk { amount: 100, unlock_at: 42 }
+			]
+		);
+	})
+}
+
+#[test]
+fn report_lock_mine_rewards() {
+	let report_data = MockData::new(0, 1, 10 * MB, &[]).report_data(0);
+	ExtBuilder::default()
+		.reward_lock_duration(20)
+		.reports(vec![(2, MACHINES[0].register_data(), report_data.clone())])
+		.build()
+		.execute_with(|| {
+			run_to_block(11);
+			Summarys::<Test>::mutate(0, |summary| summary.mine_reward = 100);
+			Nodes::<Test>::mutate(2, |maybe_node_info| {
+				if let Some(node_info) = maybe_node_info {
+					node_info.reward = 10;
+				}
+			});
+			assert_ok!(MockData::new(1, 2, 10 * MB, &[]).report_data(0).call(2));
+			assert_node!(2, deposit: default_stash_balance());
+			assert_eq!(
+				PendingRewards::<Test>::get(2),
+				RewardLedger {
+					free: 10,
+					locked: vec![UnbondingChunk { amount: 100, unlock_at: 31 }]
+				}
+			);
+
+			let u9999 = Balances::free_balance(9999);
+			assert_ok!(FileStorage::claim_rewards(Origin::signed(2)));
+			assert_eq!(Balances::free_balance(9999), u9999 + 10);
+			assert_err!(FileStorage::claim_rewards(Origin::signed(2)), Error::<Test>::NoReward);
+
+			run_to_block(31);
+			assert_ok!(FileStorage::claim_rewards(Origin::signed(2)));
+			assert_eq!(Balances::free_balance(9999), u9999 + 110);
+		})
+}
+
+#[test]
+fn stash_pool_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(FileStorage::stash(Origin::signed(1), 2));
+		assert_ok!(FileStorage::stash(Origin::signed(1), 3));
+		assert_eq!(StashPools::<Test>::get(1).unwrap().controllers, vec![2, 3]);
+
+		assert_ok!(FileStorage::deposit_pool(Origin::signed(1), default_stash_balance() + 5_000));
+		assert_last_pallet_event!(PalletEvent::PoolDeposited {
+			stash: 1,
+			amount: default_stash_balance() + 5_000
+		});
+		let u1 = Balances::free_balance(1);
+		assert_ok!(FileStorage::stash(Origin::signed(1), 4));
+		assert_eq!(Balances::free_balance(1), u1);
+		assert_node!(4, deposit: default_stash_balance());
+		assert_eq!(StashPools::<Test>::get(1).unwrap().deposit, 5_000);
+		assert_err!(FileStorage::stash(Origin::signed(1), 5), Error::<Test>::TooManyControllers);
+
+		assert_err!(
+			FileStorage::withdraw_pool(Origin::signed(1), 6_000),
+			Error::<Test>::NoEnoughToWithdraw
+		);
+		assert_ok!(FileStorage::withdraw_pool(Origin::signed(1), 5_000));
+		assert_last_pallet_event!(PalletEvent::PoolWithdrawn { stash: 1, amount: 5_000 });
+		assert_eq!(Balances::free_balance(1), u1 + 5_000);
+
+		assert_eq!(
+			FileStorage::stash_info(&1),
+			StashDepositInfo {
+				controllers: vec![2, 3, 4],
+				pool_deposit: 0,
+				nodes_deposit: 3 * default_stash_balance(),
+				required_deposit: 3 * default_stash_balance(),
+				pending_rewards: 0,
 			}
 		);
 	})
 }
+
+#[test]
+fn report_cover_deposit_from_pool() {
+	let report_data = MockData::new(0, 3, 10 * MB, &[('A', MB)]).report_data(0);
+	ExtBuilder::default()
+		.files(vec![(mock_file_id('A'), MB, 1200)])
+		.reports(vec![(2, MACHINES[0].register_data(), report_data.clone())])
+		.build()
+		.execute_with(|| {
+			assert_ok!(FileStorage::deposit_pool(Origin::signed(9999), 1_000));
+			run_to_block(11);
+			assert_ok!(MockData::new(3, 4, 10 * MB, &[]).report_data(0).call(2));
+			run_to_block(31);
+			assert_ok!(MockData::new(4, 5, 10 * MB, &[])
+				.liquidate_files(&['A'])
+				.report_data(0)
+				.call(2));
+			assert_node!(2, deposit: default_stash_balance());
+			assert_eq!(StashPools::<Test>::get(9999).unwrap().deposit, 890);
+		})
+}
+
+#[test]
+fn claim_stash_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(FileStorage::stash(Origin::signed(1), 2));
+		assert_ok!(FileStorage::stash(Origin::signed(1), 3));
+		assert_err!(FileStorage::claim_stash_rewards(Origin::signed(1)), Error::<Test>::NoReward);
+		PendingRewards::<Test>::insert(2, RewardLedger { free: 100, locked: vec![] });
+		PendingRewards::<Test>::insert(3, RewardLedger { free: 50, locked: vec![] });
+		assert_ok!(FileStorage::set_payee(Origin::signed(3), RewardDestination::Controller));
+		assert_eq!(FileStorage::stash_info(&1).pending_rewards, 150);
+
+		let u1 = Balances::free_balance(1);
+		assert_ok!(FileStorage::claim_stash_rewards(Origin::signed(1)));
+		assert_last_pallet_event!(PalletEvent::StashRewardsClaimed { stash: 1, amount: 100 });
+		assert_eq!(Balances::free_balance(1), u1 + 100);
+		assert!(!PendingRewards::<Test>::contains_key(2));
+		assert_eq!(PendingRewards::<Test>::get(3).free, 50);
+	})
+}
+
+#[test]
+fn challenge_replica_works() {
+	let report_data = MockData::new(0, 1, 10 * MB, &[('A', MB)]).report_data(0);
+	ExtBuilder::default()
+		.files(vec![(mock_file_id('A'), MB, 1200)])
+		.reports(vec![(2, MACHINES[0].register_data(), report_data.clone())])
+		.build()
+		.execute_with(|| {
+			let cid = mock_file_id('A');
+			assert_err!(
+				FileStorage::challenge_replica(Origin::signed(11), cid.clone(), 3),
+				Error::<Test>::ReplicaNotFound
+			);
+			let u11 = Balances::free_balance(11);
+			assert_ok!(FileStorage::challenge_replica(Origin::signed(11), cid.clone(), 2));
+			assert_last_pallet_event!(PalletEvent::ReplicaChallenged {
+				challenger: 11,
+				controller: 2,
+				cid: cid.clone(),
+				deadline: 6,
+			});
+			assert_eq!(Balances::reserved_balance(11), 500);
+			assert_err!(
+				FileStorage::challenge_replica(Origin::signed(11), cid.clone(), 2),
+				Error::<Test>::ChallengeExists
+			);
+
+			assert_err!(
+				FileStorage::answer_challenge(
+					Origin::signed(2),
+					cid.clone(),
+					MACHINES[0].challenge_sig(&cid, 2)
+				),
+				Error::<Test>::InvalidVerifyP256Sig
+			);
+			assert_ok!(FileStorage::answer_challenge(
+				Origin::signed(2),
+				cid.clone(),
+				MACHINES[0].challenge_sig(&cid, 1)
+			));
+			assert_last_pallet_event!(PalletEvent::ChallengeAnswered {
+				controller: 2,
+				cid: cid.clone(),
+				bond: 500,
+			});
+			assert_eq!(Balances::reserved_balance(11), 0);
+			assert_eq!(Balances::free_balance(11), u11 - 500);
+			assert_eq!(PendingRewards::<Test>::get(2).free, 500);
+			assert!(!Challenges::<Test>::contains_key(2, &cid));
+		})
+}
+
+#[test]
+fn challenge_replica_not_answered() {
+	let report_data = MockData::new(0, 1, 10 * MB, &[('A', MB)]).report_data(0);
+	ExtBuilder::default()
+		.files(vec![(mock_file_id('A'), MB, 1200)])
+		.reports(vec![(2, MACHINES[0].register_data(), report_data.clone())])
+		.build()
+		.execute_with(|| {
+			let cid = mock_file_id('A');
+			let u11 = Balances::free_balance(11);
+			assert_ok!(FileStorage::challenge_replica(Origin::signed(11), cid.clone(), 2));
+			run_to_block(6);
+			assert_err!(
+				FileStorage::settle_challenge(Origin::signed(11), 2, cid.clone()),
+				Error::<Test>::ChallengeNotExpired
+			);
+			run_to_block(7);
+			assert_err!(
+				FileStorage::answer_challenge(
+					Origin::signed(2),
+					cid.clone(),
+					MACHINES[0].challenge_sig(&cid, 1)
+				),
+				Error::<Test>::ChallengeExpired
+			);
+			StoragePotReserved::<Test>::put(100);
+			assert_ok!(FileStorage::settle_challenge(Origin::signed(11), 2, cid.clone()));
+			assert_last_pallet_event!(PalletEvent::ChallengeFailed {
+				controller: 2,
+				cid: cid.clone(),
+				slash: 10,
+				reward: 5,
+			});
+			assert_node!(2, deposit: default_stash_balance() - 10, used: 0, slash_used: 0);
+			assert_eq!(Balances::free_balance(11), u11 + 5);
+			assert_eq!(StoragePotReserved::<Test>::get(), 105);
+			assert_file!(&cid, replicas: vec![]);
+		})
+}
+
+#[test]
+fn settle_challenge_pays_only_collected_slash() {
+	let report_data = MockData::new(0, 1, 10 * MB, &[('A', MB)]).report_data(0);
+	ExtBuilder::default()
+		.files(vec![(mock_file_id('A'), MB, 1200)])
+		.reports(vec![(2, MACHINES[0].register_data(), report_data.clone())])
+		.build()
+		.execute_with(|| {
+			let cid = mock_file_id('A');
+			let u11 = Balances::free_balance(11);
+			assert_ok!(FileStorage::challenge_replica(Origin::signed(11), cid.clone(), 2));
+			run_to_block(7);
+			Nodes::<Test>::mutate(2, |maybe_node| maybe_node.as_mut().unwrap().deposit = 4);
+			PendingRewards::<Test>::mutate(2, |ledger| ledger.free = 2);
+			StoragePotReserved::<Test>::put(100);
+			assert_ok!(FileStorage::settle_challenge(Origin::signed(11), 2, cid.clone()));
+			assert_last_pallet_event!(PalletEvent::ChallengeFailed {
+				controller: 2,
+				cid: cid.clone(),
+				slash: 6,
+				reward: 3,
+			});
+			assert_node!(2, deposit: 0, used: 0, slash_used: 0);
+			assert_eq!(PendingRewards::<Test>::get(2).free, 0);
+			assert_eq!(Balances::free_balance(11), u11 + 3);
+			assert_eq!(StoragePotReserved::<Test>::get(), 103);
+		})
+}
+
+#[test]
+fn answer_challenge_credits_moved_bond() {
+	let report_data = MockData::new(0, 1, 10 * MB, &[('A', MB)]).report_data(0);
+	ExtBuilder::default()
+		.files(vec![(mock_file_id('A'), MB, 1200)])
+		.reports(vec![(2, MACHINES[0].register_data(), report_data.clone())])
+		.build()
+		.execute_with(|| {
+			let cid = mock_file_id('A');
+			assert_ok!(FileStorage::challenge_replica(Origin::signed(11), cid.clone(), 2));
+			Balances::unreserve(&11, 200);
+			assert_ok!(FileStorage::answer_challenge(
+				Origin::signed(2),
+				cid.clone(),
+				MACHINES[0].challenge_sig(&cid, 1)
+			));
+			assert_last_pallet_event!(PalletEvent::ChallengeAnswered {
+				controller: 2,
+				cid: cid.clone(),
+				bond: 300,
+			});
+			assert_eq!(Balances::reserved_balance(11), 0);
+			assert_eq!(PendingRewards::<Test>::get(2).free, 300);
+		})
+}
+
+#[test]
+fn register_by_attestation_policy() {
+	ExtBuilder::default().stash(1, 2).build().execute_with(|| {
+		let register_info = MACHINES[2].register_info();
+		assert_err!(MACHINES[2].register(2), Error::<Test>::InvalidEnclave);
+		assert_err!(
+			FileStorage::set_attestation_policy(
+				Origin::signed(2),
+				register_info.mr_signer.clone(),
+				0,
+				Some(AttestationPolicy { min_isv_svn: 0, expire_at: 1000 })
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(FileStorage::set_attestation_policy(
+			Origin::root(),
+			register_info.mr_signer.clone(),
+			0,
+			Some(AttestationPolicy { min_isv_svn: 1, expire_at: 1000 })
+		));
+		assert_err!(MACHINES[2].register(2), Error::<Test>::InvalidEnclave);
+
+		let policy = AttestationPolicy { min_isv_svn: 0, expire_at: 1000 };
+		assert_ok!(FileStorage::set_attestation_policy(
+			Origin::root(),
+			register_info.mr_signer.clone(),
+			0,
+			Some(policy.clone())
+		));
+		assert_last_pallet_event!(PalletEvent::SetAttestationPolicy {
+			mr_signer: register_info.mr_signer.clone(),
+			isv_prod_id: 0,
+			policy: Some(policy),
+		});
+		assert_ok!(MACHINES[2].register(2));
+		assert_eq!(Registers::<Test>::get(&MACHINES[2].get_machine_id()).unwrap(), register_info);
+		assert_eq!(FileStorage::attestation_expire_at(&register_info), Some(1000));
+
+		assert_ok!(FileStorage::set_attestation_policy(
+			Origin::root(),
+			register_info.mr_signer.clone(),
+			0,
+			None
+		));
+		assert_eq!(FileStorage::attestation_expire_at(&register_info), None);
+	})
+}
+
+#[test]
+fn register_checks_quote_policy() {
+	ExtBuilder::default().stash(1, 2).build().execute_with(|| {
+		assert_ok!(FileStorage::set_quote_policy(Origin::root(), vec![], vec![]));
+		assert_err!(MACHINES[0].register(2), Error::<Test>::QuoteStatusNotAllowed);
+
+		let statuses = vec![b"GROUP_OUT_OF_DATE".to_vec()];
+		let advisory_ids: Vec<Vec<u8>> =
+			MOCK_ADVISORY_IDS[1..].iter().map(|v| v.as_bytes().to_vec()).collect();
+		assert_ok!(FileStorage::set_quote_policy(
+			Origin::root(),
+			statuses.clone(),
+			advisory_ids.clone()
+		));
+		assert_last_pallet_event!(PalletEvent::SetQuotePolicy { statuses, advisory_ids });
+		assert_err!(MACHINES[0].register(2), Error::<Test>::AdvisoryNotAllowed);
+	});
+
+	ExtBuilder::default()
+		.stash(1, 2)
+		.now(1626852219000 + 30 * 86400 * 1000 + 1000)
+		.build()
+		.execute_with(|| {
+			assert_err!(MACHINES[0].register(2), Error::<Test>::ReportTooOld);
+		});
+}
+
+#[test]
+fn register_checks_trust_anchors() {
+	ExtBuilder::default().stash(1, 2).build().execute_with(|| {
+		let spki = IAS_ROOT_CA_SPKI.to_vec();
+		let expired = TrustAnchorInfo {
+			subject: IAS_ROOT_CA_SUBJECT.to_vec(),
+			not_before: IAS_ROOT_CA_NOT_BEFORE,
+			not_after: 1626852219,
+		};
+		assert_err!(
+			FileStorage::set_trust_anchor(Origin::signed(2), spki.clone(), Some(expired.clone())),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(FileStorage::set_trust_anchor(
+			Origin::root(),
+			spki.clone(),
+			Some(expired.clone())
+		));
+		assert_last_pallet_event!(PalletEvent::SetTrustAnchor {
+			spki: spki.clone(),
+			anchor: Some(expired),
+		});
+		assert_err!(MACHINES[0].register(2), Error::<Test>::NoValidTrustAnchor);
+
+		let other = TrustAnchorInfo {
+			subject: IAS_ROOT_CA_SUBJECT.to_vec(),
+			not_before: 0,
+			not_after: IAS_ROOT_CA_NOT_AFTER,
+		};
+		assert_ok!(FileStorage::set_trust_anchor(Origin::root(), vec![1u8; 32], Some(other)));
+		assert_err!(MACHINES[0].register(2), Error::<Test>::InvalidIASSigningCert);
+
+		assert_ok!(FileStorage::set_trust_anchor(
+			Origin::root(),
+			spki.clone(),
+			Some(TrustAnchorInfo {
+				subject: IAS_ROOT_CA_SUBJECT.to_vec(),
+				not_before: IAS_ROOT_CA_NOT_BEFORE,
+				not_after: IAS_ROOT_CA_NOT_AFTER,
+			})
+		));
+		assert_ok!(MACHINES[0].register(2));
+
+		assert_ok!(FileStorage::set_trust_anchor(Origin::root(), spki.clone(), None));
+		assert!(!TrustAnchors::<Test>::contains_key(&spki));
+	})
+}
+
+#[test]
+fn parse_ias_timestamp_works() {
+	assert_eq!(parse_ias_timestamp("2021-07-21T07:23:39.696594"), Some(1626852219));
+	assert_eq!(parse_ias_timestamp("1970-01-01T00:00:00"), Some(0));
+	assert_eq!(parse_ias_timestamp("2021-13-21T07:23:39"), None);
+	assert_eq!(parse_ias_timestamp("2021/07/21 07:23:39"), None);
+}
+
+#[test]
+fn store_cold_file_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let file_fee = FileStorage::store_file_fee(MB, StorageClass::Cold);
+		assert_eq!(file_fee, FILE_BASE_PRICE + 40);
+		assert_ok!(FileStorage::store_with_options(
+			Origin::signed(1000),
+			mock_file_id('A'),
+			MB,
+			file_fee,
+			StoreOptions { class: StorageClass::Cold, ..Default::default() }
+		));
+		assert_file!(mock_file_id('A'),
+			reserved: 40,
+			class: StorageClass::Cold,
+		);
+		assert_eq!(FileStorage::store_fee(MB, 120, StorageClass::Cold), FILE_BASE_PRICE + 80);
+
+		// Failed when file is stored in another class
+		assert_err!(
+			FileStorage::store(Origin::signed(1000), mock_file_id('A'), MB, 10),
+			Error::<Test>::StorageClassMismatch
+		);
+	})
+}
+
+#[test]
+fn report_cold_file_by_node_classes() {
+	ExtBuilder::default()
+		.stash(1, 2)
+		.register(2, MACHINES[0].register_data())
+		.build()
+		.execute_with(|| {
+			let file_fee = FileStorage::store_file_fee(MB, StorageClass::Cold);
+			assert_ok!(FileStorage::store_with_options(
+				Origin::signed(1000),
+				mock_file_id('A'),
+				MB,
+				file_fee,
+				StoreOptions { class: StorageClass::Cold, ..Default::default() }
+			));
+
+			// Ignored when node does not serve cold files
+			assert_eq!(NodeClasses::<Test>::get(2), vec![StorageClass::Hot]);
+			assert_ok!(MockData::new(0, 3, 10 * MB, &[('A', MB)]).report_data(0).call(2));
+			assert_file!(mock_file_id('A'), replicas: vec![], liquidate_at: 0);
+			assert_node!(2, used: 0, cold_used: 0);
+
+			assert_err!(
+				FileStorage::set_node_classes(Origin::signed(2), vec![]),
+				Error::<Test>::NoStorageClass
+			);
+			assert_err!(
+				FileStorage::set_node_classes(Origin::signed(3), vec![StorageClass::Cold]),
+				Error::<Test>::NodeNotStashed
+			);
+			assert_ok!(FileStorage::set_node_classes(
+				Origin::signed(2),
+				vec![StorageClass::Cold, StorageClass::Hot, StorageClass::Cold]
+			));
+			assert_last_pallet_event!(PalletEvent::NodeClassesSet {
+				controller: 2,
+				classes: vec![StorageClass::Hot, StorageClass::Cold],
+			});
+
+			run_to_block(11);
+			assert_ok!(MockData::new(3, 4, 10 * MB, &[('A', MB)]).report_data(0).call(2));
+			assert_file!(mock_file_id('A'), replicas: vec![2], fee: 40, liquidate_at: 71);
+			assert_node!(2, used: MB, cold_used: MB);
+			assert_summary!(1, used: MB2, cold_used: MB2);
+		})
+}
+
+#[test]
+fn set_node_tag_works() {
+	ExtBuilder::default().stash(1, 2).build().execute_with(|| {
+		assert_err!(
+			FileStorage::set_node_tag(Origin::signed(3), b"eu".to_vec(), b"op".to_vec()),
+			Error::<Test>::NodeNotStashed
+		);
+		assert_err!(
+			FileStorage::set_node_tag(Origin::signed(2), vec![b'a'; 17], b"op".to_vec()),
+			Error::<Test>::TagTooLong
+		);
+		assert_ok!(FileStorage::set_node_tag(Origin::signed(2), b"eu".to_vec(), b"op".to_vec()));
+		let tag = NodeTag { region: b"eu".to_vec(), operator: b"op".to_vec(), attested: false };
+		assert_eq!(NodeTags::<Test>::get(2), Some(tag.clone()));
+		assert_last_pallet_event!(PalletEvent::NodeTagSet { controller: 2, tag: Some(tag) });
+
+		assert_err!(
+			FileStorage::force_set_node_tag(
+				Origin::signed(2),
+				2,
+				Some((b"us".to_vec(), b"op".to_vec()))
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(FileStorage::force_set_node_tag(
+			Origin::signed(1),
+			2,
+			Some((b"us".to_vec(), b"op".to_vec()))
+		));
+		assert_eq!(NodeTags::<Test>::get(2).map(|v| v.attested), Some(true));
+
+		// Failed when tag is attested
+		assert_err!(
+			FileStorage::set_node_tag(Origin::signed(2), b"eu".to_vec(), b"op".to_vec()),
+			Error::<Test>::TagAttested
+		);
+
+		assert_ok!(FileStorage::force_set_node_tag(Origin::signed(1), 2, None));
+		assert_eq!(NodeTags::<Test>::get(2), None);
+		assert_last_pallet_event!(PalletEvent::NodeTagSet { controller: 2, tag: None });
+	})
+}
+
+#[test]
+fn report_keeps_replicas_diverse() {
+	ExtBuilder::default()
+		.stash(1, 2)
+		.stash(1, 3)
+		.register(2, MACHINES[0].register_data())
+		.register(3, MACHINES[1].register_data())
+		.files(vec![(mock_file_id('A'), MB, 1100)])
+		.max_replicas_per(1, 1)
+		.build()
+		.execute_with(|| {
+			assert_ok!(MockData::new(0, 3, 10 * MB, &[('A', MB)]).report_data(0).call(2));
+			assert_file!(mock_file_id('A'), replicas: vec![2]);
+
+			// Skipped when replica of same operator exists, self-declared tag doesn't count
+			assert_ok!(FileStorage::set_node_tag(
+				Origin::signed(3),
+				b"us".to_vec(),
+				b"op3".to_vec()
+			));
+			assert_ok!(MockData::new(0, 3, 10 * MB, &[('A', MB)]).report_data(1).call(3));
+			assert_file!(mock_file_id('A'), replicas: vec![2]);
+			assert_node!(3, used: 0);
+
+			assert_ok!(FileStorage::force_set_node_tag(
+				Origin::signed(1),
+				3,
+				Some((b"eu".to_vec(), b"op3".to_vec()))
+			));
+			run_to_block(11);
+			assert_ok!(MockData::new(3, 4, 10 * MB, &[('A', MB)]).report_data(1).call(3));
+			assert_file!(mock_file_id('A'), replicas: vec![2, 3]);
+			assert_eq!(
+				FileStorage::file_diversity(&mock_file_id('A')),
+				Some(FileDiversityInfo { replicas: 2, regions: 1, operators: 2 })
+			);
+			assert_eq!(FileStorage::file_diversity(&mock_file_id('B')), None);
+		})
+}
+
+#[test]
+fn report_stores_file_by_size_quorum() {
+	ExtBuilder::default()
+		.stash(1, 2)
+		.stash(11, 3)
+		.register(2, MACHINES[0].register_data())
+		.register(3, MACHINES[1].register_data())
+		.files(vec![(mock_file_id('A'), MB, 1100)])
+		.reports(vec![(
+			6,
+			MACHINES[3].register_data(),
+			MockData::new(0, 3, 10 * MB, &[]).report_data(3),
+		)])
+		.file_size_quorum(2)
+		.build()
+		.execute_with(|| {
+			assert_ok!(MockData::new(0, 3, 10 * MB, &[('A', MB)]).report_data(0).call(2));
+			assert_ok!(MockData::new(0, 3, 10 * MB, &[('A', 2 * MB)]).report_data(1).call(3));
+			assert_file!(mock_file_id('A'), replicas: vec![], liquidate_at: 0, file_size: MB);
+			assert_eq!(FileSizeVotes::<Test>::get(&mock_file_id('A')), vec![(2, MB), (3, 2 * MB)]);
+			assert_node!(2, used: 0);
+
+			run_to_block(11);
+			assert_ok!(MockData::new(3, 4, 10 * MB, &[('A', MB)]).report_data(3).call(6));
+			assert_file!(mock_file_id('A'), replicas: vec![2, 6], file_size: MB);
+			assert_eq!(FileSizeVotes::<Test>::get(&mock_file_id('A')), vec![]);
+			assert_node!(2, used: MB);
+			assert_node!(6, used: MB);
+			assert_node!(3, used: 0);
+			assert!(frame_system::Pallet::<Test>::events().iter().any(|e| e.event ==
+				mock::Event::FileStorage(PalletEvent::FileSizeDisputed {
+					cid: mock_file_id('A'),
+					reporter: 3,
+					reported: 2 * MB,
+					confirmed: MB,
+				})));
+		})
+}
+
+#[test]
+fn size_quorum_counts_distinct_stashes() {
+	ExtBuilder::default()
+		.stash(1, 2)
+		.stash(1, 3)
+		.register(2, MACHINES[0].register_data())
+		.register(3, MACHINES[1].register_data())
+		.files(vec![(mock_file_id('A'), MB, 1100)])
+		.file_size_quorum(2)
+		.build()
+		.execute_with(|| {
+			assert_ok!(FileStorage::force_set_node_tag(
+				Origin::signed(1),
+				2,
+				Some((b"eu".to_vec(), b"op2".to_vec()))
+			));
+			assert_ok!(FileStorage::force_set_node_tag(
+				Origin::signed(1),
+				3,
+				Some((b"us".to_vec(), b"op3".to_vec()))
+			));
+			assert_ok!(MockData::new(0, 3, 10 * MB, &[('A', MB)]).report_data(0).call(2));
+			assert_ok!(MockData::new(0, 3, 10 * MB, &[('A', MB)]).report_data(1).call(3));
+			assert_file!(mock_file_id('A'), replicas: vec![], liquidate_at: 0);
+			assert_eq!(FileSizeVotes::<Test>::get(&mock_file_id('A')), vec![(2, MB), (3, MB)]);
+		})
+}
+
+#[test]
+fn refund_unconfirmed_file() {
+	ExtBuilder::default().build().execute_with(|| {
+		let cid = mock_file_id('A');
+		assert_ok!(FileStorage::fund_sponsorship(Origin::signed(1), 3000));
+		assert_ok!(FileStorage::set_beneficiary(Origin::signed(1), 100, true));
+		assert_ok!(FileStorage::store(Origin::signed(1000), cid.clone(), MB, 1100));
+		assert_ok!(FileStorage::store_with_options(
+			Origin::signed(100),
+			cid.clone(),
+			MB,
+			10,
+			StoreOptions { sponsor: Some(1), ..Default::default() }
+		));
+		assert_eq!(FilePayments::<Test>::get(&cid), vec![(1000, 1100), (1, 10)]);
+
+		run_to_block(31);
+		assert_err!(
+			FileStorage::refund_file(Origin::signed(11), cid.clone()),
+			Error::<Test>::UnableToRefundFile
+		);
+		run_to_block(32);
+		let u1000 = Balances::free_balance(1000);
+		let u1 = Balances::free_balance(1);
+		let pot = balance_of_storage_pot();
+		assert_ok!(FileStorage::refund_file(Origin::signed(11), cid.clone()));
+		assert_last_pallet_event!(PalletEvent::FileRefunded { cid: cid.clone(), amount: 1110 });
+		assert_eq!(Balances::free_balance(1000), u1000 + 1100);
+		assert_eq!(Balances::free_balance(1), u1 + 10);
+		assert_eq!(balance_of_storage_pot(), pot - 1110);
+		assert!(!Files::<Test>::contains_key(&cid));
+		assert!(!FilePayments::<Test>::contains_key(&cid));
+		assert_eq!(AccountUsages::<Test>::get(1000).files, 0);
+		assert_err!(
+			FileStorage::refund_file(Origin::signed(11), cid.clone()),
+			Error::<Test>::UnableToRefundFile
+		);
+	})
+}
+
+#[test]
+fn heartbeat_works() {
+	ExtBuilder::default()
+		.stash(1, 2)
+		.register(2, MACHINES[0].register_data())
+		.build()
+		.execute_with(|| {
+			let session = Session::<Test>::get().current;
+			assert_err!(
+				FileStorage::heartbeat(
+					Origin::signed(2),
+					session,
+					MACHINES[0].heartbeat_sig(session)
+				),
+				Error::<Test>::ReportRequired
+			);
+			assert_ok!(MockData::new(0, 3, 10 * MB, &[]).report_data(0).call(2));
+			assert_err!(
+				FileStorage::heartbeat(
+					Origin::signed(2),
+					session,
+					MACHINES[0].heartbeat_sig(session)
+				),
+				Error::<Test>::DuplicateReport
+			);
+
+			run_to_block(11);
+			let session = Session::<Test>::get().current;
+			assert_err!(
+				FileStorage::heartbeat(
+					Origin::signed(2),
+					session + 1,
+					MACHINES[0].heartbeat_sig(session + 1)
+				),
+				Error::<Test>::InvalidSession
+			);
+			assert_err!(
+				FileStorage::heartbeat(
+					Origin::signed(2),
+					session,
+					MACHINES[1].heartbeat_sig(session)
+				),
+				Error::<Test>::InvalidVerifyP256Sig
+			);
+			let mut short_sig = MACHINES[0].heartbeat_sig(session);
+			short_sig.truncate(31);
+			assert_err!(
+				FileStorage::heartbeat(Origin::signed(2), session, short_sig),
+				Error::<Test>::InvalidVerifyP256Sig
+			);
+			let deposit = Nodes::<Test>::get(2).unwrap().deposit;
+			Summarys::<Test>::mutate(session - 1, |summary| summary.mine_reward = 100);
+			assert_ok!(FileStorage::heartbeat(
+				Origin::signed(2),
+				session,
+				MACHINES[0].heartbeat_sig(session),
+			));
+			assert_last_pallet_event!(PalletEvent::NodeHeartbeat {
+				controller: 2,
+				machine_id: MACHINES[0].get_machine_id(),
+				session,
+				mine_reward: 100,
+				share_store_reward: 0,
+			});
+			assert_node!(2, reported_at: 11, prev_reported_at: 1, rid: 3);
+			assert_eq!(Summarys::<Test>::get(session).count, 1);
+			// Previous session is settled by heartbeat
+			assert_eq!(Summarys::<Test>::get(session - 1).paid_mine_reward, 100);
+			assert_eq!(PendingRewards::<Test>::get(2).free, 100);
+
+			// Not slashed in next report
+			run_to_block(21);
+			assert_ok!(MockData::new(3, 4, 10 * MB, &[]).report_data(0).call(2));
+			assert_node!(2, deposit: deposit, reported_at: 21);
+
+			// Node should report after missed a session
+			run_to_block(41);
+			let session = Session::<Test>::get().current;
+			assert_err!(
+				FileStorage::heartbeat(
+					Origin::signed(2),
+					session,
+					MACHINES[0].heartbeat_sig(session)
+				),
+				Error::<Test>::ReportRequired
+			);
+		})
+}
+
+#[test]
+fn check_storage_report_works() {
+	ExtBuilder::default()
+		.stash(1, 2)
+		.register(2, MACHINES[0].register_data())
+		.build()
+		.execute_with(|| {
+			let report_call = |report_data: ReportData| {
+				mock::Call::FileStorage(pallet::Call::report {
+					rid: report_data.rid,
+					power: report_data.power,
+					sig: report_data.sig,
+					add_files: report_data.add_files,
+					del_files: report_data.del_files,
+					liquidate_fils: report_data.liquidate_files,
+				})
+			};
+			let check = CheckStorageReport::<Test>::new();
+			let call = report_call(MockData::new(0, 3, 10 * MB, &[]).report_data(0));
+			let info = call.get_dispatch_info();
+			assert_eq!(info.pays_fee, Pays::No);
+			let validity = check.validate(&2, &call, &info, 0).unwrap();
+			assert_eq!(validity.priority, ReportPriority::get());
+			assert_eq!(validity.longevity, 10);
+
+			// Rejected when signed by another machine or node
+			let bad_call = report_call(MockData::new(0, 3, 10 * MB, &[]).report_data(1));
+			assert_eq!(
+				check.validate(&2, &bad_call, &info, 0),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::BadProof))
+			);
+			assert_eq!(
+				check.validate(&3, &call, &info, 0),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::Call))
+			);
+
+			// Rejected rather than panic when signature is too short
+			let mut short_sig = MockData::new(0, 3, 10 * MB, &[]).report_data(0);
+			short_sig.sig.truncate(31);
+			let short_sig = report_call(short_sig);
+			assert_eq!(
+				check.validate(&2, &short_sig, &info, 0),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::BadProof))
+			);
+
+			// Signature is only verified in pool, dispatch verifies it again
+			assert_ok!(check.clone().pre_dispatch(&2, &bad_call, &info, 0));
+
+			// Rejected when any file list exceeds limit
+			let mut too_many = MockData::new(0, 3, 10 * MB, &[]).report_data(0);
+			too_many.del_files = (0..=MaxReportFiles::get()).map(|_| mock_file_id('A')).collect();
+			let too_many = report_call(too_many);
+			assert_eq!(
+				check.validate(&2, &too_many, &info, 0),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::Call))
+			);
+			assert_eq!(
+				check.clone().pre_dispatch(&2, &too_many, &info, 0),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::Call))
+			);
+
+			// Rejected when reported in current session
+			assert_ok!(MockData::new(0, 3, 10 * MB, &[]).report_data(0).call(2));
+			assert_eq!(
+				check.validate(&2, &call, &info, 0),
+				Err(TransactionValidityError::Invalid(InvalidTransaction::Stale))
+			);
+
+			run_to_block(11);
+			let session = Session::<Test>::get().current;
+			let call = mock::Call::FileStorage(pallet::Call::heartbeat {
+				session,
+				sig: MACHINES[0].heartbeat_sig(session),
+			});
+			let info = call.get_dispatch_info();
+			assert_eq!(info.pays_fee, Pays::No);
+			assert_ok!(check.validate(&2, &call, &info, 0));
+
+			// Other calls are not checked
+			let call = mock::Call::FileStorage(pallet::Call::set_node_classes {
+				classes: vec![StorageClass::Hot],
+			});
+			assert_ok!(check.clone().pre_dispatch(&3, &call, &call.get_dispatch_info(), 0));
+		})
+}
+
+#[test]
+fn store_sponsored_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let u1000 = Balances::free_balance(&1000);
+		let file_fee = FileStorage::store_file_fee(MB, StorageClass::Hot);
+		assert_eq!(file_fee, 1100);
+		assert_err!(
+			FileStorage::store_with_options(
+				Origin::signed(1000),
+				mock_file_id('A'),
+				MB,
+				file_fee,
+				StoreOptions { sponsor: Some(1), ..Default::default() }
+			),
+			Error::<Test>::NotSponsored
+		);
+
+		assert_ok!(FileStorage::fund_sponsorship(Origin::signed(1), 3000));
+		assert_last_pallet_event!(PalletEvent::SponsorshipFunded { sponsor: 1, amount: 3000 });
+		assert_ok!(FileStorage::set_sponsorship_caps(Origin::signed(1), 2000, 3000, 10));
+		assert_ok!(FileStorage::set_beneficiary(Origin::signed(1), 1000, true));
+		assert_last_pallet_event!(PalletEvent::BeneficiarySet {
+			sponsor: 1,
+			beneficiary: 1000,
+			allowed: true
+		});
+
+		let pot = balance_of_storage_pot();
+		assert_ok!(FileStorage::store_with_options(
+			Origin::signed(1000),
+			mock_file_id('A'),
+			MB,
+			file_fee,
+			StoreOptions { sponsor: Some(1), ..Default::default() }
+		));
+		assert!(frame_system::Pallet::<Test>::events().iter().any(|e| e.event ==
+			mock::Event::FileStorage(PalletEvent::FileSponsored {
+				cid: mock_file_id('A'),
+				sponsor: 1,
+				beneficiary: 1000,
+				fee: file_fee,
+			})));
+		assert_eq!(Balances::free_balance(&1000), u1000);
+		assert_eq!(balance_of_storage_pot(), pot);
+		assert_eq!(Sponsorships::<Test>::get(1).unwrap().budget, 1900);
+
+		// Failed when exceed per file cap
+		assert_err!(
+			FileStorage::store_with_options(
+				Origin::signed(1000),
+				mock_file_id('B'),
+				MB,
+				2100,
+				StoreOptions { sponsor: Some(1), ..Default::default() }
+			),
+			Error::<Test>::SponsorshipCapExceeded
+		);
+		assert_ok!(FileStorage::store_with_options(
+			Origin::signed(1000),
+			mock_file_id('B'),
+			MB,
+			file_fee,
+			StoreOptions { sponsor: Some(1), ..Default::default() }
+		));
+
+		// Failed when exceed per period cap
+		assert_err!(
+			FileStorage::store_with_options(
+				Origin::signed(1000),
+				mock_file_id('C'),
+				MB,
+				file_fee,
+				StoreOptions { sponsor: Some(1), ..Default::default() }
+			),
+			Error::<Test>::SponsorshipCapExceeded
+		);
+
+		// Failed when budget is not enough in next period
+		run_to_block(11);
+		assert_err!(
+			FileStorage::store_with_options(
+				Origin::signed(1000),
+				mock_file_id('C'),
+				MB,
+				file_fee,
+				StoreOptions { sponsor: Some(1), ..Default::default() }
+			),
+			Error::<Test>::InsufficientSponsorship
+		);
+		assert_eq!(
+			FileStorage::sponsorship_budget(&1),
+			SponsorshipBudgetInfo { budget: 800, period_remaining: 800, max_per_file: 2000 }
+		);
+
+		let u1 = Balances::free_balance(&1);
+		assert_err!(
+			FileStorage::withdraw_sponsorship(Origin::signed(1), 900),
+			Error::<Test>::NoEnoughToWithdraw
+		);
+		assert_ok!(FileStorage::withdraw_sponsorship(Origin::signed(1), 800));
+		assert_eq!(Balances::free_balance(&1), u1 + 800);
+
+		assert_ok!(FileStorage::set_beneficiary(Origin::signed(1), 1000, false));
+		assert_err!(
+			FileStorage::store_with_options(
+				Origin::signed(1000),
+				mock_file_id('A'),
+				MB,
+				10,
+				StoreOptions { sponsor: Some(1), ..Default::default() }
+			),
+			Error::<Test>::NotSponsored
+		);
+	})
+}
+
+#[test]
+fn store_sponsored_without_per_file_cap_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let file_fee = FileStorage::store_file_fee(MB, StorageClass::Hot);
+		assert_ok!(FileStorage::fund_sponsorship(Origin::signed(1), 3000));
+		assert_ok!(FileStorage::set_beneficiary(Origin::signed(1), 1000, true));
+		assert_ok!(FileStorage::set_sponsorship_caps(Origin::signed(1), 0, 0, 0));
+		assert_ok!(FileStorage::store_with_options(
+			Origin::signed(1000),
+			mock_file_id('A'),
+			MB,
+			file_fee,
+			StoreOptions { sponsor: Some(1), ..Default::default() }
+		));
+		assert_eq!(Sponsorships::<Test>::get(1).unwrap().budget, 3000 - file_fee);
+	})
+}
+
+#[test]
+fn account_usage_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(FileStorage::set_account_quota(Origin::root(), 1000, Some(MB + 100)));
+		assert_last_pallet_event!(PalletEvent::AccountQuotaSet {
+			who: 1000,
+			quota: Some(MB + 100)
+		});
+		assert_ok!(FileStorage::store(Origin::signed(1000), mock_file_id('A'), MB, 1100));
+		assert_err!(
+			FileStorage::store(Origin::signed(1000), mock_file_id('B'), MB, 1100),
+			Error::<Test>::QuotaExceeded
+		);
+
+		// File is counted once per account, bytes are counted in proportion to fee paid
+		assert_ok!(FileStorage::store(Origin::signed(1000), mock_file_id('A'), MB, 10));
+		assert_ok!(FileStorage::store(Origin::signed(100), mock_file_id('A'), MB, 10));
+		assert_eq!(
+			FileStorage::account_usage(&1000),
+			AccountUsageInfo { files: 1, bytes: MB, paid: 1110, quota: Some(MB + 100) }
+		);
+		assert_eq!(
+			FileStorage::account_usage(&100),
+			AccountUsageInfo { files: 1, bytes: MB * 10 / 1100, paid: 10, quota: None }
+		);
+		assert_err!(
+			FileStorage::store(Origin::signed(11), mock_file_id('A'), MB, 10),
+			Error::<Test>::TooManyFileOwners
+		);
+
+		assert_ok!(FileStorage::set_account_quota(Origin::root(), 1000, None));
+		assert_ok!(FileStorage::store(Origin::signed(1000), mock_file_id('B'), MB, 1100));
+		assert_eq!(
+			AccountUsages::<Test>::get(1000),
+			AccountUsage { files: 2, bytes: 2 * MB, paid: 2210 }
+		);
+
+		// Deleted file is released from owners' usage
+		run_to_block(32);
+		assert_ok!(FileStorage::force_delete(Origin::root(), mock_file_id('A')));
+		assert_eq!(
+			AccountUsages::<Test>::get(1000),
+			AccountUsage { files: 1, bytes: MB, paid: 2210 }
+		);
+		assert_eq!(AccountUsages::<Test>::get(100), AccountUsage { files: 0, bytes: 0, paid: 10 });
+		assert!(!FileOwners::<Test>::contains_key(&mock_file_id('A')));
+	})
+}
+
+#[test]
+fn runtime_upgrade_reaches_latest_release() {
+	ExtBuilder::default().build().execute_with(|| {
+		StorageVersion::<Test>::put(Releases::V1);
+		migrations::v1::V1Nodes::<Test>::insert(
+			1000,
+			migrations::v5::OldNodeInfo {
+				stash: 1,
+				deposit: 100,
+				machine_id: Some(vec![1]),
+				rid: 3,
+				used: 0,
+				power: 0,
+				slash_used: 0,
+				reward: 0,
+				reported_at: 0,
+				prev_reported_at: 0,
+			},
+		);
+		frame_support::storage::unhashed::put(
+			&Registers::<Test>::hashed_key_for(vec![1]),
+			&migrations::v6::OldRegisterInfo { key: vec![2], enclave: vec![3] },
+		);
+
+		FileStorage::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::<Test>::get(), Releases::V6);
+		assert_node!(1000, stash: 1, deposit: 100, rid: 3, cold_used: 0);
+		assert_eq!(StashPools::<Test>::get(1).unwrap().controllers, vec![1000]);
+		assert!(TrustAnchors::<Test>::contains_key(IAS_ROOT_CA_SPKI.to_vec()));
+		assert!(AttestationDeadlines::<Test>::contains_key(vec![1]));
+		let register = Registers::<Test>::get(vec![1]).unwrap();
+		assert_eq!(register.key, vec![2]);
+		assert_eq!(register.enclave, vec![3]);
+	});
+}
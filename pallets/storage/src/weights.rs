@@ -52,6 +52,32 @@ pub trait WeightInfo {
 	fn store() -> Weight;
 	fn force_delete() -> Weight;
 	fn session_end() -> Weight;
+	fn set_commission() -> Weight;
+	fn delegate() -> Weight;
+	fn undelegate() -> Weight;
+	fn withdraw_unbonded() -> Weight;
+	fn claim_delegation_rewards() -> Weight;
+	fn set_payee() -> Weight;
+	fn claim_rewards() -> Weight;
+	fn deposit_pool() -> Weight;
+	fn withdraw_pool() -> Weight;
+	fn claim_stash_rewards(c: u32, ) -> Weight;
+	fn challenge_replica() -> Weight;
+	fn answer_challenge() -> Weight;
+	fn settle_challenge() -> Weight;
+	fn set_attestation_policy() -> Weight;
+	fn set_quote_policy(s: u32, a: u32, ) -> Weight;
+	fn set_trust_anchor() -> Weight;
+	fn set_node_classes() -> Weight;
+	fn set_node_tag() -> Weight;
+	fn force_set_node_tag() -> Weight;
+	fn heartbeat() -> Weight;
+	fn fund_sponsorship() -> Weight;
+	fn withdraw_sponsorship() -> Weight;
+	fn set_sponsorship_caps() -> Weight;
+	fn set_beneficiary() -> Weight;
+	fn set_account_quota() -> Weight;
+	fn refund_file(c: u32, ) -> Weight;
 }
 
 /// Weights for pallet_storage using the Substrate node and recommended hardware.
@@ -78,16 +104,20 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: FileStorage Registers (r:1 w:1)
 	// Storage: FileStorage Nodes (r:1 w:1)
 	// Storage: FileStorage Enclaves (r:1 w:0)
+	// Storage: FileStorage TrustAnchors (r:1 w:0)
+	// Storage: FileStorage AttestationDeadlines (r:0 w:1)
 	fn register() -> Weight {
 		(4_071_520_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	// Storage: FileStorage Nodes (r:1 w:1)
 	// Storage: FileStorage Registers (r:1 w:0)
 	// Storage: FileStorage Enclaves (r:1 w:0)
+	// Storage: FileStorage AttestationDeadlines (r:1 w:0)
 	// Storage: FileStorage Session (r:1 w:0)
 	// Storage: FileStorage Files (r:15 w:9)
+	// Storage: FileStorage FileSizeVotes (r:15 w:15)
 	// Storage: FileStorage Summarys (r:1 w:1)
 	// Storage: FileStorage StoragePotReserved (r:1 w:1)
 	fn report(x: u32, y: u32, ) -> Weight {
@@ -96,25 +126,34 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add((13_807_000 as Weight).saturating_mul(x as Weight))
 			// Standard Error: 149_000
 			.saturating_add((8_618_000 as Weight).saturating_mul(y as Weight))
-			.saturating_add(T::DbWeight::get().reads(6 as Weight))
-			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(x as Weight)))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(x as Weight)))
 			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(y as Weight)))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(x as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(x as Weight)))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(y as Weight)))
 	}
 	// Storage: FileStorage Files (r:1 w:1)
+	// Storage: FileStorage SponsoredAccounts (r:1 w:0)
+	// Storage: FileStorage Sponsorships (r:1 w:1)
+	// Storage: FileStorage AccountUsages (r:1 w:1)
+	// Storage: FileStorage FileOwners (r:1 w:1)
+	// Storage: FileStorage AccountQuotas (r:1 w:0)
+	// Storage: FileStorage FilePayments (r:1 w:1)
 	fn store() -> Weight {
-		(46_333_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+		(65_308_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
 	// Storage: FileStorage Files (r:1 w:1)
 	// Storage: FileStorage StoragePotReserved (r:1 w:1)
+	// Storage: FileStorage FileSizeVotes (r:0 w:1)
+	// Storage: FileStorage FileOwners (r:1 w:1)
+	// Storage: FileStorage AccountUsages (r:1 w:1)
 	fn force_delete() -> Weight {
-		(25_493_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+		(34_817_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
 	// Storage: FileStorage Session (r:1 w:1)
 	// Storage: FileStorage Summarys (r:2 w:2)
@@ -124,6 +163,225 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// Storage: FileStorage Nodes (r:1 w:0)
+	// Storage: FileStorage Backings (r:1 w:1)
+	fn set_commission() -> Weight {
+		(18_312_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage Nodes (r:1 w:0)
+	// Storage: FileStorage Backings (r:1 w:1)
+	// Storage: FileStorage Delegations (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn delegate() -> Weight {
+		(52_164_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: FileStorage Delegations (r:1 w:1)
+	// Storage: FileStorage Backings (r:1 w:1)
+	// Storage: FileStorage Nodes (r:1 w:1)
+	// Storage: FileStorage StashPools (r:1 w:1)
+	// Storage: FileStorage Unbondings (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	fn undelegate() -> Weight {
+		(44_519_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: System Number (r:1 w:0)
+	// Storage: FileStorage Unbondings (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn withdraw_unbonded() -> Weight {
+		(45_216_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: FileStorage Delegations (r:1 w:1)
+	// Storage: FileStorage Backings (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn claim_delegation_rewards() -> Weight {
+		(47_935_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: FileStorage Nodes (r:1 w:0)
+	// Storage: FileStorage Payees (r:0 w:1)
+	fn set_payee() -> Weight {
+		(17_604_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage Nodes (r:1 w:0)
+	// Storage: FileStorage PendingRewards (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: FileStorage Payees (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn claim_rewards() -> Weight {
+		(51_382_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: System Account (r:2 w:2)
+	// Storage: FileStorage StashPools (r:1 w:1)
+	fn deposit_pool() -> Weight {
+		(43_720_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: FileStorage StashPools (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn withdraw_pool() -> Weight {
+		(44_105_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: FileStorage StashPools (r:1 w:0)
+	// Storage: FileStorage Payees (r:1 w:0)
+	// Storage: FileStorage PendingRewards (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn claim_stash_rewards(c: u32, ) -> Weight {
+		(40_593_000 as Weight)
+			// Standard Error: 12_000
+			.saturating_add((9_214_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: FileStorage Files (r:1 w:0)
+	// Storage: FileStorage Challenges (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	fn challenge_replica() -> Weight {
+		(39_817_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: FileStorage Challenges (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: FileStorage Nodes (r:1 w:0)
+	// Storage: FileStorage Registers (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: FileStorage PendingRewards (r:1 w:1)
+	fn answer_challenge() -> Weight {
+		(171_406_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: FileStorage Challenges (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System Account (r:3 w:3)
+	// Storage: FileStorage Files (r:1 w:1)
+	// Storage: FileStorage Nodes (r:1 w:1)
+	// Storage: FileStorage Backings (r:1 w:1)
+	// Storage: FileStorage PendingRewards (r:1 w:1)
+	// Storage: FileStorage StoragePotReserved (r:1 w:1)
+	fn settle_challenge() -> Weight {
+		(79_843_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: FileStorage AttestationPolicies (r:0 w:1)
+	fn set_attestation_policy() -> Weight {
+		(18_106_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage AllowedQuoteStatuses (r:0 w:1)
+	// Storage: FileStorage AllowedAdvisoryIds (r:0 w:1)
+	fn set_quote_policy(s: u32, a: u32, ) -> Weight {
+		(17_329_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((412_000 as Weight).saturating_mul(s as Weight))
+			// Standard Error: 2_000
+			.saturating_add((405_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: FileStorage TrustAnchors (r:0 w:1)
+	fn set_trust_anchor() -> Weight {
+		(17_852_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage Nodes (r:1 w:0)
+	// Storage: FileStorage NodeClasses (r:0 w:1)
+	fn set_node_classes() -> Weight {
+		(21_937_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage Nodes (r:1 w:0)
+	// Storage: FileStorage NodeTags (r:1 w:1)
+	fn set_node_tag() -> Weight {
+		(24_518_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage NodeTags (r:0 w:1)
+	fn force_set_node_tag() -> Weight {
+		(18_930_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage Nodes (r:1 w:1)
+	// Storage: FileStorage Registers (r:1 w:0)
+	// Storage: FileStorage Enclaves (r:1 w:0)
+	// Storage: FileStorage AttestationDeadlines (r:1 w:0)
+	// Storage: FileStorage Session (r:1 w:0)
+	// Storage: FileStorage Summarys (r:2 w:2)
+	// Storage: FileStorage Backings (r:1 w:1)
+	// Storage: FileStorage PendingRewards (r:1 w:1)
+	fn heartbeat() -> Weight {
+		(1_224_871_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: FileStorage Sponsorships (r:1 w:1)
+	fn fund_sponsorship() -> Weight {
+		(38_762_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: FileStorage Sponsorships (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_sponsorship() -> Weight {
+		(37_915_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: FileStorage Sponsorships (r:1 w:1)
+	fn set_sponsorship_caps() -> Weight {
+		(19_402_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage SponsoredAccounts (r:0 w:1)
+	fn set_beneficiary() -> Weight {
+		(16_318_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage AccountQuotas (r:0 w:1)
+	fn set_account_quota() -> Weight {
+		(15_402_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage Files (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: FileStorage FilePayments (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: FileStorage FileSizeVotes (r:0 w:1)
+	// Storage: FileStorage FileChunks (r:0 w:1)
+	// Storage: FileStorage FileOwners (r:1 w:1)
+	fn refund_file(c: u32, ) -> Weight {
+		(31_402_000 as Weight)
+			// Standard Error: 15_000
+			.saturating_add((21_385_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -149,16 +407,20 @@ impl WeightInfo for () {
 	// Storage: FileStorage Registers (r:1 w:1)
 	// Storage: FileStorage Nodes (r:1 w:1)
 	// Storage: FileStorage Enclaves (r:1 w:0)
+	// Storage: FileStorage TrustAnchors (r:1 w:0)
+	// Storage: FileStorage AttestationDeadlines (r:0 w:1)
 	fn register() -> Weight {
 		(4_071_520_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
 	// Storage: FileStorage Nodes (r:1 w:1)
 	// Storage: FileStorage Registers (r:1 w:0)
 	// Storage: FileStorage Enclaves (r:1 w:0)
+	// Storage: FileStorage AttestationDeadlines (r:1 w:0)
 	// Storage: FileStorage Session (r:1 w:0)
 	// Storage: FileStorage Files (r:15 w:9)
+	// Storage: FileStorage FileSizeVotes (r:15 w:15)
 	// Storage: FileStorage Summarys (r:1 w:1)
 	// Storage: FileStorage StoragePotReserved (r:1 w:1)
 	fn report(x: u32, y: u32, ) -> Weight {
@@ -167,25 +429,34 @@ impl WeightInfo for () {
 			.saturating_add((13_807_000 as Weight).saturating_mul(x as Weight))
 			// Standard Error: 149_000
 			.saturating_add((8_618_000 as Weight).saturating_mul(y as Weight))
-			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
-			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(x as Weight)))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(x as Weight)))
 			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(y as Weight)))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
-			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(x as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(x as Weight)))
 			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(y as Weight)))
 	}
 	// Storage: FileStorage Files (r:1 w:1)
+	// Storage: FileStorage SponsoredAccounts (r:1 w:0)
+	// Storage: FileStorage Sponsorships (r:1 w:1)
+	// Storage: FileStorage AccountUsages (r:1 w:1)
+	// Storage: FileStorage FileOwners (r:1 w:1)
+	// Storage: FileStorage AccountQuotas (r:1 w:0)
+	// Storage: FileStorage FilePayments (r:1 w:1)
 	fn store() -> Weight {
-		(46_333_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+		(65_308_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
 	}
 	// Storage: FileStorage Files (r:1 w:1)
 	// Storage: FileStorage StoragePotReserved (r:1 w:1)
+	// Storage: FileStorage FileSizeVotes (r:0 w:1)
+	// Storage: FileStorage FileOwners (r:1 w:1)
+	// Storage: FileStorage AccountUsages (r:1 w:1)
 	fn force_delete() -> Weight {
-		(25_493_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+		(34_817_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
 	}
 	// Storage: FileStorage Session (r:1 w:1)
 	// Storage: FileStorage Summarys (r:2 w:2)
@@ -195,4 +466,223 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
+	// Storage: FileStorage Nodes (r:1 w:0)
+	// Storage: FileStorage Backings (r:1 w:1)
+	fn set_commission() -> Weight {
+		(18_312_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage Nodes (r:1 w:0)
+	// Storage: FileStorage Backings (r:1 w:1)
+	// Storage: FileStorage Delegations (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn delegate() -> Weight {
+		(52_164_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: FileStorage Delegations (r:1 w:1)
+	// Storage: FileStorage Backings (r:1 w:1)
+	// Storage: FileStorage Nodes (r:1 w:1)
+	// Storage: FileStorage StashPools (r:1 w:1)
+	// Storage: FileStorage Unbondings (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	fn undelegate() -> Weight {
+		(44_519_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: System Number (r:1 w:0)
+	// Storage: FileStorage Unbondings (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn withdraw_unbonded() -> Weight {
+		(45_216_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: FileStorage Delegations (r:1 w:1)
+	// Storage: FileStorage Backings (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn claim_delegation_rewards() -> Weight {
+		(47_935_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: FileStorage Nodes (r:1 w:0)
+	// Storage: FileStorage Payees (r:0 w:1)
+	fn set_payee() -> Weight {
+		(17_604_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage Nodes (r:1 w:0)
+	// Storage: FileStorage PendingRewards (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: FileStorage Payees (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn claim_rewards() -> Weight {
+		(51_382_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: System Account (r:2 w:2)
+	// Storage: FileStorage StashPools (r:1 w:1)
+	fn deposit_pool() -> Weight {
+		(43_720_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: FileStorage StashPools (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn withdraw_pool() -> Weight {
+		(44_105_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: FileStorage StashPools (r:1 w:0)
+	// Storage: FileStorage Payees (r:1 w:0)
+	// Storage: FileStorage PendingRewards (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn claim_stash_rewards(c: u32, ) -> Weight {
+		(40_593_000 as Weight)
+			// Standard Error: 12_000
+			.saturating_add((9_214_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	// Storage: FileStorage Files (r:1 w:0)
+	// Storage: FileStorage Challenges (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	fn challenge_replica() -> Weight {
+		(39_817_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: FileStorage Challenges (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: FileStorage Nodes (r:1 w:0)
+	// Storage: FileStorage Registers (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: FileStorage PendingRewards (r:1 w:1)
+	fn answer_challenge() -> Weight {
+		(171_406_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: FileStorage Challenges (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System Account (r:3 w:3)
+	// Storage: FileStorage Files (r:1 w:1)
+	// Storage: FileStorage Nodes (r:1 w:1)
+	// Storage: FileStorage Backings (r:1 w:1)
+	// Storage: FileStorage PendingRewards (r:1 w:1)
+	// Storage: FileStorage StoragePotReserved (r:1 w:1)
+	fn settle_challenge() -> Weight {
+		(79_843_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: FileStorage AttestationPolicies (r:0 w:1)
+	fn set_attestation_policy() -> Weight {
+		(18_106_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage AllowedQuoteStatuses (r:0 w:1)
+	// Storage: FileStorage AllowedAdvisoryIds (r:0 w:1)
+	fn set_quote_policy(s: u32, a: u32, ) -> Weight {
+		(17_329_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((412_000 as Weight).saturating_mul(s as Weight))
+			// Standard Error: 2_000
+			.saturating_add((405_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: FileStorage TrustAnchors (r:0 w:1)
+	fn set_trust_anchor() -> Weight {
+		(17_852_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage Nodes (r:1 w:0)
+	// Storage: FileStorage NodeClasses (r:0 w:1)
+	fn set_node_classes() -> Weight {
+		(21_937_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage Nodes (r:1 w:0)
+	// Storage: FileStorage NodeTags (r:1 w:1)
+	fn set_node_tag() -> Weight {
+		(24_518_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage NodeTags (r:0 w:1)
+	fn force_set_node_tag() -> Weight {
+		(18_930_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage Nodes (r:1 w:1)
+	// Storage: FileStorage Registers (r:1 w:0)
+	// Storage: FileStorage Enclaves (r:1 w:0)
+	// Storage: FileStorage AttestationDeadlines (r:1 w:0)
+	// Storage: FileStorage Session (r:1 w:0)
+	// Storage: FileStorage Summarys (r:2 w:2)
+	// Storage: FileStorage Backings (r:1 w:1)
+	// Storage: FileStorage PendingRewards (r:1 w:1)
+	fn heartbeat() -> Weight {
+		(1_224_871_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: FileStorage Sponsorships (r:1 w:1)
+	fn fund_sponsorship() -> Weight {
+		(38_762_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: FileStorage Sponsorships (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_sponsorship() -> Weight {
+		(37_915_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: FileStorage Sponsorships (r:1 w:1)
+	fn set_sponsorship_caps() -> Weight {
+		(19_402_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage SponsoredAccounts (r:0 w:1)
+	fn set_beneficiary() -> Weight {
+		(16_318_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage AccountQuotas (r:0 w:1)
+	fn set_account_quota() -> Weight {
+		(15_402_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: FileStorage Files (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: FileStorage FilePayments (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: FileStorage FileSizeVotes (r:0 w:1)
+	// Storage: FileStorage FileChunks (r:0 w:1)
+	// Storage: FileStorage FileOwners (r:1 w:1)
+	fn refund_file(c: u32, ) -> Weight {
+		(31_402_000 as Weight)
+			// Standard Error: 15_000
+			.saturating_add((21_385_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
 }